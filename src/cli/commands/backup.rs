@@ -0,0 +1,31 @@
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes the `.backup <path>` command from the CLI, writing a consistent copy of the open
+/// database to a new file.
+///
+/// The copy is encrypted with the current key of the database, and opens with the same key flags.
+///
+/// # Parameters
+///
+/// - `input`: The command line, `.backup` followed by the path of the backup file.
+/// - `db`: A mutable reference to the [`Nosqlite`] instance to back up.
+///
+/// # Returns
+///
+/// - `Ok(String)` containing a success message if the backup was written.
+/// - `Err(String)` describing the error if the path is missing or the backup fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::backup_to`]
+pub fn handle_backup(input: &str, db: &mut Nosqlite) -> Result<String, String> {
+    let path = input.strip_prefix(".backup").unwrap_or_default().trim();
+    if path.is_empty() {
+        return Err("Usage: .backup <path>".to_string());
+    }
+
+    db.backup_to(path, None)
+        .map(|_| format!("Backup written to '{path}'"))
+        .map_err(|e| format!("Error writing backup, {e}"))
+}
@@ -0,0 +1,35 @@
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes the `.passphrase` command from the CLI, re-encrypting the database with a new passphrase.
+///
+/// This function does the following:
+/// - Prompts twice for the new passphrase on the terminal, without echoing it
+/// - Checks that both entries match
+/// - Calls [`Nosqlite::change_passphrase`] to derive the new key and save the database
+///
+/// # Parameters
+///
+/// - `db`: A mutable reference to the [`Nosqlite`] instance to re-encrypt.
+///
+/// # Returns
+///
+/// - `Ok(String)` containing a success message if the passphrase was changed.
+/// - `Err(String)` describing the error if the prompt fails, the entries differ, or the save fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::change_passphrase`]
+pub fn handle_change_passphrase(db: &mut Nosqlite) -> Result<String, String> {
+    let passphrase = rpassword::prompt_password("New passphrase: ")
+        .map_err(|e| format!("Cannot read passphrase: {e}"))?;
+    let confirmation = rpassword::prompt_password("Confirm new passphrase: ")
+        .map_err(|e| format!("Cannot read passphrase: {e}"))?;
+    if passphrase != confirmation {
+        return Err("Passphrases do not match".to_string());
+    }
+
+    db.change_passphrase(&passphrase)
+        .map(|_| "Passphrase changed successfully".to_string())
+        .map_err(|e| format!("Error changing passphrase, {e}"))
+}
@@ -0,0 +1,54 @@
+use crate::engine::models::ExportFormat;
+use crate::engine::Nosqlite;
+use std::io::BufWriter;
+
+/// 🦀
+/// Executes the `.export <collection> <path> [json|ndjson|csv]` command from the CLI, writing the
+/// documents of a collection to a file.
+///
+/// Without a format, it is guessed from the extension of the path. An existing file is replaced.
+///
+/// # Parameters
+///
+/// - `input`: The command line, `.export` followed by the collection, the path and the format.
+/// - `db`: A mutable reference to the [`Nosqlite`] instance to read from.
+///
+/// # Returns
+///
+/// - `Ok(String)` containing the number of documents written.
+/// - `Err(String)` describing the error if the arguments are invalid or the export fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::export_collection`]
+pub fn handle_export(input: &str, db: &mut Nosqlite) -> Result<String, String> {
+    let args = input.strip_prefix(".export").unwrap_or_default();
+    let (collection, path, format) = parse_transfer_args(args)
+        .ok_or_else(|| "Usage: .export <collection> <path> [json|ndjson|csv]".to_string())?;
+
+    let file = std::fs::File::create(path).map_err(|e| format!("Error creating '{path}', {e}"))?;
+    db.export_collection(collection, format, BufWriter::new(file))
+        .map(|count| format!("{count} document(s) exported to '{path}'"))
+        .map_err(|e| format!("Error exporting collection, {e}"))
+}
+
+/// 🦀
+/// Parses the `<collection> <path> [format]` arguments shared by `.export` and `.import`.
+///
+/// # Returns
+///
+/// - `Some((collection, path, format))`, with the format guessed from the path when omitted.
+/// - `None` if an argument is missing or extra, or the format is unknown.
+pub fn parse_transfer_args(args: &str) -> Option<(&str, &str, ExportFormat)> {
+    let mut parts = args.split_whitespace();
+    let collection = parts.next()?;
+    let path = parts.next()?;
+    let format = match parts.next() {
+        Some(name) => ExportFormat::from_name(name)?,
+        None => ExportFormat::from_path(path)?,
+    };
+    match parts.next() {
+        Some(_) => None,
+        None => Some((collection, path, format)),
+    }
+}
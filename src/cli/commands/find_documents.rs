@@ -13,10 +13,17 @@ use serde_json::Value;
 /// - `input`: Raw CLI command string.
 /// - `db`: Mutable reference to the NoSQLite instance.
 ///
+/// Appending `.explain()` to any of these prints the query plan instead of the documents,
+/// e.g. `db.findDocuments("collection", {filter}).explain()`.
+///
 /// # Returns
-/// - `Ok(String)` with pretty-printed JSON documents.
+/// - `Ok(String)` with pretty-printed JSON documents, or the query plan.
 /// - `Err(String)` on syntax or execution errors.
 pub fn handle_find_documents(input: &str, db: &mut Nosqlite) -> Result<String, String> {
+    if let Some(query) = input.strip_suffix(".explain()") {
+        return handle_explain(query, db);
+    }
+
     let (collection, filter, projection) = parse_find_command_args(input)?;
 
     let docs = db
@@ -35,6 +42,15 @@ pub fn handle_find_documents(input: &str, db: &mut Nosqlite) -> Result<String, S
     Ok(output)
 }
 
+/// Handles `db.findDocuments(...).explain()` by printing the query plan.
+fn handle_explain(input: &str, db: &mut Nosqlite) -> Result<String, String> {
+    let (collection, filter, projection) = parse_find_command_args(input)?;
+
+    db.explain(collection, &filter, &projection)
+        .map(|plan| plan.to_string())
+        .map_err(|e| format!("Error explaining query, {e}"))
+}
+
 /// Parses the CLI arguments for `db.findDocuments(...)`.
 fn parse_find_command_args(input: &str) -> Result<(&str, Value, Value), String> {
     let args = input
@@ -0,0 +1,34 @@
+use crate::cli::commands::export::parse_transfer_args;
+use crate::engine::Nosqlite;
+use std::io::BufReader;
+
+/// 🦀
+/// Executes the `.import <collection> <path> [json|ndjson|csv]` command from the CLI, inserting
+/// the documents of a file into a collection.
+///
+/// Without a format, it is guessed from the extension of the path. Every document is validated
+/// against the collection structure first, so nothing is inserted if one of them is invalid.
+///
+/// # Parameters
+///
+/// - `input`: The command line, `.import` followed by the collection, the path and the format.
+/// - `db`: A mutable reference to the [`Nosqlite`] instance to insert into.
+///
+/// # Returns
+///
+/// - `Ok(String)` containing the number of documents inserted.
+/// - `Err(String)` describing the error if the arguments are invalid or the import fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::import_collection`]
+pub fn handle_import(input: &str, db: &mut Nosqlite) -> Result<String, String> {
+    let args = input.strip_prefix(".import").unwrap_or_default();
+    let (collection, path, format) = parse_transfer_args(args)
+        .ok_or_else(|| "Usage: .import <collection> <path> [json|ndjson|csv]".to_string())?;
+
+    let file = std::fs::File::open(path).map_err(|e| format!("Error opening '{path}', {e}"))?;
+    db.import_collection(collection, format, BufReader::new(file))
+        .map(|count| format!("{count} document(s) imported into '{collection}'"))
+        .map_err(|e| format!("Error importing collection, {e}"))
+}
@@ -5,7 +5,7 @@ use crate::engine::Nosqlite;
 /// retrieving the list of all collections in the database.
 ///
 /// This function does the following:
-/// - Calls [`Nosqlite::list_collections`] to fetch the list of collections
+/// - Calls [`Nosqlite::load_all_collections`] to fetch the list of collections
 /// - Formats the output as a newline-separated string
 /// - Returns a message if no collections are found
 ///
@@ -21,13 +21,15 @@ use crate::engine::Nosqlite;
 ///
 /// # Errors
 ///
-/// - Returns a string describing errors if the operation fails unexpectedly.
+/// - Returns a string describing errors if a collection cannot be loaded from its segment.
 ///
 /// # See Also
 ///
-/// - [`Nosqlite::list_collections`]
+/// - [`Nosqlite::load_all_collections`]
 pub fn handle_list_collections(db: &mut Nosqlite) -> Result<String, String> {
-    let collections = db.list_collections();
+    let collections = db
+        .load_all_collections()
+        .map_err(|e| format!("Failed to list collections: {e}"))?;
     if collections.is_empty() {
         return Ok("No collections found.".to_string());
     }
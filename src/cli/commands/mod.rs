@@ -1,4 +1,11 @@
+pub mod backup;
+pub mod change_passphrase;
 pub mod create_collection;
+pub mod export;
 pub mod find_documents;
+pub mod import;
 pub mod insert_document;
 pub mod list_collections;
+pub mod restore;
+pub mod snapshot;
+pub mod vacuum;
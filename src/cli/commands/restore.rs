@@ -0,0 +1,30 @@
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes the `.restore <name>` command from the CLI, replacing the content of the database with
+/// a named snapshot.
+///
+/// # Parameters
+///
+/// - `input`: The command line, `.restore` followed by the name of the snapshot.
+/// - `db`: A mutable reference to the [`Nosqlite`] instance to restore.
+///
+/// # Returns
+///
+/// - `Ok(String)` containing a success message if the snapshot was restored.
+/// - `Err(String)` describing the error if the name is missing, the snapshot does not exist, or the
+///   save fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::restore_snapshot`]
+pub fn handle_restore(input: &str, db: &mut Nosqlite) -> Result<String, String> {
+    let name = input.strip_prefix(".restore").unwrap_or_default().trim();
+    if name.is_empty() {
+        return Err("Usage: .restore <name>".to_string());
+    }
+
+    db.restore_snapshot(name)
+        .map(|_| format!("Snapshot '{name}' restored successfully"))
+        .map_err(|e| format!("Error restoring snapshot, {e}"))
+}
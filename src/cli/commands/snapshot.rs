@@ -0,0 +1,54 @@
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes the `.snapshot <name>` command from the CLI, saving the current content of the database
+/// as a named snapshot kept next to it.
+///
+/// # Parameters
+///
+/// - `input`: The command line, `.snapshot` followed by the name of the snapshot.
+/// - `db`: A mutable reference to the [`Nosqlite`] instance to snapshot.
+///
+/// # Returns
+///
+/// - `Ok(String)` containing a success message if the snapshot was saved.
+/// - `Err(String)` describing the error if the name is missing or invalid, or the save fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::create_snapshot`]
+pub fn handle_create_snapshot(input: &str, db: &mut Nosqlite) -> Result<String, String> {
+    let name = input.strip_prefix(".snapshot").unwrap_or_default().trim();
+    if name.is_empty() {
+        return Err("Usage: .snapshot <name>".to_string());
+    }
+
+    db.create_snapshot(name)
+        .map(|_| format!("Snapshot '{name}' created successfully"))
+        .map_err(|e| format!("Error creating snapshot, {e}"))
+}
+
+/// 🦀
+/// Executes the `.snapshots` command from the CLI, listing the named snapshots of the database.
+///
+/// # Parameters
+///
+/// - `db`: A mutable reference to the [`Nosqlite`] instance whose snapshots are listed.
+///
+/// # Returns
+///
+/// - `Ok(String)` containing the names of the snapshots, one per line, or a message if there is none.
+/// - `Err(String)` describing the error if the snapshots cannot be listed.
+///
+/// # See Also
+///
+/// - [`Nosqlite::list_snapshots`]
+pub fn handle_list_snapshots(db: &mut Nosqlite) -> Result<String, String> {
+    let names = db
+        .list_snapshots()
+        .map_err(|e| format!("Failed to list snapshots: {e}"))?;
+    if names.is_empty() {
+        return Ok("No snapshots found.".to_string());
+    }
+    Ok(names.join("\n"))
+}
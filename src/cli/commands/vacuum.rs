@@ -0,0 +1,24 @@
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes the `.vacuum` command from the CLI, compacting the database and reporting the bytes
+/// reclaimed.
+///
+/// # Parameters
+///
+/// - `db`: A mutable reference to the [`Nosqlite`] instance to compact.
+///
+/// # Returns
+///
+/// - `Ok(String)` containing the [`VacuumReport`](crate::engine::models::VacuumReport) if the
+///   database was compacted.
+/// - `Err(String)` describing the error if the database is read-only or the rewrite fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::vacuum`]
+pub fn handle_vacuum(db: &mut Nosqlite) -> Result<String, String> {
+    db.vacuum()
+        .map(|report| report.to_string())
+        .map_err(|e| format!("Error compacting database, {e}"))
+}
@@ -1,18 +1,98 @@
+use crate::engine::models::{Compression, Encoding, Kdf, KeySource, Layout, NosqliteOptions};
 use std::env;
 use std::str::FromStr;
+use std::time::Duration;
 
 /// Represents supported CLI flags like --timing
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum CliFlags {
     Timing,
+    /// `--key-file=<path>`: read the encryption key from this file
+    KeyFile(String),
+    /// `--key-env=<VAR>`: read the hex-encoded encryption key from this environment variable
+    KeyEnv(String),
+    /// `--key-sidecar`: use a key file stored next to the database
+    KeySidecar,
+    /// `--passphrase`: prompt for the passphrase protecting the database
+    Passphrase,
+    /// `--passphrase-env=<VAR>`: read the passphrase from this environment variable
+    PassphraseEnv(String),
+    /// `--kdf=<argon2id|pbkdf2[:iterations]>`: key derivation function for new passphrases
+    Kdf(String),
+    /// `--plain`: the database is stored unencrypted, as plain JSON
+    Plain,
+    /// `--to=<plain|encrypted>`: target mode of the `convert` subcommand
+    ConvertTo(String),
+    /// `--new-key-file=<path>`: `rekey` to the key stored in this file
+    NewKeyFile(String),
+    /// `--new-key-env=<VAR>`: `rekey` to the hex-encoded key of this environment variable
+    NewKeyEnv(String),
+    /// `--new-key-sidecar`: `rekey` to a key file stored next to the database
+    NewKeySidecar,
+    /// `--new-passphrase`: `rekey` to a passphrase prompted on the terminal
+    NewPassphrase,
+    /// `--new-passphrase-env=<VAR>`: `rekey` to the passphrase of this environment variable
+    NewPassphraseEnv(String),
+    /// `--readonly`: open an existing database without ever modifying it
+    ReadOnly,
+    /// `--lock-timeout=<seconds>`: wait this long for another process to release the database
+    LockTimeout(String),
+    /// `--encoding=<json|msgpack>`: encoding the data file is written with
+    Encoding(String),
+    /// `--compression=<none|deflate|zstd>`: compression of the data file
+    Compression(String),
+    /// `--layout=<single|segmented>`: layout the database is written with
+    Layout(String),
 }
 
 impl FromStr for CliFlags {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("--key-file=") {
+            return Ok(CliFlags::KeyFile(path.to_string()));
+        }
+        if let Some(name) = s.strip_prefix("--key-env=") {
+            return Ok(CliFlags::KeyEnv(name.to_string()));
+        }
+        if let Some(name) = s.strip_prefix("--passphrase-env=") {
+            return Ok(CliFlags::PassphraseEnv(name.to_string()));
+        }
+        if let Some(kdf) = s.strip_prefix("--kdf=") {
+            return Ok(CliFlags::Kdf(kdf.to_string()));
+        }
+        if let Some(mode) = s.strip_prefix("--to=") {
+            return Ok(CliFlags::ConvertTo(mode.to_string()));
+        }
+        if let Some(path) = s.strip_prefix("--new-key-file=") {
+            return Ok(CliFlags::NewKeyFile(path.to_string()));
+        }
+        if let Some(name) = s.strip_prefix("--new-key-env=") {
+            return Ok(CliFlags::NewKeyEnv(name.to_string()));
+        }
+        if let Some(name) = s.strip_prefix("--new-passphrase-env=") {
+            return Ok(CliFlags::NewPassphraseEnv(name.to_string()));
+        }
+        if let Some(seconds) = s.strip_prefix("--lock-timeout=") {
+            return Ok(CliFlags::LockTimeout(seconds.to_string()));
+        }
+        if let Some(encoding) = s.strip_prefix("--encoding=") {
+            return Ok(CliFlags::Encoding(encoding.to_string()));
+        }
+        if let Some(compression) = s.strip_prefix("--compression=") {
+            return Ok(CliFlags::Compression(compression.to_string()));
+        }
+        if let Some(layout) = s.strip_prefix("--layout=") {
+            return Ok(CliFlags::Layout(layout.to_string()));
+        }
         match s {
             "--timing" | "-t" => Ok(CliFlags::Timing),
+            "--key-sidecar" => Ok(CliFlags::KeySidecar),
+            "--passphrase" => Ok(CliFlags::Passphrase),
+            "--plain" => Ok(CliFlags::Plain),
+            "--readonly" => Ok(CliFlags::ReadOnly),
+            "--new-key-sidecar" => Ok(CliFlags::NewKeySidecar),
+            "--new-passphrase" => Ok(CliFlags::NewPassphrase),
             _ => Err(()),
         }
     }
@@ -31,3 +111,115 @@ pub fn parse_and_clean_args() -> (Vec<CliFlags>, Vec<String>) {
 
     (flags, args_clean)
 }
+
+/// Builds the database options from the key source, KDF, read-only, lock, encoding, compression and
+/// layout flags; the last key source wins.
+pub fn options_from_flags(flags: &[CliFlags]) -> Result<NosqliteOptions, String> {
+    let mut options = NosqliteOptions::default();
+    for flag in flags {
+        match flag {
+            CliFlags::KeyFile(path) => options.key_source = KeySource::File(path.clone()),
+            CliFlags::KeyEnv(name) => options.key_source = KeySource::Env(name.clone()),
+            CliFlags::KeySidecar => options.key_source = KeySource::Sidecar,
+            CliFlags::Plain => options.key_source = KeySource::Unencrypted,
+            CliFlags::Passphrase => {
+                let passphrase = rpassword::prompt_password("Passphrase: ")
+                    .map_err(|e| format!("cannot read passphrase: {e}"))?;
+                options.key_source = KeySource::Passphrase(passphrase);
+            }
+            CliFlags::PassphraseEnv(name) => {
+                options.key_source = KeySource::Passphrase(env_var(name)?);
+            }
+            CliFlags::Kdf(name) => {
+                options.kdf = match name.split_once(':') {
+                    None if name == "argon2id" => Kdf::default(),
+                    None if name == "pbkdf2" => Kdf::Pbkdf2 {
+                        iterations: 600_000,
+                    },
+                    Some(("pbkdf2", iterations)) => Kdf::Pbkdf2 {
+                        iterations: iterations
+                            .parse()
+                            .map_err(|_| format!("invalid PBKDF2 iterations '{iterations}'"))?,
+                    },
+                    _ => return Err(format!("unknown KDF '{name}', expected argon2id or pbkdf2")),
+                }
+            }
+            CliFlags::ReadOnly => options.read_only = true,
+            CliFlags::LockTimeout(seconds) => {
+                let timeout = seconds
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                    .ok_or_else(|| format!("invalid lock timeout '{seconds}'"))?;
+                options.lock_timeout = Some(timeout);
+            }
+            CliFlags::Encoding(name) => {
+                options.encoding = match name.as_str() {
+                    "json" => Encoding::Json,
+                    "msgpack" => Encoding::MessagePack,
+                    _ => {
+                        return Err(format!(
+                            "unknown encoding '{name}', expected json or msgpack"
+                        ))
+                    }
+                }
+            }
+            CliFlags::Compression(name) => {
+                options.compression = match name.as_str() {
+                    "none" => Compression::None,
+                    "deflate" => Compression::Deflate,
+                    "zstd" => Compression::Zstd,
+                    _ => {
+                        return Err(format!(
+                            "unknown compression '{name}', expected none, deflate or zstd"
+                        ))
+                    }
+                }
+            }
+            CliFlags::Layout(name) => {
+                options.layout = match name.as_str() {
+                    "single" => Layout::Single,
+                    "segmented" => Layout::Segmented,
+                    _ => {
+                        return Err(format!(
+                            "unknown layout '{name}', expected single or segmented"
+                        ))
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(options)
+}
+
+/// Builds the key source a database is re-encrypted with from the `--new-*` flags; the last one wins.
+pub fn new_key_source_from_flags(flags: &[CliFlags]) -> Result<Option<KeySource>, String> {
+    let mut key_source = None;
+    for flag in flags {
+        match flag {
+            CliFlags::NewKeyFile(path) => key_source = Some(KeySource::File(path.clone())),
+            CliFlags::NewKeyEnv(name) => key_source = Some(KeySource::Env(name.clone())),
+            CliFlags::NewKeySidecar => key_source = Some(KeySource::Sidecar),
+            CliFlags::NewPassphrase => {
+                let passphrase = rpassword::prompt_password("New passphrase: ")
+                    .map_err(|e| format!("cannot read passphrase: {e}"))?;
+                let confirmation = rpassword::prompt_password("Confirm new passphrase: ")
+                    .map_err(|e| format!("cannot read passphrase: {e}"))?;
+                if passphrase != confirmation {
+                    return Err("passphrases do not match".to_string());
+                }
+                key_source = Some(KeySource::Passphrase(passphrase));
+            }
+            CliFlags::NewPassphraseEnv(name) => {
+                key_source = Some(KeySource::Passphrase(env_var(name)?));
+            }
+            _ => {}
+        }
+    }
+    Ok(key_source)
+}
+
+fn env_var(name: &str) -> Result<String, String> {
+    env::var(name).map_err(|_| format!("environment variable '{name}' is not set"))
+}
@@ -1,3 +1,4 @@
 pub mod commands;
 pub mod flags;
 pub mod repl;
+pub mod subcommands;
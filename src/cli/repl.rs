@@ -1,7 +1,14 @@
+use crate::cli::commands::backup::handle_backup;
+use crate::cli::commands::change_passphrase::handle_change_passphrase;
 use crate::cli::commands::create_collection::handle_create_collection;
+use crate::cli::commands::export::handle_export;
+use crate::cli::commands::import::handle_import;
 use crate::cli::commands::insert_document::handle_insert_document;
 use crate::cli::commands::list_collections::handle_list_collections;
-use crate::cli::flags::{parse_and_clean_args, CliFlags};
+use crate::cli::commands::restore::handle_restore;
+use crate::cli::commands::snapshot::{handle_create_snapshot, handle_list_snapshots};
+use crate::cli::commands::vacuum::handle_vacuum;
+use crate::cli::flags::{options_from_flags, parse_and_clean_args, CliFlags};
 use crate::engine::nosqlite::Nosqlite;
 
 use rustyline::error::ReadlineError;
@@ -15,7 +22,15 @@ pub fn start_repl() {
     let (flags, args) = parse_and_clean_args();
     let path = get_db_path(args);
 
-    let mut db = match Nosqlite::open(&path) {
+    let options = match options_from_flags(&flags) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Invalid options: {e}");
+            return;
+        }
+    };
+
+    let mut db = match Nosqlite::open_with_options(&path, options) {
         Ok(db) => db,
         Err(e) => {
             eprintln!("Failed to open or create database: {e}");
@@ -82,7 +97,7 @@ fn handle_input(input_raw: &str, flags: &[CliFlags], db: &mut Nosqlite) -> bool
     false
 }
 
-fn get_db_path(args: Vec<String>) -> String {
+pub fn get_db_path(args: Vec<String>) -> String {
     let raw = args
         .first()
         .cloned()
@@ -103,6 +118,22 @@ pub fn execute_command(input: &str, db: &mut Nosqlite) -> Result<String, String>
         handle_insert_document(input, db)
     } else if input.starts_with("db.findDocuments(") {
         handle_find_documents(input, db)
+    } else if input == ".passphrase" {
+        handle_change_passphrase(db)
+    } else if input == ".backup" || input.starts_with(".backup ") {
+        handle_backup(input, db)
+    } else if input == ".snapshots" {
+        handle_list_snapshots(db)
+    } else if input == ".snapshot" || input.starts_with(".snapshot ") {
+        handle_create_snapshot(input, db)
+    } else if input == ".restore" || input.starts_with(".restore ") {
+        handle_restore(input, db)
+    } else if input == ".vacuum" {
+        handle_vacuum(db)
+    } else if input == ".export" || input.starts_with(".export ") {
+        handle_export(input, db)
+    } else if input == ".import" || input.starts_with(".import ") {
+        handle_import(input, db)
     } else {
         Err("Unknown or unsupported command".to_string())
     }
@@ -0,0 +1,76 @@
+use crate::cli::flags::{options_from_flags, CliFlags};
+use crate::cli::repl::get_db_path;
+use crate::engine::models::KeySource;
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes `nosqlite-cli convert <database> --to=<plain|encrypted>`, switching a database between
+/// encrypted and unencrypted storage.
+///
+/// In both directions, the key flags (`--key-file=`, `--passphrase`, ...) describe the encrypted
+/// side: the key the database is decrypted with for `--to=plain`, or the key it is encrypted with
+/// for `--to=encrypted` (the default `db.key` file if none is given).
+///
+/// # Parameters
+///
+/// - `flags`: The parsed command line flags.
+/// - `args`: The remaining arguments; the first one is the database path.
+///
+/// # Returns
+///
+/// - `0` if the database was converted.
+/// - `1` if the flags are invalid, the database cannot be opened, or the conversion fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::rotate_key`]
+pub fn run(flags: &[CliFlags], args: Vec<String>) -> i32 {
+    let target = flags.iter().find_map(|flag| match flag {
+        CliFlags::ConvertTo(mode) => Some(mode.as_str()),
+        _ => None,
+    });
+    if args.is_empty() || !matches!(target, Some("plain" | "encrypted")) {
+        eprintln!("Usage: nosqlite-cli convert <database> --to=<plain|encrypted> [key flags]");
+        return 1;
+    }
+    let path = get_db_path(args);
+
+    let mut options = match options_from_flags(flags) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Invalid options: {e}");
+            return 1;
+        }
+    };
+    let new_key_source = if target == Some("plain") {
+        KeySource::Unencrypted
+    } else {
+        std::mem::replace(&mut options.key_source, KeySource::Unencrypted)
+    };
+
+    if !std::path::Path::new(&path).exists() {
+        eprintln!("Database not found: {path}");
+        return 1;
+    }
+    let mut db = match Nosqlite::open_with_options(&path, options) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Failed to open database: {e}");
+            return 1;
+        }
+    };
+
+    match db.rotate_key(new_key_source) {
+        Ok(()) => {
+            println!(
+                "Database '{path}' converted to {} successfully",
+                target.unwrap_or_default()
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("Error converting database, {e}");
+            1
+        }
+    }
+}
@@ -0,0 +1,36 @@
+pub mod convert;
+pub mod recover;
+pub mod rekey;
+pub mod stats;
+
+use crate::cli::flags::parse_and_clean_args;
+
+/// 🦀
+/// Runs the subcommand named by the first command line argument, if any.
+///
+/// # Returns
+///
+/// - `Some(exit_code)` if a subcommand was run.
+/// - `None` if the arguments do not name a subcommand, and the REPL should be started instead.
+pub fn dispatch() -> Option<i32> {
+    let (flags, mut args) = parse_and_clean_args();
+    match args.first().map(String::as_str) {
+        Some("rekey") => {
+            args.remove(0);
+            Some(rekey::run(&flags, args))
+        }
+        Some("convert") => {
+            args.remove(0);
+            Some(convert::run(&flags, args))
+        }
+        Some("recover") => {
+            args.remove(0);
+            Some(recover::run(&flags, args))
+        }
+        Some("stats") => {
+            args.remove(0);
+            Some(stats::run(&flags, args))
+        }
+        _ => None,
+    }
+}
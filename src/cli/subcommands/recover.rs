@@ -0,0 +1,68 @@
+use crate::cli::flags::{options_from_flags, CliFlags};
+use crate::cli::repl::get_db_path;
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes `nosqlite-cli recover <database> <output>`, copying every readable collection and
+/// document of a damaged database into a new file, and printing a report of what was lost.
+///
+/// The key flags (`--key-file=`, `--passphrase`, ...) describe the key of the damaged database; the
+/// new file is encrypted with the same key source, and written with the `--layout=` flag, if any.
+///
+/// An encrypted database saved with `--layout=single` (the default) cannot be recovered partially:
+/// its data file is authenticated as a whole, so a damaged byte loses it entirely, and only the
+/// write-ahead log is recovered. Segmented databases only lose the damaged collections.
+///
+/// # Parameters
+///
+/// - `flags`: The parsed command line flags.
+/// - `args`: The remaining arguments; the database path, then the path of the new file.
+///
+/// # Returns
+///
+/// - `0` if the new file was written, even if some data could not be recovered.
+/// - `1` if the arguments are invalid, the database cannot be read at all, or the new file cannot
+///   be written.
+///
+/// # See Also
+///
+/// - [`Nosqlite::salvage`]
+pub fn run(flags: &[CliFlags], args: Vec<String>) -> i32 {
+    if args.len() < 2 {
+        eprintln!("Usage: nosqlite-cli recover <database> <output> [key flags]");
+        return 1;
+    }
+    let output = get_db_path(args[1..].to_vec());
+    let path = get_db_path(args);
+    if std::path::Path::new(&output).exists() {
+        eprintln!("Output file already exists: {output}");
+        return 1;
+    }
+
+    let options = match options_from_flags(flags) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Invalid options: {e}");
+            return 1;
+        }
+    };
+    let (mut db, report) = match Nosqlite::salvage(&path, options.clone()) {
+        Ok(salvaged) => salvaged,
+        Err(e) => {
+            eprintln!("Failed to read database: {e}");
+            return 1;
+        }
+    };
+    print!("{report}");
+
+    match db.save_to(&output, options) {
+        Ok(()) => {
+            println!("Recovered '{path}' into '{output}'");
+            0
+        }
+        Err(e) => {
+            eprintln!("Error writing recovered database, {e}");
+            1
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::cli::flags::{new_key_source_from_flags, options_from_flags, CliFlags};
+use crate::cli::repl::get_db_path;
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes `nosqlite-cli rekey <database>`, re-encrypting a database with a new key.
+///
+/// The current key is given with the usual key flags (`--key-file=`, `--passphrase`, ...), and the
+/// new one with their `--new-*` counterparts (`--new-key-file=`, `--new-passphrase`, ...).
+///
+/// # Parameters
+///
+/// - `flags`: The parsed command line flags.
+/// - `args`: The remaining arguments; the first one is the database path.
+///
+/// # Returns
+///
+/// - `0` if the database was re-encrypted.
+/// - `1` if the flags are invalid, the database cannot be opened, or the rotation fails.
+///
+/// # See Also
+///
+/// - [`Nosqlite::rotate_key`]
+pub fn run(flags: &[CliFlags], args: Vec<String>) -> i32 {
+    if args.is_empty() {
+        eprintln!("Usage: nosqlite-cli rekey <database> [key flags] --new-key-file=<path>|--new-key-env=<VAR>|--new-key-sidecar|--new-passphrase|--new-passphrase-env=<VAR>");
+        return 1;
+    }
+    let path = get_db_path(args);
+
+    let (options, new_key_source) = match options_from_flags(flags)
+        .and_then(|options| Ok((options, new_key_source_from_flags(flags)?)))
+    {
+        Ok((options, Some(new_key_source))) => (options, new_key_source),
+        Ok((_, None)) => {
+            eprintln!("Invalid options: a new key is required (e.g. --new-key-file=<path>)");
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("Invalid options: {e}");
+            return 1;
+        }
+    };
+
+    if !std::path::Path::new(&path).exists() {
+        eprintln!("Database not found: {path}");
+        return 1;
+    }
+    let mut db = match Nosqlite::open_with_options(&path, options) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Failed to open database: {e}");
+            return 1;
+        }
+    };
+
+    match db.rotate_key(new_key_source) {
+        Ok(()) => {
+            println!("Database '{path}' re-encrypted successfully");
+            0
+        }
+        Err(e) => {
+            eprintln!("Error re-encrypting database, {e}");
+            1
+        }
+    }
+}
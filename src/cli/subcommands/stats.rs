@@ -0,0 +1,62 @@
+use crate::cli::flags::{options_from_flags, CliFlags};
+use crate::cli::repl::get_db_path;
+use crate::engine::Nosqlite;
+
+/// 🦀
+/// Executes `nosqlite-cli stats <database>`, reporting the size of a database file and how well its
+/// payload compresses.
+///
+/// The database is opened read-only, with the usual key flags (`--key-file=`, `--passphrase`, ...).
+///
+/// # Parameters
+///
+/// - `flags`: The parsed command line flags.
+/// - `args`: The remaining arguments; the first one is the database path.
+///
+/// # Returns
+///
+/// - `0` if the statistics were printed.
+/// - `1` if the flags are invalid, or the database cannot be opened or read.
+///
+/// # See Also
+///
+/// - [`Nosqlite::payload_stats`]
+pub fn run(flags: &[CliFlags], args: Vec<String>) -> i32 {
+    if args.is_empty() {
+        eprintln!("Usage: nosqlite-cli stats <database> [key flags]");
+        return 1;
+    }
+    let path = get_db_path(args);
+
+    let options = match options_from_flags(flags) {
+        Ok(options) => options.with_read_only(true),
+        Err(e) => {
+            eprintln!("Invalid options: {e}");
+            return 1;
+        }
+    };
+    let mut db = match Nosqlite::open_with_options(&path, options) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Failed to open database: {e}");
+            return 1;
+        }
+    };
+
+    match db.payload_stats() {
+        Ok(stats) => {
+            println!("Database: {path}");
+            println!("Encoding: {:?}", stats.encoding);
+            println!("Compression: {:?}", stats.compression);
+            println!("File size: {} bytes", stats.file_bytes);
+            println!("Serialized payload: {} bytes", stats.serialized_bytes);
+            println!("Compressed payload: {} bytes", stats.compressed_bytes);
+            println!("Compression ratio: {:.2}", stats.compression_ratio());
+            0
+        }
+        Err(e) => {
+            eprintln!("Error reading database statistics, {e}");
+            1
+        }
+    }
+}
@@ -6,18 +6,32 @@ pub enum NosqliteError {
     DatabaseNotFound(String),
     DatabaseAlreadyExists(String),
     InvalidDatabaseFormat(String),
+    UnsupportedFormatVersion(String),
     CollectionAlreadyExists(String),
     CollectionNotFound(String),
     CollectionNameEmpty(),
     InvalidCollectionStructure(String),
     DocumentInvalid(String),
     DocumentNotFound(String),
+    IndexAlreadyExists(String),
+    IndexNotFound(String),
+    InvalidIndex(String),
+    InvalidFilter(String),
+    InvalidVector(String),
     IoError(String),
     SerializationError(String),
     EncryptionError(String),
+    InvalidKey(String),
+    KeyMismatch(String),
+    TransactionError(String),
+    DatabaseLocked(String),
+    ReadOnly(String),
     DeserializationError(String),
     HexDecodeError(String),
     Base64DecodeError(String),
+    Corrupted(String),
+    SnapshotNotFound(String),
+    InvalidSnapshotName(String),
     // Ajoute ce dont tu as besoin ici
 }
 
@@ -53,12 +67,30 @@ impl Display for NosqliteError {
             NosqliteError::DocumentNotFound(id) => {
                 write!(f, "Document not found: `{}`", id)
             }
+            NosqliteError::IndexAlreadyExists(name) => {
+                write!(f, "Index already exists: `{}`", name)
+            }
+            NosqliteError::IndexNotFound(name) => write!(f, "Index not found: `{}`", name),
+            NosqliteError::InvalidIndex(msg) => write!(f, "Invalid index: {}", msg),
+            NosqliteError::InvalidFilter(msg) => write!(f, "Invalid filter: {}", msg),
+            NosqliteError::InvalidVector(msg) => write!(f, "Invalid vector: {}", msg),
             NosqliteError::IoError(msg) => write!(f, "IO error: {}", msg),
             NosqliteError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
             NosqliteError::EncryptionError(msg) => write!(f, "Encryption error: {}", msg),
+            NosqliteError::InvalidKey(msg) => write!(f, "Invalid key: {}", msg),
+            NosqliteError::KeyMismatch(msg) => write!(f, "Key mismatch: {}", msg),
+            NosqliteError::TransactionError(msg) => write!(f, "Transaction error: {}", msg),
+            NosqliteError::DatabaseLocked(msg) => write!(f, "Database locked: {}", msg),
+            NosqliteError::ReadOnly(msg) => write!(f, "Read-only database: {}", msg),
+            NosqliteError::UnsupportedFormatVersion(msg) => {
+                write!(f, "Unsupported format version: {}", msg)
+            }
             NosqliteError::DeserializationError(msg) => write!(f, "Deserialization error: {}", msg),
             NosqliteError::HexDecodeError(msg) => write!(f, "Hex decode error: {}", msg),
             NosqliteError::Base64DecodeError(msg) => write!(f, "Base64 decode error: {}", msg),
+            NosqliteError::Corrupted(msg) => write!(f, "Corrupted data: {}", msg),
+            NosqliteError::SnapshotNotFound(name) => write!(f, "Snapshot not found: `{}`", name),
+            NosqliteError::InvalidSnapshotName(msg) => write!(f, "Invalid snapshot name: {}", msg),
         }
     }
 }
@@ -8,6 +8,8 @@ use super::NosqliteError;
 pub struct NosqliteErrorHandler {
     errors: Vec<NosqliteError>,
     db_path: String,
+    #[serde(default)]
+    in_memory_only: bool,
 }
 
 impl NosqliteErrorHandler {
@@ -51,6 +53,30 @@ impl NosqliteErrorHandler {
         Self {
             errors: Vec::new(),
             db_path,
+            in_memory_only: false,
+        }
+    }
+
+    /// 🦀
+    /// Creates a [`NosqliteErrorHandler`] that keeps errors in memory only, without ever writing the
+    /// `.log` file of the database.
+    ///
+    /// Used for databases opened read-only, whose directory must not be modified.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+    ///
+    /// let mut handler = NosqliteErrorHandler::in_memory("temp/data66.nosqlite".to_string());
+    /// handler.log_error(NosqliteError::DocumentNotFound("abc123".to_string()));
+    /// assert_eq!(handler.all_errors().len(), 1);
+    /// assert!(!std::path::Path::new("temp/data66.log").exists());
+    /// ```
+    pub fn in_memory(db_path: String) -> Self {
+        Self {
+            in_memory_only: true,
+            ..Self::new(db_path)
         }
     }
 
@@ -134,6 +160,9 @@ impl NosqliteErrorHandler {
     /// - [`NosqliteErrorHandler::log_error`] — logs and persists errors together
     /// - [`NosqliteError`] — the structured error type
     fn persist_error(&self, error: &NosqliteError) {
+        if self.in_memory_only {
+            return;
+        }
         let log_path = self.db_path.replace(".nosqlite", ".log");
 
         let mut file = std::fs::OpenOptions::new()
@@ -1,8 +1,11 @@
 use super::model::Collection;
 use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
 use crate::engine::models::document::model::Document;
+use crate::engine::models::index::model::{Index, IndexKind};
 use crate::engine::models::utils::{get_nested_value, now, validate_against_structure};
+use crate::engine::models::vector::model::VectorMetric;
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 
 impl Collection {
@@ -58,6 +61,8 @@ impl Collection {
             structure,
             documents: Vec::new(),
             created_at: now(),
+            indexes: Vec::new(),
+            positions: HashMap::new(),
         }
     }
 
@@ -152,6 +157,11 @@ impl Collection {
         }
 
         let document = Document::new(data);
+        for index in &mut self.indexes {
+            index.insert(&document);
+        }
+        self.positions
+            .insert(document.id.clone(), self.documents.len());
         self.documents.push(document);
         Ok(())
     }
@@ -187,6 +197,7 @@ impl Collection {
     /// - All documents where `field_name == field_value` will be fully overwritten.
     /// - Each updated document receives a fresh `updated_at` timestamp.
     /// - Partial updates are not supported by this method.
+    /// - Documents expired by a TTL index are never matched, so an update cannot revive them.
     /// # Example
     ///
     /// ```rust
@@ -240,18 +251,7 @@ impl Collection {
         }
 
         // Trouve tous les documents correspondants
-        let matching_indices: Vec<usize> = self
-            .documents
-            .iter()
-            .enumerate()
-            .filter_map(|(i, doc)| {
-                if get_nested_value(&doc.data, field_name) == Some(field_value) {
-                    Some(i)
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let matching_indices = self.matching_positions(field_name, field_value);
 
         if matching_indices.is_empty() {
             let error = NosqliteError::DocumentNotFound(format!(
@@ -267,7 +267,7 @@ impl Collection {
             let mut document = self.documents[index].clone();
             document.data = new_data.clone(); // Cloner car on modifie plusieurs documents
             document.updated_at = now();
-            self.documents[index] = document;
+            self.replace_document(index, document);
         }
 
         Ok(())
@@ -298,6 +298,7 @@ impl Collection {
     /// - If it does not exist, the field is inserted.
     /// - All matching documents receive a fresh `updated_at` timestamp.
     /// - This method does **not** perform schema validation for the updated field.
+    /// - Documents expired by a TTL index are never matched, so an update cannot revive them.
     ///
     /// # Errors
     ///
@@ -335,18 +336,7 @@ impl Collection {
         value: Value,
         handler: &mut NosqliteErrorHandler,
     ) -> Result<(), NosqliteError> {
-        let matching_indices: Vec<usize> = self
-            .documents
-            .iter()
-            .enumerate()
-            .filter_map(|(i, doc)| {
-                if get_nested_value(&doc.data, field_name) == Some(field_value) {
-                    Some(i)
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let matching_indices = self.matching_positions(field_name, field_value);
 
         if matching_indices.is_empty() {
             let error = NosqliteError::DocumentNotFound(format!(
@@ -358,9 +348,11 @@ impl Collection {
         }
 
         for index in matching_indices {
-            if let Value::Object(ref mut doc_map) = self.documents[index].data {
+            let mut document = self.documents[index].clone();
+            if let Value::Object(ref mut doc_map) = document.data {
                 doc_map.insert(target_field.to_string(), value.clone());
-                self.documents[index].updated_at = now();
+                document.updated_at = now();
+                self.replace_document(index, document);
             } else {
                 let error =
                     NosqliteError::DocumentInvalid("Document data is not a JSON object".into());
@@ -393,6 +385,7 @@ impl Collection {
     ///
     /// - This is a destructive operation; matching documents are permanently removed from memory.
     /// - All matches are deleted in one call.
+    /// - Documents expired by a TTL index are never matched; they are left to the next purge.
     /// - If no match is found, an error is returned and logged.
     ///
     /// # Errors
@@ -428,17 +421,13 @@ impl Collection {
         field_value: &Value,
         handler: &mut NosqliteErrorHandler,
     ) -> Result<(), NosqliteError> {
-        let original_len = self.documents.len();
-
-        self.documents.retain(|doc| {
-            let keep = get_nested_value(&doc.data, field_name) != Some(field_value);
-            // Log matched deletions (optional: could add handler logging here if needed)
-            keep
-        });
-
-        let new_len = self.documents.len();
+        let ids: Vec<String> = self
+            .matching_positions(field_name, field_value)
+            .into_iter()
+            .map(|position| self.documents[position].id.clone())
+            .collect();
 
-        if new_len == original_len {
+        if self.remove_documents(&ids) == 0 {
             let error = NosqliteError::DocumentNotFound(format!(
                 "No document found where '{}' == '{}'",
                 field_name, field_value
@@ -490,6 +479,7 @@ impl Collection {
     ///
     /// - Performs a linear scan over the internal document list.
     /// - Stops at the **first match**. Use another method if you expect multiple matches.
+    /// - Documents expired by a TTL index are skipped.
     ///
     /// # See Also
     ///
@@ -497,9 +487,11 @@ impl Collection {
     /// - [`Collection::delete_documents`] — for deletion using a field filter
     /// - [`get_nested_value`] — for resolving field paths
     pub fn get_document(&self, field_name: &str, field_value: &Value) -> Option<&Document> {
-        self.documents
-            .iter()
-            .find(|doc| get_nested_value(&doc.data, field_name) == Some(field_value))
+        let now = now();
+        self.documents.iter().find(|doc| {
+            get_nested_value(&doc.data, field_name) == Some(field_value)
+                && !self.is_expired(doc, now)
+        })
     }
 
     /// 🦀
@@ -592,6 +584,339 @@ impl Collection {
     pub fn document_count(&self) -> usize {
         self.documents.len()
     }
+
+    /// 🦀
+    /// Attaches a new secondary index to the collection and builds it from the existing documents.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the index. Must be unique within the collection.
+    /// - `kind`: The [`IndexKind`] describing what to index.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for logging errors.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the index was created and built.
+    /// - `Err(NosqliteError)` if:
+    ///   - An index with the same name already exists ([`NosqliteError::IndexAlreadyExists`])
+    ///   - The definition is invalid, e.g. a text index without fields ([`NosqliteError::InvalidIndex`])
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::{Collection, IndexKind};
+    /// use nosqlite_rust::engine::error::NosqliteErrorHandler;
+    ///
+    /// let mut collection = Collection::new("articles".to_string(), json!({ "body": "string" }));
+    /// let mut handler = NosqliteErrorHandler::new("temp/data46.nosqlite".to_string());
+    ///
+    /// collection.add_document(json!({ "body": "Hello, world!" }), &mut handler).unwrap();
+    /// collection
+    ///     .add_index("content", IndexKind::Text { fields: vec!["body".to_string()] }, &mut handler)
+    ///     .unwrap();
+    /// assert_eq!(collection.indexes.len(), 1);
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`Collection::remove_index`] — to drop an index
+    pub fn add_index(
+        &mut self,
+        name: &str,
+        kind: IndexKind,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        if self.indexes.iter().any(|index| index.name == name) {
+            let error = NosqliteError::IndexAlreadyExists(name.to_string());
+            handler.log_error(error.clone());
+            return Err(error);
+        }
+        if let Err(reason) = kind.validate() {
+            let error = NosqliteError::InvalidIndex(format!("Index '{}': {}", name, reason));
+            handler.log_error(error.clone());
+            return Err(error);
+        }
+
+        let mut index = Index::new(name, kind);
+        index.rebuild(&self.documents);
+        self.indexes.push(index);
+        Ok(())
+    }
+
+    /// 🦀
+    /// Removes a secondary index from the collection by its name.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the index to drop.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for logging errors.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the index was removed.
+    /// - `Err(NosqliteError::IndexNotFound)` if no index has that name.
+    pub fn remove_index(
+        &mut self,
+        name: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let position = self
+            .indexes
+            .iter()
+            .position(|index| index.name == name)
+            .ok_or_else(|| {
+                let error = NosqliteError::IndexNotFound(name.to_string());
+                handler.log_error(error.clone());
+                error
+            })?;
+
+        self.indexes.remove(position);
+        Ok(())
+    }
+
+    /// 🦀
+    /// Returns the first text index of the collection, if any.
+    ///
+    /// Used by the query engine to resolve the `$text` filter operator.
+    pub fn text_index(&self) -> Option<&Index> {
+        self.indexes
+            .iter()
+            .find(|index| matches!(index.kind, IndexKind::Text { .. }))
+    }
+
+    /// 🦀
+    /// Returns the geo index covering the given field, if any.
+    ///
+    /// Used by the query engine to accelerate the `$geoWithin` and `$near` filter operators.
+    pub fn geo_index(&self, field: &str) -> Option<&Index> {
+        self.indexes.iter().find(
+            |index| matches!(&index.kind, IndexKind::Geo { field: indexed } if indexed == field),
+        )
+    }
+
+    /// 🦀
+    /// Returns the vector index covering the given field with the given metric, if any.
+    ///
+    /// Used by the query engine to answer k-nearest queries approximately.
+    pub fn vector_index(&self, field: &str, metric: VectorMetric) -> Option<&Index> {
+        self.indexes.iter().find(|index| {
+            matches!(&index.kind, IndexKind::Vector { field: indexed, metric: used } if indexed == field && *used == metric)
+        })
+    }
+
+    /// 🦀
+    /// Rebuilds every index of the collection from its current documents.
+    ///
+    /// Index data is not persisted, so this must be called after the collection is deserialized.
+    /// The positions used by [`Collection::document_position`] are rebuilt too.
+    pub fn rebuild_indexes(&mut self) {
+        for index in &mut self.indexes {
+            index.rebuild(&self.documents);
+        }
+        self.index_positions();
+    }
+
+    /// 🦀
+    /// Returns the position in [`Collection::documents`] of the document with the given ID.
+    ///
+    /// The position is looked up in a map kept alongside the indexes, so that the hits of an
+    /// index can be resolved without scanning the collection. Expired documents are found too;
+    /// check them with [`Collection::is_expired`].
+    ///
+    /// # Parameters
+    ///
+    /// - `id`: The ID of the document.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(position)` if the collection holds the document, `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::Collection;
+    /// use nosqlite_rust::engine::error::NosqliteErrorHandler;
+    ///
+    /// let mut collection = Collection::new("users".to_string(), json!({ "name": "string" }));
+    /// let mut handler = NosqliteErrorHandler::new("temp/data91.nosqlite".to_string());
+    /// collection.add_document(json!({ "name": "Alice" }), &mut handler).unwrap();
+    /// collection.add_document(json!({ "name": "Bob" }), &mut handler).unwrap();
+    ///
+    /// let id = collection.documents[1].id.clone();
+    /// assert_eq!(collection.document_position(&id), Some(1));
+    /// assert_eq!(collection.document_position("missing"), None);
+    /// ```
+    pub fn document_position(&self, id: &str) -> Option<usize> {
+        match self.positions.get(id) {
+            Some(&position) if self.documents.get(position).is_some_and(|doc| doc.id == id) => {
+                Some(position)
+            }
+            _ => self.documents.iter().position(|doc| doc.id == id),
+        }
+    }
+
+    /// 🦀
+    /// Returns the documents that have not expired under any TTL index of the collection.
+    ///
+    /// Expired documents stay in memory until [`Collection::purge_expired`] runs,
+    /// but they are hidden from every read through this method.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::{Collection, IndexKind};
+    /// use nosqlite_rust::engine::error::NosqliteErrorHandler;
+    ///
+    /// let mut collection = Collection::new("sessions".to_string(), json!({}));
+    /// let mut handler = NosqliteErrorHandler::new("temp/data50.nosqlite".to_string());
+    /// collection.add_document(json!({ "seen_at": 0 }), &mut handler).unwrap();
+    /// collection.add_document(json!({ "seen_at": 4102444800u64 }), &mut handler).unwrap();
+    /// collection
+    ///     .add_index("expiry", IndexKind::Ttl { field: "seen_at".to_string(), expire_after_secs: 60 }, &mut handler)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(collection.live_documents().len(), 1);
+    /// ```
+    pub fn live_documents(&self) -> Vec<&Document> {
+        let now = now();
+        self.documents
+            .iter()
+            .filter(|doc| !self.is_expired(doc, now))
+            .collect()
+    }
+
+    /// 🦀
+    /// Checks whether a document has expired under any TTL index of the collection.
+    ///
+    /// # Parameters
+    ///
+    /// - `document`: The document to check.
+    /// - `now`: The current Unix timestamp, in seconds.
+    ///
+    /// # Returns
+    ///
+    /// - `true` if at least one TTL index gives the document an expiry time at or before `now`.
+    pub fn is_expired(&self, document: &Document, now: u64) -> bool {
+        self.indexes
+            .iter()
+            .filter_map(|index| index.expires_at(document))
+            .any(|expires_at| expires_at <= now)
+    }
+
+    /// 🦀
+    /// Permanently removes every document that has expired under a TTL index.
+    ///
+    /// # Parameters
+    ///
+    /// - `now`: The current Unix timestamp, in seconds.
+    ///
+    /// # Returns
+    ///
+    /// - The number of documents removed.
+    ///
+    /// # See Also
+    ///
+    /// - [`Collection::live_documents`] — hides expired documents without removing them
+    pub fn purge_expired(&mut self, now: u64) -> usize {
+        let expired: Vec<String> = self
+            .documents
+            .iter()
+            .filter(|doc| self.is_expired(doc, now))
+            .map(|doc| doc.id.clone())
+            .collect();
+        if expired.is_empty() {
+            return 0;
+        }
+
+        self.remove_documents(&expired)
+    }
+
+    /// 🦀
+    /// Stores a document as is, replacing the document with the same ID if there is one.
+    ///
+    /// Unlike [`Collection::add_document`], the document is neither validated nor given a new ID or
+    /// timestamps: this is used to replay changes that were already validated, such as the records of
+    /// a write-ahead log.
+    ///
+    /// # Parameters
+    ///
+    /// - `document`: The [`Document`] to store.
+    pub fn put_document(&mut self, document: Document) {
+        match self.documents.iter().position(|doc| doc.id == document.id) {
+            Some(position) => self.replace_document(position, document),
+            None => {
+                for index in &mut self.indexes {
+                    index.insert(&document);
+                }
+                self.positions
+                    .insert(document.id.clone(), self.documents.len());
+                self.documents.push(document);
+            }
+        }
+    }
+
+    /// 🦀
+    /// Removes the documents with the given IDs, ignoring IDs that are not in the collection.
+    ///
+    /// # Parameters
+    ///
+    /// - `ids`: The IDs of the documents to remove.
+    ///
+    /// # Returns
+    ///
+    /// - The number of documents removed.
+    pub fn remove_documents(&mut self, ids: &[String]) -> usize {
+        let ids: HashSet<&String> = ids.iter().collect();
+        let (removed, kept): (Vec<Document>, Vec<Document>) = std::mem::take(&mut self.documents)
+            .into_iter()
+            .partition(|doc| ids.contains(&doc.id));
+        self.documents = kept;
+        self.index_positions();
+        for index in &mut self.indexes {
+            for document in &removed {
+                index.remove(document);
+            }
+        }
+        removed.len()
+    }
+
+    /// Returns the positions of the documents where `field_name == field_value`, skipping documents
+    /// expired by a TTL index, like [`Collection::live_documents`].
+    fn matching_positions(&self, field_name: &str, field_value: &Value) -> Vec<usize> {
+        let now = now();
+        self.documents
+            .iter()
+            .enumerate()
+            .filter(|(_, doc)| {
+                get_nested_value(&doc.data, field_name) == Some(field_value)
+                    && !self.is_expired(doc, now)
+            })
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Maps the ID of every document to its position, after documents were removed or loaded.
+    fn index_positions(&mut self) {
+        self.positions = self
+            .documents
+            .iter()
+            .enumerate()
+            .map(|(position, doc)| (doc.id.clone(), position))
+            .collect();
+    }
+
+    /// Replaces the document at `position`, moving its indexed entries from the old content to the
+    /// new one.
+    fn replace_document(&mut self, position: usize, document: Document) {
+        for index in &mut self.indexes {
+            index.remove(&self.documents[position]);
+            index.insert(&document);
+        }
+        self.documents[position] = document;
+    }
 }
 
 impl Display for Collection {
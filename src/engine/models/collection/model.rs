@@ -1,6 +1,8 @@
 use crate::engine::models::document::model::Document;
+use crate::engine::models::index::model::Index;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// 🦀
 /// Represents a collection of documents within a NoSQL database.
@@ -15,6 +17,9 @@ use serde_json::Value;
 /// - `documents`: A vector containing all the documents in the collection.
 /// - `created_at`: The timestamp (in seconds since Unix epoch) when the collection was created.
 /// - `structure`: A JSON value defining the schema or structure of the documents within the collection.
+/// - `indexes`: The secondary [`Index`]es maintained for this collection.
+/// - `positions`: The position of each document in `documents`, by ID. Like index data, it is not
+///   persisted and is rebuilt by [`Collection::rebuild_indexes`].
 ///
 /// # Example
 ///
@@ -32,4 +37,8 @@ pub struct Collection {
     pub documents: Vec<Document>,
     pub created_at: u64,
     pub structure: Value,
+    #[serde(default)]
+    pub indexes: Vec<Index>,
+    #[serde(skip)]
+    pub(crate) positions: HashMap<String, usize>,
 }
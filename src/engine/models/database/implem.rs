@@ -1,6 +1,7 @@
 use super::model::Database;
 use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
 use crate::engine::models::collection::model::Collection;
+use crate::engine::models::segment::model::SegmentTable;
 use serde_json::Value;
 use std::fmt::Display;
 
@@ -42,6 +43,8 @@ impl Database {
     pub fn new(db_path: &str) -> Self {
         Database {
             collections: Vec::new(),
+            wal_sequence: 0,
+            segments: SegmentTable::default(),
         }
     }
 
@@ -293,6 +296,80 @@ impl Database {
     pub fn get_collection_mut(&mut self, name: &str) -> Option<&mut Collection> {
         self.collections.iter_mut().find(|c| c.name == name)
     }
+
+    /// 🦀
+    /// Rebuilds the in-memory indexes of every collection.
+    ///
+    /// Only index definitions are persisted, so this is called once after the database
+    /// has been loaded from disk.
+    ///
+    /// # See Also
+    ///
+    /// - [`Collection::rebuild_indexes`] — per-collection rebuild
+    pub fn rebuild_indexes(&mut self) {
+        for collection in &mut self.collections {
+            collection.rebuild_indexes();
+        }
+    }
+
+    /// 🦀
+    /// Permanently removes the expired documents of every collection that has a TTL index.
+    ///
+    /// # Parameters
+    ///
+    /// - `now`: The current Unix timestamp, in seconds.
+    ///
+    /// # Returns
+    ///
+    /// - The total number of documents removed.
+    ///
+    /// # See Also
+    ///
+    /// - [`Collection::purge_expired`] — per-collection purge
+    pub fn purge_expired(&mut self, now: u64) -> usize {
+        let mut purged = 0;
+        for collection in &mut self.collections {
+            let removed = collection.purge_expired(now);
+            if removed > 0 {
+                self.segments.dirty.insert(collection.name.clone());
+                purged += removed;
+            }
+        }
+        purged
+    }
+
+    /// 🦀
+    /// Records that loaded collections changed, so that their segments are rewritten on the next
+    /// save of a database stored with the segmented [`Layout`](crate::engine::models::Layout).
+    ///
+    /// # Parameters
+    ///
+    /// - `names`: The names of the changed collections, or `None` for every loaded collection.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+    /// use nosqlite_rust::engine::models::Database;
+    ///
+    /// let mut db = Database::new("temp/data74.nosqlite");
+    /// let mut handler = NosqliteErrorHandler::new("temp/data74.nosqlite".to_string());
+    /// db.add_collection("users", json!({}), &mut handler)?;
+    /// db.add_collection("orders", json!({}), &mut handler)?;
+    ///
+    /// db.mark_dirty(Some(vec!["users"]));
+    /// assert!(db.segments.dirty.contains("users"));
+    /// assert!(!db.segments.dirty.contains("orders"));
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn mark_dirty(&mut self, names: Option<Vec<&str>>) {
+        let dirty = &mut self.segments.dirty;
+        match names {
+            Some(names) => dirty.extend(names.into_iter().map(str::to_string)),
+            None => dirty.extend(self.collections.iter().map(|c| c.name.clone())),
+        }
+    }
 }
 
 impl Display for Database {
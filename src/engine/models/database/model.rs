@@ -1,3 +1,4 @@
+use crate::engine::models::segment::model::SegmentTable;
 use crate::engine::{error::NosqliteErrorHandler, models::collection::model::Collection};
 use serde::{Deserialize, Serialize};
 
@@ -12,17 +13,27 @@ use serde::{Deserialize, Serialize};
 /// # Fields
 ///
 /// - `collections`: A vector of [`Collection`] items that belong to this database.
+/// - `wal_sequence`: The sequence number of the last write-ahead log record included in this
+///   database. Records up to it are skipped when the log is replayed.
+/// - `segments`: The [`SegmentTable`] of a database stored with the segmented
+///   [`Layout`](crate::engine::models::Layout). It is not serialized.
 ///
 /// # Example
 ///
 /// ```rust
-/// use nosqlite_rust::engine::models::Database;
+/// use nosqlite_rust::engine::models::{Database, SegmentTable};
 ///
 /// let db = Database {
 ///     collections: vec![],
+///     wal_sequence: 0,
+///     segments: SegmentTable::default(),
 /// };
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Database {
     pub collections: Vec<Collection>,
+    #[serde(default)]
+    pub wal_sequence: u64,
+    #[serde(skip)]
+    pub segments: SegmentTable,
 }
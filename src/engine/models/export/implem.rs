@@ -0,0 +1,47 @@
+use crate::engine::models::export::model::ExportFormat;
+use std::fmt::Display;
+use std::path::Path;
+
+impl ExportFormat {
+    /// 🦀
+    /// Parses a format name: `json`, `ndjson` (or `jsonl`) and `csv`, case-insensitively.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(ExportFormat)` for a known name.
+    /// - `None` otherwise.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(ExportFormat::Json),
+            "ndjson" | "jsonl" => Some(ExportFormat::Ndjson),
+            "csv" => Some(ExportFormat::Csv),
+            _ => None,
+        }
+    }
+
+    /// 🦀
+    /// Guesses the format of a file from its extension, as parsed by [`ExportFormat::from_name`].
+    ///
+    /// # Returns
+    ///
+    /// - `Some(ExportFormat)` if the extension names a format.
+    /// - `None` if the path has no extension, or an unknown one.
+    pub fn from_path(path: &str) -> Option<Self> {
+        Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(Self::from_name)
+    }
+}
+
+impl Display for ExportFormat {
+    /// 🦀
+    /// Formats the [`ExportFormat`] as its name, as accepted by [`ExportFormat::from_name`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Json => write!(f, "json"),
+            ExportFormat::Ndjson => write!(f, "ndjson"),
+            ExportFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
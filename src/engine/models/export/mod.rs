@@ -0,0 +1,8 @@
+//! # Export Model
+//!
+//! This module defines the formats collections are exported to and imported from.
+
+pub mod implem;
+pub mod model;
+
+pub use model::ExportFormat;
@@ -0,0 +1,29 @@
+/// 🦀
+/// The format of documents exported by [`crate::engine::Nosqlite::export_collection`] and imported
+/// by [`crate::engine::Nosqlite::import_collection`].
+///
+/// Only the content of the documents is exchanged: imported documents get new identifiers and
+/// timestamps.
+///
+/// # Variants
+///
+/// - `Json`: A single JSON array of objects.
+/// - `Ndjson`: One JSON object per line (newline-delimited JSON).
+/// - `Csv`: One row per document, after a header row. Nested fields are flattened into columns
+///   named by their dot path (e.g. `address.city`); arrays are written as JSON text, and a missing
+///   field is an empty cell.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::ExportFormat;
+///
+/// assert_eq!(ExportFormat::from_path("users.csv"), Some(ExportFormat::Csv));
+/// assert_eq!(ExportFormat::from_path("users.nosqlite"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Ndjson,
+    Csv,
+}
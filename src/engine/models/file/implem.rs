@@ -1,177 +1,1289 @@
 use crate::engine::error::NosqliteError;
-use crate::engine::models::file::model::File;
+use crate::engine::models::file::model::{
+    EncryptionKey, File, FileHeader, KdfHeader, PayloadStats,
+};
+use crate::engine::models::options::model::{
+    Compression, Encoding, Kdf, KeySource, Layout, NosqliteOptions,
+};
+use crate::engine::models::segment::model::SegmentCatalog;
+use crate::engine::models::storage::model::{FileBackend, StorageBackend};
+use crate::engine::models::wal::model::WalRecord;
+use crate::engine::models::Collection;
 use crate::engine::{error::NosqliteErrorHandler, models::database::model::Database};
 use aes_gcm::{
     aead::{Aead, KeyInit, OsRng},
     AeadCore, Aes256Gcm, Key, Nonce,
 };
 use base64::{engine::general_purpose, Engine as _};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
 use std::{fs, path::Path};
 
-const DEFAULT_KEY_PATH: &str = "db.key";
+/// Key file used when no other [`KeySource`] is configured, relative to the working directory.
+pub const DEFAULT_KEY_PATH: &str = "db.key";
+/// Version of the on-disk format written by this build, see [`FileHeader::version`].
+pub const FORMAT_VERSION: u32 = 4;
+/// Marker starting the plaintext header line of a database file.
+const HEADER_PREFIX: &str = "NOSQLITE ";
+/// Length of the random salt of a passphrase-derived key, in bytes.
+const SALT_LENGTH: usize = 16;
+
+impl File {
+    /// 🦀
+    /// Loads the database from disk, or creates a new one if the file does not exist.
+    ///
+    /// This function attempts to read, decrypt, and deserialize a database file into a [`Database`] instance.
+    /// If the file does not exist, a new in-memory database is created using the specified path.
+    ///
+    /// It also ensures a valid encryption key is available by attempting to load or generate one
+    /// from the default key path (`DEFAULT_KEY_PATH`). Use [`File::load_or_create_with_options`]
+    /// to read the key from another [`KeySource`].
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The filesystem path to the encrypted `.nosqlite` database file.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging and error tracking.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Database)` if the database was successfully loaded or created.
+    /// - `Err(NosqliteError)` if any stage of key loading, file reading, decryption, or deserialization fails.
+    ///
+    /// # Behavior
+    ///
+    /// - If the file **exists**:
+    ///   1. Reads its contents as an encrypted string
+    ///   2. Attempts decryption using the AES key from `DEFAULT_KEY_PATH`
+    ///   3. Attempts deserialization of the decrypted JSON into a [`Database`]
+    ///
+    /// - If the file **does not exist**:
+    ///   - Returns a new `Database` instance with no collections
+    ///
+    /// # Panics
+    ///
+    /// This method **may panic** only in unrecoverable conditions such as:
+    /// - Inability to open the database file (e.g. permissions issues not caught earlier)
+    /// - Internal `expect()`/`unwrap()` usage in key/decryption layers
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+    /// use nosqlite_rust::engine::models::File;
+    ///
+    /// let mut handler = NosqliteErrorHandler::new("temp/data42.nosqlite".to_string());
+    /// let db = File::load_or_create("temp/data42.nosqlite", &mut handler)?;
+    /// println!("{}", db);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # Security Notes
+    ///
+    /// - Encryption is handled via `File::decrypt`
+    /// - Keys are managed via `File::load_or_generate_key` and stored at `DEFAULT_KEY_PATH`
+    /// - Ensure secure handling of key and file paths in production deployments
+    ///
+    /// # See Also
+    ///
+    /// - [`Database::new`] — creates a new empty database
+    /// - [`NosqliteErrorHandler`] — handles structured error logging
+    /// - [`NosqliteError`] — error type used throughout the system
+    pub fn load_or_create(
+        db_path: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Database, NosqliteError> {
+        Self::load_or_create_with_options(db_path, &NosqliteOptions::default(), handler)
+    }
+
+    /// 🦀
+    /// Loads the database from disk, or creates a new one, using the key described by `options`.
+    ///
+    /// A file-based key ([`KeySource::File`], [`KeySource::Sidecar`]) is only generated when the
+    /// database does not exist yet; opening an existing database without its key file fails.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The filesystem path to the encrypted `.nosqlite` database file.
+    /// - `options`: The [`NosqliteOptions`] holding the [`KeySource`] to use.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging and error tracking.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Database)` if the database was successfully loaded or created.
+    /// - `Err(NosqliteError::InvalidKey)` if the key cannot be found or is malformed.
+    /// - `Err(NosqliteError::KeyMismatch)` if the key does not decrypt the database.
+    /// - `Err(NosqliteError)` for any other read or deserialization failure.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+    /// use nosqlite_rust::engine::models::{File, KeySource, NosqliteOptions};
+    ///
+    /// let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([7u8; 32]));
+    /// let mut handler = NosqliteErrorHandler::new("temp/data54.nosqlite".to_string());
+    /// let db = File::load_or_create_with_options("temp/data54.nosqlite", &options, &mut handler)?;
+    /// File::save_with_options("temp/data54.nosqlite", &db, &options, &mut handler)?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn load_or_create_with_options(
+        db_path: &str,
+        options: &NosqliteOptions,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Database, NosqliteError> {
+        let key = Self::resolve_key(db_path, options, handler)?;
+        Self::load_or_create_with_key(db_path, &key, handler)
+    }
+
+    /// 🦀
+    /// Loads the database from disk with an already resolved key, or creates a new one if the file does not exist.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The filesystem path to the encrypted `.nosqlite` database file.
+    /// - `key`: The [`EncryptionKey`] returned by [`File::resolve_key`].
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Database)` if the database was successfully loaded or created.
+    /// - `Err(NosqliteError::KeyMismatch)` if the key does not decrypt the database.
+    /// - `Err(NosqliteError)` for any other read, header or deserialization failure.
+    ///
+    /// A database stored with the segmented [`Layout`] is loaded whole: every segment is read.
+    pub fn load_or_create_with_key(
+        db_path: &str,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Database, NosqliteError> {
+        match Self::read_payload(db_path, key, handler)? {
+            Some((header, payload)) if !header.layout.is_single() => {
+                let catalog = Self::catalog_from_value(payload, handler)?;
+                let mut backend = FileBackend::new(db_path);
+                let mut db = Database::new(db_path);
+                db.wal_sequence = catalog.wal_sequence;
+                for entry in &catalog.collections {
+                    let (_, payload) =
+                        Self::read_segment(&mut backend, &entry.segment, key, handler)?;
+                    db.collections
+                        .push(Self::collection_from_value(payload, handler)?);
+                }
+                db.segments.stored = catalog.collections;
+                Ok(db)
+            }
+            Some((_, payload)) => Self::database_from_value(payload, handler),
+            None => Ok(Database::new(db_path)),
+        }
+    }
+
+    /// 🦀
+    /// Reads and decrypts a database file into its [`FileHeader`] and JSON payload, without
+    /// deserializing it into a [`Database`].
+    ///
+    /// This is the first step of loading a database written by an older format version: the payload
+    /// can be upgraded by the migrations of
+    /// [`crate::engine::services::database_service::migrate_database`] before being deserialized
+    /// with [`File::database_from_value`].
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The filesystem path to the `.nosqlite` database file.
+    /// - `key`: The [`EncryptionKey`] returned by [`File::resolve_key`].
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(None)` if the file does not exist.
+    /// - `Ok(Some((header, payload)))` with the header and the decrypted JSON.
+    /// - `Err(NosqliteError::UnsupportedFormatVersion)` if the file was written by a newer version.
+    /// - `Err(NosqliteError)` if the file cannot be read, decrypted or parsed as JSON.
+    pub fn read_payload(
+        db_path: &str,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<(FileHeader, serde_json::Value)>, NosqliteError> {
+        match FileBackend::new(db_path).load(handler)? {
+            Some(content) => Self::decode_payload(db_path, &content, key, handler).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// 🦀
+    /// Decrypts and decodes the content of a database file into its [`FileHeader`] and payload,
+    /// whatever the [`Encoding`] recorded in the header.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_name`: The name of the database, used in error messages.
+    /// - `content`: The bytes of the file, as returned by [`StorageBackend::load`].
+    /// - `key`: The [`EncryptionKey`] returned by [`File::resolve_key`].
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((header, payload))` with the header and the decrypted payload, as a JSON value.
+    /// - `Err(NosqliteError::UnsupportedFormatVersion)` if the file was written by a newer version.
+    /// - `Err(NosqliteError::Corrupted)` if the payload does not match the checksum of the header.
+    /// - `Err(NosqliteError)` if the content cannot be decrypted or decoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+    /// use nosqlite_rust::engine::models::{Database, EncryptionKey, File};
+    ///
+    /// let mut handler = NosqliteErrorHandler::new("temp/data71.nosqlite".to_string());
+    /// let key = EncryptionKey::new([8u8; 32], None);
+    /// let content = File::encode_database(&Database::default(), &key, &mut handler)?;
+    ///
+    /// let (header, payload) = File::decode_payload("memory", &content, &key, &mut handler)?;
+    /// assert!(payload["collections"].as_array().unwrap().is_empty());
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn decode_payload(
+        db_name: &str,
+        content: &[u8],
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(FileHeader, serde_json::Value), NosqliteError> {
+        let (header, payload) = Self::decrypt_payload(db_name, content, key, handler)?;
+        let payload = Self::decompress(&header, &payload, handler)?;
+
+        let value = match header.encoding {
+            Encoding::Json => handler.try_or_log(serde_json::from_slice(&payload), |e| {
+                NosqliteError::InvalidDatabaseFormat(format!("Failed to parse database: {}", e))
+            })?,
+            Encoding::MessagePack => handler.try_or_log(rmp_serde::from_slice(&payload), |e| {
+                NosqliteError::InvalidDatabaseFormat(format!("Failed to parse database: {}", e))
+            })?,
+        };
+        Ok((header, value))
+    }
+
+    /// 🦀
+    /// Decodes the content of a database file as [`File::decode_payload`] does, but without
+    /// verifying its checksum, to salvage what is still readable from a damaged file.
+    ///
+    /// An encrypted payload is still authenticated by AES-GCM, so only unencrypted files can be
+    /// read past a checksum mismatch.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((header, payload))` with the header and the decoded payload, as a JSON value.
+    /// - `Err(NosqliteError)` if the content cannot be decrypted or decoded.
+    pub fn decode_unverified_payload(
+        db_name: &str,
+        content: &[u8],
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(FileHeader, serde_json::Value), NosqliteError> {
+        let (header, payload) = Self::split_header(content, handler)?;
+        let header = FileHeader {
+            checksum: None,
+            ..header
+        };
+        let header = handler.try_or_log(serde_json::to_string(&header), |e| {
+            NosqliteError::SerializationError(e.to_string())
+        })?;
+        let mut content = format!("{}{}\n", HEADER_PREFIX, header).into_bytes();
+        content.extend(payload);
+        Self::decode_payload(db_name, &content, key, handler)
+    }
+
+    /// 🦀
+    /// Measures the payload of a database file: its size once serialized, and once compressed.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_name`: The name of the database, used in error messages.
+    /// - `content`: The bytes of the file, as returned by [`StorageBackend::load`].
+    /// - `key`: The [`EncryptionKey`] the payload is encrypted with.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PayloadStats)` with the sizes, [`Encoding`] and [`Compression`] of the payload.
+    /// - `Err(NosqliteError)` if the content cannot be decrypted or decompressed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+    /// use nosqlite_rust::engine::models::{Compression, Database, EncryptionKey, File};
+    ///
+    /// let mut handler = NosqliteErrorHandler::new("temp/data72.nosqlite".to_string());
+    /// let mut db = Database::default();
+    /// db.add_collection("users", json!({ "name": "string" }), &mut handler)?;
+    /// let key = EncryptionKey::new([5u8; 32], None).with_compression(Compression::Zstd);
+    /// let content = File::encode_database(&db, &key, &mut handler)?;
+    ///
+    /// let stats = File::payload_stats("memory", &content, &key, &mut handler)?;
+    /// assert_eq!(stats.compression, Compression::Zstd);
+    /// assert!(stats.compressed_bytes < stats.serialized_bytes);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn payload_stats(
+        db_name: &str,
+        content: &[u8],
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<PayloadStats, NosqliteError> {
+        let (header, compressed) = Self::decrypt_payload(db_name, content, key, handler)?;
+        let serialized = Self::decompress(&header, &compressed, handler)?;
+        Ok(PayloadStats {
+            encoding: header.encoding,
+            compression: header.compression,
+            file_bytes: content.len() as u64,
+            serialized_bytes: serialized.len() as u64,
+            compressed_bytes: compressed.len() as u64,
+        })
+    }
+
+    /// 🦀
+    /// Splits the content of a database file into its [`FileHeader`] and decrypted payload, still
+    /// compressed. The checksum of the header, if any, is verified first.
+    fn decrypt_payload(
+        db_name: &str,
+        content: &[u8],
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(FileHeader, Vec<u8>), NosqliteError> {
+        let db_path = db_name;
+        let (header, payload) = Self::split_header(content, handler)?;
+        if header.version > FORMAT_VERSION {
+            let error = NosqliteError::UnsupportedFormatVersion(format!(
+                "database '{}' uses format version {}, this build reads up to version {}",
+                db_path, header.version, FORMAT_VERSION
+            ));
+            handler.log_error(error.clone());
+            return Err(error);
+        }
+        if let Some(expected) = &header.checksum {
+            if *expected != checksum_of(payload) {
+                let error =
+                    NosqliteError::Corrupted(format!("'{}' does not match its checksum", db_path));
+                handler.log_error(error.clone());
+                return Err(error);
+            }
+        }
+        let payload = match (header.encoding, header.plaintext) {
+            (_, true) => payload.to_vec(),
+            (Encoding::Json, false) => {
+                let encrypted = Self::content_as_str(payload, handler)?;
+                let decoded = handler
+                    .try_or_log(general_purpose::STANDARD.decode(encrypted), |e| {
+                        NosqliteError::Base64DecodeError(e.to_string())
+                    })?;
+                Self::decrypt_with_any(db_path, &decoded, &header, key, handler)?
+            }
+            (Encoding::MessagePack, false) => {
+                Self::decrypt_with_any(db_path, payload, &header, key, handler)?
+            }
+        };
+        Ok((header, payload))
+    }
+
+    /// 🦀
+    /// Decompresses a decrypted payload with the [`Compression`] recorded in its header.
+    fn decompress(
+        header: &FileHeader,
+        payload: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
+        handler.try_or_log(header.compression.decompress(payload), |e| {
+            NosqliteError::InvalidDatabaseFormat(format!(
+                "Failed to decompress database ({:?}): {}",
+                header.compression, e
+            ))
+        })
+    }
+
+    /// 🦀
+    /// Deserializes the JSON payload of a database file into a [`Database`] and rebuilds its indexes.
+    ///
+    /// # Parameters
+    ///
+    /// - `payload`: The JSON payload, in the current format version.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Database)` with in-memory indexes rebuilt.
+    /// - `Err(NosqliteError::InvalidDatabaseFormat)` if the payload does not describe a database.
+    pub fn database_from_value(
+        payload: serde_json::Value,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Database, NosqliteError> {
+        let mut db: Database = handler
+            .try_or_log(serde_json::from_value(payload), |e| {
+                NosqliteError::DeserializationError(e.to_string())
+            })
+            .map_err(|_| {
+                let err = NosqliteError::InvalidDatabaseFormat(
+                    "Failed to deserialize database".to_string(),
+                );
+                handler.log_error(err.clone());
+                err
+            })?;
+        db.rebuild_indexes();
+        Ok(db)
+    }
+
+    /// 🦀
+    /// Deserializes the payload of a database file stored with the segmented [`Layout`] into its
+    /// [`SegmentCatalog`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(SegmentCatalog)` with the collections and their segments.
+    /// - `Err(NosqliteError::InvalidDatabaseFormat)` if the payload does not describe a catalog.
+    pub fn catalog_from_value(
+        payload: serde_json::Value,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<SegmentCatalog, NosqliteError> {
+        handler.try_or_log(serde_json::from_value(payload), |e| {
+            NosqliteError::InvalidDatabaseFormat(format!("Failed to parse segment catalog: {}", e))
+        })
+    }
+
+    /// 🦀
+    /// Deserializes the payload of a segment into a [`Collection`] and rebuilds its indexes.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Collection)` with in-memory indexes rebuilt.
+    /// - `Err(NosqliteError::InvalidDatabaseFormat)` if the payload does not describe a collection.
+    pub fn collection_from_value(
+        payload: serde_json::Value,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Collection, NosqliteError> {
+        let mut collection: Collection = handler
+            .try_or_log(serde_json::from_value(payload), |e| {
+                NosqliteError::InvalidDatabaseFormat(format!("Failed to parse segment: {}", e))
+            })?;
+        collection.rebuild_indexes();
+        Ok(collection)
+    }
+
+    /// 🦀
+    /// Reads and decrypts a segment of a database stored with the segmented [`Layout`].
+    ///
+    /// # Parameters
+    ///
+    /// - `backend`: The [`StorageBackend`] holding the database.
+    /// - `id`: The identifier of the segment, as listed in the [`SegmentCatalog`].
+    /// - `key`: The [`EncryptionKey`] of the database.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok((header, payload))` with the header of the segment and its payload: one collection.
+    /// - `Err(NosqliteError::InvalidDatabaseFormat)` if the segment does not exist.
+    /// - `Err(NosqliteError)` if it cannot be read, decrypted or decoded.
+    pub fn read_segment(
+        backend: &mut dyn StorageBackend,
+        id: &str,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(FileHeader, serde_json::Value), NosqliteError> {
+        let name = format!("{} (segment {})", backend.name(), id);
+        let Some(content) = backend.load_segment(id, handler)? else {
+            let error = NosqliteError::InvalidDatabaseFormat(format!("{} is missing", name));
+            handler.log_error(error.clone());
+            return Err(error);
+        };
+        Self::decode_payload(&name, &content, key, handler)
+    }
+
+    /// 🦀
+    /// Saves the [`Database`] to disk in encrypted form.
+    ///
+    /// This method serializes the entire database to pretty-printed JSON, encrypts it
+    /// using AES-256-GCM, and writes the result to the provided path. It ensures that all
+    /// saved data remains secure and tamper-resistant.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The file path where the encrypted database should be written.
+    /// - `db`: The [`Database`] instance to be serialized and persisted.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging of any I/O,
+    ///   serialization, or encryption errors.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the database was successfully saved
+    /// - `Err(NosqliteError)` if any step in the pipeline fails
+    ///
+    /// # Pipeline Steps
+    ///
+    /// 1. **Key management**: Loads or generates an AES encryption key via `load_or_generate_key`
+    /// 2. **Serialization**: Converts the `Database` instance to pretty-printed JSON
+    /// 3. **Encryption**: Encrypts the JSON payload with AES-256-GCM
+    /// 4. **File write**: Writes the encrypted data to a temporary file, flushes it and renames it
+    ///    over `db_path`, so that an interrupted save leaves the previous version intact
+    ///
+    /// # Panics
+    ///
+    /// While this method uses structured error logging (`try_or_log`) throughout, it may still
+    /// panic in rare cases (e.g. internal `expect()` calls in utility methods).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::{Database, File};
+    /// use nosqlite_rust::engine::error::NosqliteErrorHandler;
+    ///
+    /// let db = Database::default();
+    /// let mut handler = NosqliteErrorHandler::new("temp/data43.nosqlite".to_string());
+    ///
+    /// File::save("temp/data43.nosqlite", &db, &mut handler).expect("Failed to save database");
+    /// ```
+    ///
+    /// # Security Notes
+    ///
+    /// - Encryption is performed using AES-256-GCM with keys stored at `DEFAULT_KEY_PATH`
+    /// - The resulting file at `db_path` is **not human-readable**
+    /// - For plaintext backups or inspection, implement a `save_as_json()` variant
+    ///
+    /// # See Also
+    ///
+    /// - [`File::load_or_create`] — for loading the encrypted database back
+    /// - [`NosqliteError`] — unified error type
+    /// - [`NosqliteErrorHandler`] — used for logging any failures in the pipeline
+    pub fn save(
+        db_path: &str,
+        db: &Database,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        Self::save_with_options(db_path, db, &NosqliteOptions::default(), handler)
+    }
+
+    /// 🦀
+    /// Saves the [`Database`] to disk in encrypted form, using the key described by `options`.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The file path where the encrypted database should be written.
+    /// - `db`: The [`Database`] instance to be serialized and persisted.
+    /// - `options`: The [`NosqliteOptions`] holding the [`KeySource`] to use.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the database was successfully saved
+    /// - `Err(NosqliteError)` if the key cannot be obtained or any step of the pipeline fails
+    ///
+    /// # See Also
+    ///
+    /// - [`File::save`] — same pipeline with the default key file
+    /// - [`File::load_or_create_with_options`] — for loading the database back
+    pub fn save_with_options(
+        db_path: &str,
+        db: &Database,
+        options: &NosqliteOptions,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let key = Self::resolve_key(db_path, options, handler)?;
+        Self::save_with_key(db_path, db, &key, handler)
+    }
+
+    /// 🦀
+    /// Saves the [`Database`] to disk in encrypted form with an already resolved key.
+    ///
+    /// The [`FileHeader`] of the key is written in plaintext on the first line of the file, stamped
+    /// with the current [`FORMAT_VERSION`]. A key from [`EncryptionKey::unencrypted`] writes the pretty-printed JSON
+    /// as is after the header.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The file path where the encrypted database should be written.
+    /// - `db`: The [`Database`] instance to be serialized and persisted.
+    /// - `key`: The [`EncryptionKey`] returned by [`File::resolve_key`] or [`File::derive_key`].
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the database was successfully saved
+    /// - `Err(NosqliteError)` if serialization, encryption or the file write fails
+    pub fn save_with_key(
+        db_path: &str,
+        db: &Database,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let content = Self::encode_database(db, key, handler)?;
+        FileBackend::new(db_path).save(&content, handler)
+    }
+
+    /// 🦀
+    /// Serializes and encrypts a [`Database`] into the content of a database file, as written by
+    /// [`File::save_with_key`]: the plaintext [`FileHeader`] line, then the payload serialized in the
+    /// [`Encoding`] of the key header, compressed with its [`Compression`], and encrypted (as base64
+    /// text for JSON, raw bytes for MessagePack).
+    ///
+    /// # Parameters
+    ///
+    /// - `db`: The [`Database`] instance to serialize.
+    /// - `key`: The [`EncryptionKey`] to encrypt with.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<u8>)` with the content of the file.
+    /// - `Err(NosqliteError)` if serialization or encryption fails.
+    pub fn encode_database(
+        db: &Database,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
+        Self::encode_payload(db, Layout::Single, key, handler)
+    }
+
+    /// 🦀
+    /// Serializes and encrypts a [`SegmentCatalog`] into the content of the data file of a database
+    /// stored with the segmented [`Layout`], as [`File::encode_database`] does for a whole database.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+    /// use nosqlite_rust::engine::models::{EncryptionKey, File, Layout, SegmentCatalog};
+    ///
+    /// let mut handler = NosqliteErrorHandler::new("temp/data75.nosqlite".to_string());
+    /// let key = EncryptionKey::new([4u8; 32], None);
+    /// let content = File::encode_catalog(&SegmentCatalog::default(), &key, &mut handler)?;
+    ///
+    /// let (header, payload) = File::decode_payload("memory", &content, &key, &mut handler)?;
+    /// assert_eq!(header.layout, Layout::Segmented);
+    /// assert!(File::catalog_from_value(payload, &mut handler)?.collections.is_empty());
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn encode_catalog(
+        catalog: &SegmentCatalog,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
+        Self::encode_payload(catalog, Layout::Segmented, key, handler)
+    }
+
+    /// 🦀
+    /// Serializes and encrypts a [`Collection`] into the content of a segment, with the same header
+    /// and payload format as a database file.
+    pub fn encode_segment(
+        collection: &Collection,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
+        Self::encode_payload(collection, Layout::Single, key, handler)
+    }
+
+    /// 🦀
+    /// Writes the header line stamped with `layout`, then `value` serialized, compressed and
+    /// encrypted as described by the header of `key`.
+    fn encode_payload<T: Serialize>(
+        value: &T,
+        layout: Layout,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
+        let serialized = match key.header.encoding {
+            Encoding::Json => handler.try_or_log(serde_json::to_vec_pretty(value), |e| {
+                NosqliteError::SerializationError(e.to_string())
+            })?,
+            Encoding::MessagePack => handler.try_or_log(rmp_serde::to_vec_named(value), |e| {
+                NosqliteError::SerializationError(e.to_string())
+            })?,
+        };
+        let compression = key.header.compression;
+        let compressed = handler.try_or_log(compression.compress(&serialized), |e| {
+            NosqliteError::SerializationError(format!(
+                "Failed to compress database ({:?}): {}",
+                compression, e
+            ))
+        })?;
+        let payload = match (key.header.encoding, key.header.plaintext) {
+            (_, true) => compressed,
+            (Encoding::Json, false) => {
+                let encrypted = Self::encrypt_bytes(&compressed, &key.key, handler)?;
+                general_purpose::STANDARD.encode(&encrypted).into_bytes()
+            }
+            (Encoding::MessagePack, false) => Self::encrypt_bytes(&compressed, &key.key, handler)?,
+        };
+        let header = FileHeader {
+            version: FORMAT_VERSION,
+            layout,
+            checksum: Some(checksum_of(&payload)),
+            ..key.header.clone()
+        };
+        let header = handler.try_or_log(serde_json::to_string(&header), |e| {
+            NosqliteError::SerializationError(e.to_string())
+        })?;
+        let mut content = format!("{}{}\n", HEADER_PREFIX, header).into_bytes();
+        content.extend(payload);
+        Ok(content)
+    }
+
+    /// 🦀
+    /// Returns the path of the write-ahead log of a database: the database path with the `.wal`
+    /// extension (e.g. `data/app.wal` for `data/app.nosqlite`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::File;
+    ///
+    /// assert_eq!(File::wal_path("data/app.nosqlite"), "data/app.wal");
+    /// ```
+    pub fn wal_path(db_path: &str) -> String {
+        Path::new(db_path)
+            .with_extension("wal")
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// 🦀
+    /// Returns the directory holding the segments of a database stored with the segmented
+    /// [`Layout`]: the database path with the `.segments` extension.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::File;
+    ///
+    /// assert_eq!(File::segments_path("data/app.nosqlite"), "data/app.segments");
+    /// ```
+    pub fn segments_path(db_path: &str) -> String {
+        Path::new(db_path)
+            .with_extension("segments")
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// 🦀
+    /// Returns the directory holding the named snapshots of a database: the database path with the
+    /// `.snapshots` extension.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::File;
+    ///
+    /// assert_eq!(File::snapshots_path("data/app.nosqlite"), "data/app.snapshots");
+    /// ```
+    pub fn snapshots_path(db_path: &str) -> String {
+        Path::new(db_path)
+            .with_extension("snapshots")
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// 🦀
+    /// Appends records to the write-ahead log of a database and flushes them to disk.
+    ///
+    /// Each record is written on its own line, encrypted and authenticated separately with
+    /// AES-256-GCM, so that the cost of an append only depends on the size of the records. With an
+    /// [`EncryptionKey::unencrypted`] key, records are written as plain JSON.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The path of the database; the log is stored at [`File::wal_path`].
+    /// - `records`: The [`WalRecord`]s to append.
+    /// - `key`: The [`EncryptionKey`] of the database.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(u64)` with the size of the log after the append, in bytes.
+    /// - `Err(NosqliteError)` if serialization, encryption or the write fails.
+    pub fn append_wal(
+        db_path: &str,
+        records: &[WalRecord],
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<u64, NosqliteError> {
+        let content = Self::encode_wal(records, key, handler)?;
+        FileBackend::new(db_path).append_log(&content, handler)
+    }
+
+    /// 🦀
+    /// Serializes and encrypts records into lines of a write-ahead log, as appended by
+    /// [`File::append_wal`].
+    ///
+    /// # Parameters
+    ///
+    /// - `records`: The [`WalRecord`]s to encode.
+    /// - `key`: The [`EncryptionKey`] of the database.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<u8>)` with one line per record.
+    /// - `Err(NosqliteError)` if serialization or encryption fails.
+    pub fn encode_wal(
+        records: &[WalRecord],
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
+        let mut content = String::new();
+        for record in records {
+            let json = handler.try_or_log(serde_json::to_string(record), |e| {
+                NosqliteError::SerializationError(e.to_string())
+            })?;
+            if key.header.plaintext {
+                content.push_str(&json);
+            } else {
+                content.push_str(&Self::encrypt(&json, &key.key, handler)?);
+            }
+            content.push('\n');
+        }
+        Ok(content.into_bytes())
+    }
+
+    /// 🦀
+    /// Reads the records of the write-ahead log of a database, in order.
+    ///
+    /// A last line without its terminating newline is the trace of an append interrupted by a crash:
+    /// it was never acknowledged, so it is ignored. Any other record that cannot be authenticated or
+    /// parsed makes the whole log unreadable.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The path of the database; the log is read from [`File::wal_path`].
+    /// - `key`: The [`EncryptionKey`] of the database; its fallback keys are tried too.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<WalRecord>)` with the complete records, empty if there is no log.
+    /// - `Err(NosqliteError::InvalidDatabaseFormat)` if a complete record is corrupted.
+    pub fn read_wal(
+        db_path: &str,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<WalRecord>, NosqliteError> {
+        match FileBackend::new(db_path).load_log(handler)? {
+            Some(content) => Self::decode_wal(&Self::wal_path(db_path), &content, key, handler),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// 🦀
+    /// Decrypts the content of a write-ahead log into its records, as read by [`File::read_wal`].
+    ///
+    /// # Parameters
+    ///
+    /// - `wal_name`: The name of the log, used in error messages.
+    /// - `content`: The bytes of the log, as returned by [`StorageBackend::load_log`].
+    /// - `key`: The [`EncryptionKey`] of the database; its fallback keys are tried too.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<WalRecord>)` with the complete records.
+    /// - `Err(NosqliteError::InvalidDatabaseFormat)` if a complete record is corrupted.
+    pub fn decode_wal(
+        wal_name: &str,
+        content: &[u8],
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<WalRecord>, NosqliteError> {
+        match Self::decode_wal_prefix(wal_name, content, key, handler) {
+            (records, None) => Ok(records),
+            (_, Some(error)) => Err(error),
+        }
+    }
 
-impl File {
     /// 🦀
-    /// Loads the database from disk, or creates a new one if the file does not exist.
+    /// Decrypts the records of a write-ahead log up to the first corrupted one, as needed to salvage
+    /// a damaged database.
     ///
-    /// This function attempts to read, decrypt, and deserialize a database file into a [`Database`] instance.
-    /// If the file does not exist, a new in-memory database is created using the specified path.
+    /// # Parameters
     ///
-    /// It also ensures a valid encryption key is available by attempting to load or generate one
-    /// from the default key path (`DEFAULT_KEY_PATH`).
+    /// - `wal_name`: The name of the log, used in error messages.
+    /// - `content`: The bytes of the log, as returned by [`StorageBackend::load_log`].
+    /// - `key`: The [`EncryptionKey`] of the database; its fallback keys are tried too.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
+    ///
+    /// # Returns
+    ///
+    /// The complete records before the first corrupted one, and the
+    /// [`NosqliteError::InvalidDatabaseFormat`] describing that record, if any. Records after it are
+    /// not read: they may depend on the lost one.
+    pub fn decode_wal_prefix(
+        wal_name: &str,
+        content: &[u8],
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> (Vec<WalRecord>, Option<NosqliteError>) {
+        let content = String::from_utf8_lossy(content);
+
+        let mut lines: Vec<&str> = content.split('\n').collect();
+        lines.pop();
+
+        let mut records = Vec::with_capacity(lines.len());
+        for (position, line) in lines.into_iter().enumerate() {
+            let json = if key.header.plaintext {
+                Some(line.to_string())
+            } else {
+                std::iter::once(&key.key)
+                    .chain(key.fallback_keys.iter())
+                    .find_map(|candidate| Self::decrypt(line, candidate, handler).ok())
+            };
+            let record = json.and_then(|json| serde_json::from_str::<WalRecord>(&json).ok());
+            let Some(record) = record else {
+                let error = NosqliteError::InvalidDatabaseFormat(format!(
+                    "write-ahead log '{}' is corrupted at record {}",
+                    wal_name,
+                    position + 1
+                ));
+                handler.log_error(error.clone());
+                return (records, Some(error));
+            };
+            records.push(record);
+        }
+        (records, None)
+    }
+
+    /// 🦀
+    /// Deletes the write-ahead log of a database, once its records are part of the saved database.
     ///
     /// # Parameters
     ///
-    /// - `db_path`: The filesystem path to the encrypted `.nosqlite` database file.
-    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging and error tracking.
+    /// - `db_path`: The path of the database.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
     ///
     /// # Returns
     ///
-    /// - `Ok(Database)` if the database was successfully loaded or created.
-    /// - `Err(NosqliteError)` if any stage of key loading, file reading, decryption, or deserialization fails.
+    /// - `Ok(())` if the log was deleted or did not exist.
+    /// - `Err(NosqliteError::IoError)` if it cannot be deleted.
+    pub fn remove_wal(
+        db_path: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        FileBackend::new(db_path).clear_log(handler)
+    }
+
+    /// 🦀
+    /// Obtains the key of a database from the [`KeySource`] of `options`.
     ///
-    /// # Behavior
+    /// For an existing database, its plaintext header is read first: a passphrase is derived with the
+    /// salt and parameters stored there. For a new database, a passphrase is derived with
+    /// `options.kdf` and a fresh random salt, and a missing key file is generated.
     ///
-    /// - If the file **exists**:
-    ///   1. Reads its contents as an encrypted string
-    ///   2. Attempts decryption using the AES key from `DEFAULT_KEY_PATH`
-    ///   3. Attempts deserialization of the decrypted JSON into a [`Database`]
+    /// The keys of `options.fallback_key_sources` are resolved against the existing header too, and
+    /// attached to the returned key for decryption only. While fallback keys are given, the primary
+    /// key may be new (e.g. a key file that does not exist yet): the database is then decrypted with
+    /// a fallback key and re-encrypted with the primary key on the next save.
     ///
-    /// - If the file **does not exist**:
-    ///   - Returns a new `Database` instance with no collections
+    /// # Parameters
     ///
-    /// # Panics
+    /// - `db_path`: The path of the database.
+    /// - `options`: The [`NosqliteOptions`] describing where the key comes from.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
     ///
-    /// This method **may panic** only in unrecoverable conditions such as:
-    /// - Inability to open the database file (e.g. permissions issues not caught earlier)
-    /// - Internal `expect()`/`unwrap()` usage in key/decryption layers
+    /// # Returns
+    ///
+    /// - `Ok(EncryptionKey)` with the key and the header to save along with the data.
+    /// - `Err(NosqliteError::InvalidKey)` if the key cannot be obtained, or if the key source does not
+    ///   match how the database is protected (passphrase or not).
     ///
     /// # Example
     ///
     /// ```rust
     /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
-    /// use nosqlite_rust::engine::models::File;
+    /// use nosqlite_rust::engine::models::{Database, File, Kdf, KeySource, NosqliteOptions};
     ///
-    /// let mut handler = NosqliteErrorHandler::new("temp/data42.nosqlite".to_string());
-    /// let db = File::load_or_create("temp/data42.nosqlite", &mut handler)?;
-    /// println!("{}", db);
+    /// let options = NosqliteOptions::default()
+    ///     .with_key_source(KeySource::Passphrase("s3cret".to_string()))
+    ///     .with_kdf(Kdf::Pbkdf2 { iterations: 1_000 });
+    /// let mut handler = NosqliteErrorHandler::new("temp/data56.nosqlite".to_string());
+    ///
+    /// let key = File::resolve_key("temp/data56.nosqlite", &options, &mut handler)?;
+    /// File::save_with_key("temp/data56.nosqlite", &Database::default(), &key, &mut handler)?;
+    ///
+    /// let again = File::resolve_key("temp/data56.nosqlite", &options, &mut handler)?;
+    /// assert!(again == key);
     /// Ok::<(), NosqliteError>(())
     /// ```
+    pub fn resolve_key(
+        db_path: &str,
+        options: &NosqliteOptions,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<EncryptionKey, NosqliteError> {
+        let header = match FileBackend::new(db_path).load(handler)? {
+            Some(content) => Some(Self::parse_header(&content, handler)?),
+            None => None,
+        };
+        Self::resolve_key_with_header(db_path, header.as_ref(), options, handler)
+    }
+
+    /// 🦀
+    /// Obtains the key of a database as [`File::resolve_key`] does, from a header already read.
     ///
-    /// # Security Notes
+    /// # Parameters
     ///
-    /// - Encryption is handled via `File::decrypt`
-    /// - Keys are managed via `File::load_or_generate_key` and stored at `DEFAULT_KEY_PATH`
-    /// - Ensure secure handling of key and file paths in production deployments
+    /// - `db_path`: The path or name of the database, used to locate a sidecar key file.
+    /// - `header`: The [`FileHeader`] of the existing database, or `None` for a new database.
+    /// - `options`: The [`NosqliteOptions`] holding the [`KeySource`] and [`Kdf`] to use.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
     ///
-    /// # See Also
+    /// # Returns
     ///
-    /// - [`Database::new`] — creates a new empty database
-    /// - [`NosqliteErrorHandler`] — handles structured error logging
-    /// - [`NosqliteError`] — error type used throughout the system
-    pub fn load_or_create(
+    /// - `Ok(EncryptionKey)` with the key and the [`FileHeader`] to store with the database.
+    /// - `Err(NosqliteError::InvalidKey)` if the key cannot be obtained or is malformed.
+    pub fn resolve_key_with_header(
         db_path: &str,
+        header: Option<&FileHeader>,
+        options: &NosqliteOptions,
         handler: &mut NosqliteErrorHandler,
-    ) -> Result<Database, NosqliteError> {
-        let key = Self::load_or_generate_key(DEFAULT_KEY_PATH, handler)?;
-
-        if Path::new(db_path).exists() {
-            let encrypted = handler.try_or_log(fs::read_to_string(db_path), |e| {
-                NosqliteError::IoError(e.to_string())
-            })?;
-            let decrypted_result = Self::decrypt(&encrypted, &key, handler);
-            let decrypted = handler.try_or_log(decrypted_result, |e| {
-                NosqliteError::EncryptionError(e.to_string())
-            })?;
+    ) -> Result<EncryptionKey, NosqliteError> {
+        let allow_new =
+            header.is_none() || (!options.fallback_key_sources.is_empty() && !options.read_only);
 
-            let db: Database = handler
-                .try_or_log(serde_json::from_str(&decrypted), |e| {
-                    NosqliteError::DeserializationError(e.to_string())
-                })
-                .map_err(|_| {
-                    let err = NosqliteError::InvalidDatabaseFormat(
-                        "Failed to deserialize database".to_string(),
-                    );
-                    handler.log_error(err.clone());
-                    err
-                })?;
-            Ok(db)
-        } else {
-            Ok(Database::new(db_path))
+        let mut fallback_keys = Vec::new();
+        for source in &options.fallback_key_sources {
+            let fallback =
+                Self::key_from_source(db_path, source, header, false, options.kdf, handler)?;
+            fallback_keys.push(fallback.key);
         }
+
+        let mut key = Self::key_from_source(
+            db_path,
+            &options.key_source,
+            header,
+            allow_new,
+            options.kdf,
+            handler,
+        )?;
+        key.fallback_keys = fallback_keys;
+        Ok(key
+            .with_encoding(options.encoding)
+            .with_compression(options.compression))
     }
 
     /// 🦀
-    /// Saves the [`Database`] to disk in encrypted form.
+    /// Creates the key a database should be re-encrypted with, as if the database were new.
     ///
-    /// This method serializes the entire database to pretty-printed JSON, encrypts it
-    /// using AES-256-GCM, and writes the result to the provided path. It ensures that all
-    /// saved data remains secure and tamper-resistant.
+    /// A passphrase gets a fresh salt, and a missing key file is generated. An existing key file is used as is.
     ///
     /// # Parameters
     ///
-    /// - `db_path`: The file path where the encrypted database should be written.
-    /// - `db`: The [`Database`] instance to be serialized and persisted.
-    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging of any I/O,
-    ///   serialization, or encryption errors.
+    /// - `db_path`: The path of the database, used to locate a sidecar key file.
+    /// - `source`: Where the new key comes from.
+    /// - `kdf`: The [`Kdf`] used if `source` is a passphrase.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if the database was successfully saved
-    /// - `Err(NosqliteError)` if any step in the pipeline fails
-    ///
-    /// # Pipeline Steps
+    /// - `Ok(EncryptionKey)` with the new key and its header.
+    /// - `Err(NosqliteError::InvalidKey)` if the key cannot be obtained.
+    pub fn new_key(
+        db_path: &str,
+        source: &KeySource,
+        kdf: Kdf,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<EncryptionKey, NosqliteError> {
+        Self::key_from_source(db_path, source, None, true, kdf, handler)
+    }
+
+    /// 🦀
+    /// Derives a new key from a passphrase, with a fresh random salt.
     ///
-    /// 1. **Key management**: Loads or generates an AES encryption key via `load_or_generate_key`
-    /// 2. **Serialization**: Converts the `Database` instance to pretty-printed JSON
-    /// 3. **Encryption**: Encrypts the JSON payload with AES-256-GCM
-    /// 4. **File write**: Saves the encrypted data to `db_path`
+    /// # Parameters
     ///
-    /// # Panics
+    /// - `passphrase`: The passphrase. It must not be empty.
+    /// - `kdf`: The [`Kdf`] and cost parameters to use.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
     ///
-    /// While this method uses structured error logging (`try_or_log`) throughout, it may still
-    /// panic in rare cases (e.g. internal `expect()` calls in utility methods).
+    /// # Returns
     ///
-    /// # Example
+    /// - `Ok(EncryptionKey)` whose header records the salt and KDF parameters.
+    /// - `Err(NosqliteError::InvalidKey)` if the passphrase is empty or the parameters are rejected.
+    pub fn derive_key(
+        passphrase: &str,
+        kdf: Kdf,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<EncryptionKey, NosqliteError> {
+        if passphrase.is_empty() {
+            let error = NosqliteError::InvalidKey("the passphrase must not be empty".to_string());
+            handler.log_error(error.clone());
+            return Err(error);
+        }
+
+        use rand::RngCore;
+        let mut salt = [0u8; SALT_LENGTH];
+        rand::rng().fill_bytes(&mut salt);
+        let key = handler.try_or_log(kdf.derive(passphrase, &salt), NosqliteError::InvalidKey)?;
+
+        Ok(EncryptionKey::new(
+            key,
+            Some(KdfHeader {
+                kdf,
+                salt: hex::encode(salt),
+            }),
+        ))
+    }
+
+    /// 🦀
+    /// Obtains a key from a [`KeySource`], given the header of the existing database (if any).
     ///
-    /// ```rust
-    /// use nosqlite_rust::engine::models::{Database, File};
-    /// use nosqlite_rust::engine::error::NosqliteErrorHandler;
+    /// When `allow_new` is false, the source must match how the existing database is protected. When
+    /// it is true, a passphrase that does not match the stored key ID gets a fresh salt, and a
+    /// missing key file is generated.
+    fn key_from_source(
+        db_path: &str,
+        source: &KeySource,
+        header: Option<&FileHeader>,
+        allow_new: bool,
+        kdf: Kdf,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<EncryptionKey, NosqliteError> {
+        let stored_kdf = header.and_then(|header| header.kdf.as_ref());
+        let stored_key_id = header.and_then(|header| header.key_id.as_ref());
+        let stored_plaintext = header.is_some_and(|header| header.plaintext);
+
+        if !allow_new && stored_plaintext != (*source == KeySource::Unencrypted) {
+            let error = NosqliteError::InvalidKey(if stored_plaintext {
+                format!("database '{}' is not encrypted", db_path)
+            } else {
+                format!("database '{}' is encrypted and needs a key", db_path)
+            });
+            handler.log_error(error.clone());
+            return Err(error);
+        }
+
+        match source {
+            KeySource::Unencrypted => Ok(EncryptionKey::unencrypted()),
+            KeySource::Passphrase(passphrase) => {
+                if let Some(stored) = stored_kdf {
+                    let salt = handler.try_or_log(hex::decode(&stored.salt), |e| {
+                        NosqliteError::HexDecodeError(e.to_string())
+                    })?;
+                    let key = handler.try_or_log(
+                        stored.kdf.derive(passphrase, &salt),
+                        NosqliteError::InvalidKey,
+                    )?;
+                    let existing = EncryptionKey::new(key, Some(stored.clone()));
+                    if !allow_new || stored_key_id.is_none_or(|id| *id == existing.key_id()) {
+                        return Ok(existing);
+                    }
+                } else if !allow_new {
+                    let error = NosqliteError::InvalidKey(format!(
+                        "database '{}' is not protected by a passphrase",
+                        db_path
+                    ));
+                    handler.log_error(error.clone());
+                    return Err(error);
+                }
+                Self::derive_key(passphrase, kdf, handler)
+            }
+            _ => {
+                if !allow_new && stored_kdf.is_some() {
+                    let error = NosqliteError::InvalidKey(format!(
+                        "database '{}' is protected by a passphrase",
+                        db_path
+                    ));
+                    handler.log_error(error.clone());
+                    return Err(error);
+                }
+                let key = Self::load_key(db_path, source, allow_new, handler)?;
+                Ok(EncryptionKey::new(key, None))
+            }
+        }
+    }
+
+    /// 🦀
+    /// Decrypts a payload with the key (or fallback key) it was encrypted with.
     ///
-    /// let db = Database::default();
-    /// let mut handler = NosqliteErrorHandler::new("temp/data43.nosqlite".to_string());
+    /// If the header records a key ID, only the matching key is tried, so that a wrong key is reported
+    /// as a [`NosqliteError::KeyMismatch`] naming the expected key. Files without a key ID are tried
+    /// with the primary key first, then with every fallback key.
+    fn decrypt_with_any(
+        db_path: &str,
+        encrypted: &[u8],
+        header: &FileHeader,
+        key: &EncryptionKey,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
+        let candidates: Vec<&[u8; 32]> = std::iter::once(&key.key)
+            .chain(key.fallback_keys.iter())
+            .collect();
+
+        if let Some(key_id) = &header.key_id {
+            let Some(matching) = candidates.iter().find(|k| key_id_of(k) == *key_id) else {
+                let error = NosqliteError::KeyMismatch(format!(
+                    "database '{}' was encrypted with key '{}', which is not among the provided keys",
+                    db_path, key_id
+                ));
+                handler.log_error(error.clone());
+                return Err(error);
+            };
+            return Self::decrypt_bytes(encrypted, matching, handler);
+        }
+
+        let mut result = Self::decrypt_bytes(encrypted, &key.key, handler);
+        for fallback in &key.fallback_keys {
+            if !matches!(result, Err(NosqliteError::KeyMismatch(_))) {
+                break;
+            }
+            result = Self::decrypt_bytes(encrypted, fallback, handler);
+        }
+        result
+    }
+
+    /// 🦀
+    /// Reads the plaintext [`FileHeader`] from the content of a database file.
     ///
-    /// File::save("temp/data43.nosqlite", &db, &mut handler).expect("Failed to save database");
-    /// ```
+    /// # Parameters
     ///
-    /// # Security Notes
+    /// - `content`: The bytes of the file, as returned by [`StorageBackend::load`].
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
     ///
-    /// - Encryption is performed using AES-256-GCM with keys stored at `DEFAULT_KEY_PATH`
-    /// - The resulting file at `db_path` is **not human-readable**
-    /// - For plaintext backups or inspection, implement a `save_as_json()` variant
+    /// # Returns
     ///
-    /// # See Also
+    /// - `Ok(FileHeader)`, a default header for files written without one.
+    /// - `Err(NosqliteError::InvalidDatabaseFormat)` if the header is malformed.
+    pub fn parse_header(
+        content: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<FileHeader, NosqliteError> {
+        let (header, _) = Self::split_header(content, handler)?;
+        Ok(header)
+    }
+
+    /// 🦀
+    /// Reads the content of a database file as text.
+    fn content_as_str<'a>(
+        content: &'a [u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<&'a str, NosqliteError> {
+        handler.try_or_log(std::str::from_utf8(content), |e| {
+            NosqliteError::InvalidDatabaseFormat(format!("database file is not valid UTF-8: {}", e))
+        })
+    }
+
+    /// 🦀
+    /// Splits the content of a database file into its [`FileHeader`] and encrypted payload.
     ///
-    /// - [`File::load_or_create`] — for loading the encrypted database back
-    /// - [`NosqliteError`] — unified error type
-    /// - [`NosqliteErrorHandler`] — used for logging any failures in the pipeline
-    pub fn save(
-        db_path: &str,
-        db: &Database,
+    /// Files written without a header (the original format) get a default header. The payload is
+    /// left as bytes, since a binary [`Encoding`] is not valid text.
+    fn split_header<'a>(
+        content: &'a [u8],
         handler: &mut NosqliteErrorHandler,
-    ) -> Result<(), NosqliteError> {
-        let key = Self::load_or_generate_key(DEFAULT_KEY_PATH, handler)?;
-        let json = handler.try_or_log(serde_json::to_string_pretty(db), |e| {
-            NosqliteError::SerializationError(e.to_string())
-        })?;
-        let encrypted_result = Self::encrypt(&json, &key, handler);
-        let encrypted = handler.try_or_log(encrypted_result, |e| {
-            NosqliteError::EncryptionError(e.to_string())
-        })?;
-        handler.try_or_log(fs::write(db_path, &encrypted), |e| {
-            NosqliteError::IoError(e.to_string())
+    ) -> Result<(FileHeader, &'a [u8]), NosqliteError> {
+        let Some(rest) = content.strip_prefix(HEADER_PREFIX.as_bytes()) else {
+            return Ok((FileHeader::default(), content));
+        };
+        let (line, payload) = match rest.iter().position(|byte| *byte == b'\n') {
+            Some(end) => (&rest[..end], &rest[end + 1..]),
+            None => (rest, &rest[rest.len()..]),
+        };
+        let header = handler.try_or_log(serde_json::from_slice(line), |e| {
+            NosqliteError::InvalidDatabaseFormat(format!("Invalid file header: {}", e))
         })?;
-        Ok(())
+        Ok((header, payload))
     }
 
     /// 🦀
@@ -218,15 +1330,27 @@ impl File {
         key: &[u8; 32],
         handler: &mut NosqliteErrorHandler,
     ) -> Result<String, NosqliteError> {
+        let encrypted = Self::encrypt_bytes(data.as_bytes(), key, handler)?;
+        Ok(general_purpose::STANDARD.encode(&encrypted))
+    }
+
+    /// 🦀
+    /// Encrypts bytes using AES-256-GCM into `[nonce || ciphertext]`, as [`File::encrypt`] does
+    /// before base64-encoding.
+    fn encrypt_bytes(
+        data: &[u8],
+        key: &[u8; 32],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
         let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
         let nonce = Aes256Gcm::generate_nonce(&mut OsRng); // 12 bytes
-        let ciphertext = handler.try_or_log(cipher.encrypt(&nonce, data.as_bytes()), |e| {
+        let ciphertext = handler.try_or_log(cipher.encrypt(&nonce, data), |e| {
             NosqliteError::EncryptionError(e.to_string())
         })?;
 
         let mut result = nonce.to_vec();
         result.extend(ciphertext);
-        Ok(general_purpose::STANDARD.encode(&result))
+        Ok(result)
     }
 
     /// 🦀
@@ -276,17 +1400,37 @@ impl File {
         let decoded = handler.try_or_log(general_purpose::STANDARD.decode(data), |e| {
             NosqliteError::Base64DecodeError(e.to_string())
         })?;
+        let plaintext = Self::decrypt_bytes(&decoded, key, handler)?;
+        let decrypt = handler.try_or_log(String::from_utf8(plaintext), |e| {
+            NosqliteError::DeserializationError(e.to_string())
+        })?;
+        Ok(decrypt)
+    }
+
+    /// 🦀
+    /// Decrypts `[nonce || ciphertext]` bytes produced by [`File::encrypt_bytes`].
+    fn decrypt_bytes(
+        decoded: &[u8],
+        key: &[u8; 32],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<u8>, NosqliteError> {
+        if decoded.len() < 12 {
+            let error = NosqliteError::InvalidDatabaseFormat(
+                "Encrypted payload is shorter than its nonce".to_string(),
+            );
+            handler.log_error(error.clone());
+            return Err(error);
+        }
         let (nonce_bytes, ciphertext) = decoded.split_at(12);
         let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
         let nonce = Nonce::from_slice(nonce_bytes);
 
-        let plaintext = handler.try_or_log(cipher.decrypt(nonce, ciphertext), |e| {
-            NosqliteError::EncryptionError(e.to_string())
-        })?;
-        let decrypt = handler.try_or_log(String::from_utf8(plaintext.clone()), |e| {
-            NosqliteError::DeserializationError(e.to_string())
-        })?;
-        Ok(decrypt)
+        handler.try_or_log(cipher.decrypt(nonce, ciphertext), |_| {
+            NosqliteError::KeyMismatch(
+                "the database cannot be decrypted with this key (wrong key or corrupted file)"
+                    .to_string(),
+            )
+        })
     }
 
     /// 🦀
@@ -344,12 +1488,7 @@ impl File {
             let content = handler.try_or_log(fs::read_to_string(path), |e| {
                 NosqliteError::IoError(e.to_string())
             })?;
-            let bytes = handler.try_or_log(hex::decode(content.trim()), |e| {
-                NosqliteError::HexDecodeError(e.to_string())
-            })?;
-            let mut key = [0u8; 32];
-            key.copy_from_slice(&bytes[..32]);
-            Ok(key)
+            Self::decode_key(&content, &format!("key file '{}'", path), handler)
         } else {
             use rand::RngCore;
             let mut raw = [0u8; 32];
@@ -361,4 +1500,211 @@ impl File {
             Ok(raw)
         }
     }
+
+    /// 🦀
+    /// Obtains the AES key of a database from its [`KeySource`].
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The path of the database, used to locate a sidecar key file.
+    /// - `source`: Where the key comes from.
+    /// - `create`: Whether a missing key file may be generated (only for new databases and saves).
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok([u8; 32])` with the key.
+    /// - `Err(NosqliteError::InvalidKey)` if the key file or environment variable is missing,
+    ///   or if it does not hold a 256-bit hex-encoded key.
+    fn load_key(
+        db_path: &str,
+        source: &KeySource,
+        create: bool,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<[u8; 32], NosqliteError> {
+        match source {
+            KeySource::Bytes(key) => Ok(*key),
+            KeySource::Env(name) => {
+                let content = handler.try_or_log(std::env::var(name), |_| {
+                    NosqliteError::InvalidKey(format!("environment variable '{}' is not set", name))
+                })?;
+                Self::decode_key(
+                    &content,
+                    &format!("environment variable '{}'", name),
+                    handler,
+                )
+            }
+            KeySource::Passphrase(_) | KeySource::Unencrypted => {
+                let error = NosqliteError::InvalidKey(format!(
+                    "{:?} has no key of its own, see File::resolve_key",
+                    source
+                ));
+                handler.log_error(error.clone());
+                Err(error)
+            }
+            KeySource::File(_) | KeySource::Sidecar => {
+                let path = source.key_path(db_path).unwrap_or_default();
+                if !create && !Path::new(&path).exists() {
+                    let error = NosqliteError::InvalidKey(format!(
+                        "key file '{}' not found for existing database '{}'",
+                        path, db_path
+                    ));
+                    handler.log_error(error.clone());
+                    return Err(error);
+                }
+                Self::load_or_generate_key(&path, handler)
+            }
+        }
+    }
+
+    /// 🦀
+    /// Decodes a hex-encoded 256-bit key, naming `origin` in error messages.
+    fn decode_key(
+        content: &str,
+        origin: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<[u8; 32], NosqliteError> {
+        let bytes = handler.try_or_log(hex::decode(content.trim()), |e| {
+            NosqliteError::HexDecodeError(e.to_string())
+        })?;
+        handler.try_or_log(<[u8; 32]>::try_from(bytes.as_slice()), |_| {
+            NosqliteError::InvalidKey(format!(
+                "{} holds {} bytes instead of 32",
+                origin,
+                bytes.len()
+            ))
+        })
+    }
+}
+
+impl EncryptionKey {
+    /// 🦀
+    /// Wraps a key, recording its key ID and optional KDF parameters in the header to write.
+    pub fn new(key: [u8; 32], kdf: Option<KdfHeader>) -> Self {
+        EncryptionKey {
+            key,
+            header: FileHeader {
+                version: FORMAT_VERSION,
+                kdf,
+                key_id: Some(key_id_of(&key)),
+                plaintext: false,
+                encoding: Encoding::Json,
+                compression: Compression::None,
+                layout: Layout::Single,
+                checksum: None,
+            },
+            fallback_keys: Vec::new(),
+        }
+    }
+
+    /// 🦀
+    /// Returns the pseudo-key of an unencrypted database: its header marks the payload as plain JSON.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::EncryptionKey;
+    ///
+    /// let key = EncryptionKey::unencrypted();
+    /// assert!(key.header.plaintext);
+    /// assert_eq!(key.header.key_id, None);
+    /// ```
+    pub fn unencrypted() -> Self {
+        EncryptionKey {
+            key: [0u8; 32],
+            header: FileHeader {
+                version: FORMAT_VERSION,
+                plaintext: true,
+                ..FileHeader::default()
+            },
+            fallback_keys: Vec::new(),
+        }
+    }
+
+    /// 🦀
+    /// Returns the public identifier of the key: the first 8 bytes of its SHA-256 digest, hex-encoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::EncryptionKey;
+    ///
+    /// let key = EncryptionKey::new([0u8; 32], None);
+    /// assert_eq!(key.key_id().len(), 16);
+    /// assert_eq!(key.header.key_id, Some(key.key_id()));
+    /// ```
+    pub fn key_id(&self) -> String {
+        key_id_of(&self.key)
+    }
+
+    /// 🦀
+    /// Sets the [`Encoding`] recorded in the header, which the database is then saved with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::{EncryptionKey, Encoding};
+    ///
+    /// let key = EncryptionKey::unencrypted().with_encoding(Encoding::MessagePack);
+    /// assert_eq!(key.header.encoding, Encoding::MessagePack);
+    /// ```
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.header.encoding = encoding;
+        self
+    }
+
+    /// 🦀
+    /// Sets the [`Compression`] recorded in the header, which the database is then saved with.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.header.compression = compression;
+        self
+    }
+}
+
+impl PayloadStats {
+    /// 🦀
+    /// Returns how many times smaller compression made the payload, `1.0` without compression.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::{Compression, Encoding, PayloadStats};
+    ///
+    /// let stats = PayloadStats {
+    ///     encoding: Encoding::Json,
+    ///     compression: Compression::Zstd,
+    ///     file_bytes: 300,
+    ///     serialized_bytes: 1_000,
+    ///     compressed_bytes: 250,
+    /// };
+    /// assert_eq!(stats.compression_ratio(), 4.0);
+    /// ```
+    pub fn compression_ratio(&self) -> f64 {
+        match self.compressed_bytes {
+            0 => 1.0,
+            compressed => self.serialized_bytes as f64 / compressed as f64,
+        }
+    }
+}
+
+impl Debug for EncryptionKey {
+    /// 🦀
+    /// Formats the [`EncryptionKey`] without revealing the key bytes.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionKey")
+            .field("key", &"<redacted>")
+            .field("header", &self.header)
+            .field("fallback_keys", &self.fallback_keys.len())
+            .finish()
+    }
+}
+
+/// Computes the key ID of a raw key, see [`EncryptionKey::key_id`].
+fn key_id_of(key: &[u8; 32]) -> String {
+    hex::encode(&Sha256::digest(key)[..8])
+}
+
+/// Returns the checksum stored in the [`FileHeader`] of a payload: its SHA-256 digest, hex-encoded.
+fn checksum_of(payload: &[u8]) -> String {
+    hex::encode(Sha256::digest(payload))
 }
@@ -1,4 +1,4 @@
 pub mod implem;
 pub mod model;
 
-pub use model::File;
+pub use model::{EncryptionKey, File, FileHeader, KdfHeader, PayloadStats};
@@ -1,3 +1,6 @@
+use crate::engine::models::options::model::{Compression, Encoding, Kdf, Layout};
+use serde::{Deserialize, Serialize};
+
 /// 🦀
 /// Provides an interface for file-level operations in the NoSQLite engine.
 ///
@@ -9,3 +12,100 @@
 /// This abstraction allows the engine to operate like a lightweight embedded database,
 /// similar in spirit to SQLite, with built-in encryption support.
 pub struct File;
+
+/// 🦀
+/// The plaintext header written on the first line of a database file, before the (encrypted) payload.
+///
+/// The `NOSQLITE ` marker doubles as the magic bytes of the format. Every save writes the header;
+/// files written before it existed are read with default values. On disk it looks like:
+///
+/// ```text
+/// NOSQLITE {"version":4,"kdf":{"algorithm":"argon2id","memory_kib":19456,"iterations":2,"parallelism":1,"salt":"..."},"key_id":"..."}
+/// ```
+///
+/// # Fields
+///
+/// - `version`: The on-disk format version the file was written with. Files written before
+///   versioning (including those without any header) are version `0`.
+/// - `kdf`: The key derivation parameters of a passphrase-protected database.
+/// - `key_id`: The [`EncryptionKey::key_id`] of the key the payload is encrypted with. It tells a
+///   wrong key apart from a corrupted file, and selects the right key during a key rotation.
+/// - `plaintext`: Whether the payload is stored as is instead of being encrypted.
+/// - `encoding`: The [`Encoding`] of the payload. Files without it are JSON.
+/// - `compression`: The [`Compression`] applied to the payload before encryption. Files without it
+///   are not compressed.
+/// - `layout`: The [`Layout`] of the database. With [`Layout::Segmented`], the payload is a
+///   [`crate::engine::models::SegmentCatalog`] instead of the whole database.
+/// - `checksum`: The SHA-256 digest of the stored payload (after compression and encryption),
+///   hex-encoded. It tells a damaged file from a wrong key before anything is decrypted. Files
+///   without it are not checked.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FileHeader {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<KdfHeader>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub plaintext: bool,
+    #[serde(default, skip_serializing_if = "Encoding::is_json")]
+    pub encoding: Encoding,
+    #[serde(default, skip_serializing_if = "Compression::is_none")]
+    pub compression: Compression,
+    #[serde(default, skip_serializing_if = "Layout::is_single")]
+    pub layout: Layout,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+/// 🦀
+/// The sizes of the payload of a database file, as reported by [`File::payload_stats`].
+///
+/// # Fields
+///
+/// - `encoding`: The [`Encoding`] of the payload.
+/// - `compression`: The [`Compression`] of the payload.
+/// - `file_bytes`: The size of the whole file, header included.
+/// - `serialized_bytes`: The size of the serialized payload, before compression.
+/// - `compressed_bytes`: The size of the payload after compression, before encryption.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadStats {
+    pub encoding: Encoding,
+    pub compression: Compression,
+    pub file_bytes: u64,
+    pub serialized_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+/// 🦀
+/// The parameters needed to derive the key of a passphrase-protected database again.
+///
+/// # Fields
+///
+/// - `kdf`: The [`Kdf`] algorithm and cost parameters.
+/// - `salt`: The hex-encoded random salt.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KdfHeader {
+    #[serde(flatten)]
+    pub kdf: Kdf,
+    pub salt: String,
+}
+
+/// 🦀
+/// A resolved AES-256-GCM key, together with the file header to write alongside data encrypted with it.
+///
+/// It is obtained once with [`File::resolve_key`] so that saving does not need to read a key
+/// file or run a key derivation function again.
+///
+/// # Fields
+///
+/// - `key`: The 256-bit AES key.
+/// - `header`: The [`FileHeader`] describing how the key was obtained.
+/// - `fallback_keys`: Older keys that may still decrypt the database, but are never used to encrypt it.
+#[derive(Clone, PartialEq)]
+pub struct EncryptionKey {
+    pub key: [u8; 32],
+    pub header: FileHeader,
+    pub fallback_keys: Vec<[u8; 32]>,
+}
@@ -0,0 +1,304 @@
+use super::model::{GeoPoint, GeoQuery, GeoShape};
+use serde_json::Value;
+
+/// Mean Earth radius, in kilometers.
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+/// Length of one degree of latitude, in kilometers.
+const KM_PER_DEGREE: f64 = 111.32;
+
+impl GeoPoint {
+    /// 🦀
+    /// Reads a [`GeoPoint`] from a JSON value.
+    ///
+    /// Accepts `{ "lat": .., "lng": .. }` and GeoJSON `{ "type": "Point", "coordinates": [lng, lat] }`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(GeoPoint)` if the value is a point with coordinates in range.
+    /// - `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::GeoPoint;
+    ///
+    /// let a = GeoPoint::from_value(&json!({ "lat": 48.85, "lng": 2.35 }));
+    /// let b = GeoPoint::from_value(&json!({ "type": "Point", "coordinates": [2.35, 48.85] }));
+    /// assert_eq!(a, b);
+    /// assert!(GeoPoint::from_value(&json!({ "lat": 123.0, "lng": 0.0 })).is_none());
+    /// ```
+    pub fn from_value(value: &Value) -> Option<GeoPoint> {
+        let (lat, lng) = match (value.get("lat"), value.get("lng")) {
+            (Some(lat), Some(lng)) => (lat.as_f64()?, lng.as_f64()?),
+            _ => {
+                if value.get("type")?.as_str()? != "Point" {
+                    return None;
+                }
+                match value.get("coordinates")?.as_array()?.as_slice() {
+                    [lng, lat] => (lat.as_f64()?, lng.as_f64()?),
+                    _ => return None,
+                }
+            }
+        };
+
+        if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng) {
+            Some(GeoPoint { lat, lng })
+        } else {
+            None
+        }
+    }
+
+    /// 🦀
+    /// Computes the great-circle distance to another point with the haversine formula.
+    ///
+    /// # Returns
+    ///
+    /// - The distance in kilometers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::GeoPoint;
+    ///
+    /// let paris = GeoPoint { lat: 48.8566, lng: 2.3522 };
+    /// let london = GeoPoint { lat: 51.5074, lng: -0.1278 };
+    /// let km = paris.distance_km(&london);
+    /// assert!((km - 343.5).abs() < 1.0);
+    /// ```
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let d_lat = (other.lat - self.lat).to_radians();
+        let d_lng = (other.lng - self.lng).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2)
+            + self.lat.to_radians().cos()
+                * other.lat.to_radians().cos()
+                * (d_lng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+
+    /// Returns the south-west and north-east corners of the box enclosing a circle around this point.
+    fn bounding_box_km(&self, radius_km: f64) -> (GeoPoint, GeoPoint) {
+        let d_lat = radius_km / KM_PER_DEGREE;
+        let cos_lat = self.lat.to_radians().cos();
+        let d_lng = if cos_lat > f64::EPSILON {
+            radius_km / (KM_PER_DEGREE * cos_lat)
+        } else {
+            360.0
+        };
+
+        let (min_lng, max_lng) = if self.lng - d_lng < -180.0 || self.lng + d_lng > 180.0 {
+            (-180.0, 180.0)
+        } else {
+            (self.lng - d_lng, self.lng + d_lng)
+        };
+        (
+            GeoPoint {
+                lat: (self.lat - d_lat).max(-90.0),
+                lng: min_lng,
+            },
+            GeoPoint {
+                lat: (self.lat + d_lat).min(90.0),
+                lng: max_lng,
+            },
+        )
+    }
+}
+
+impl GeoShape {
+    /// 🦀
+    /// Parses the argument of a `$geoWithin` operator.
+    ///
+    /// Supported forms:
+    /// - `{ "$circle": { "center": <point>, "radius_km": 5 } }`
+    /// - `{ "$box": [<south-west point>, <north-east point>] }`
+    /// - `{ "$polygon": [<point>, <point>, <point>, ...] }`
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(GeoShape)` if the shape is well formed.
+    /// - `Err(String)` describing the problem otherwise.
+    pub fn from_value(value: &Value) -> Result<GeoShape, String> {
+        if let Some(circle) = value.get("$circle") {
+            let center = circle
+                .get("center")
+                .and_then(GeoPoint::from_value)
+                .ok_or("$circle expects a valid 'center' point")?;
+            let radius_km = circle
+                .get("radius_km")
+                .and_then(Value::as_f64)
+                .filter(|radius| *radius >= 0.0)
+                .ok_or("$circle expects a non-negative 'radius_km'")?;
+            return Ok(GeoShape::Circle { center, radius_km });
+        }
+        if let Some(corners) = value.get("$box") {
+            let points = parse_points(corners).ok_or("$box expects two valid points")?;
+            return match points.as_slice() {
+                [min, max] => Ok(GeoShape::Box {
+                    min: *min,
+                    max: *max,
+                }),
+                _ => Err("$box expects exactly two points".to_string()),
+            };
+        }
+        if let Some(vertices) = value.get("$polygon") {
+            let points =
+                parse_points(vertices).ok_or("$polygon expects an array of valid points")?;
+            if points.len() < 3 {
+                return Err("$polygon expects at least three points".to_string());
+            }
+            return Ok(GeoShape::Polygon(points));
+        }
+        Err("$geoWithin expects one of $circle, $box or $polygon".to_string())
+    }
+
+    /// 🦀
+    /// Checks whether a point lies inside the shape.
+    ///
+    /// Polygons are evaluated with the even-odd rule on the latitude/longitude plane,
+    /// which is accurate for areas that do not span the antimeridian or a pole.
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        match self {
+            GeoShape::Circle { center, radius_km } => center.distance_km(point) <= *radius_km,
+            GeoShape::Box { min, max } => {
+                (min.lat..=max.lat).contains(&point.lat) && (min.lng..=max.lng).contains(&point.lng)
+            }
+            GeoShape::Polygon(vertices) => {
+                let mut inside = false;
+                let mut j = vertices.len() - 1;
+                for i in 0..vertices.len() {
+                    let (a, b) = (&vertices[i], &vertices[j]);
+                    if (a.lat > point.lat) != (b.lat > point.lat)
+                        && point.lng
+                            < (b.lng - a.lng) * (point.lat - a.lat) / (b.lat - a.lat) + a.lng
+                    {
+                        inside = !inside;
+                    }
+                    j = i;
+                }
+                inside
+            }
+        }
+    }
+
+    /// 🦀
+    /// Returns the south-west and north-east corners of a box enclosing the shape.
+    pub fn bounding_box(&self) -> (GeoPoint, GeoPoint) {
+        match self {
+            GeoShape::Circle { center, radius_km } => center.bounding_box_km(*radius_km),
+            GeoShape::Box { min, max } => (*min, *max),
+            GeoShape::Polygon(vertices) => {
+                vertices
+                    .iter()
+                    .fold((vertices[0], vertices[0]), |(min, max), vertex| {
+                        (
+                            GeoPoint {
+                                lat: min.lat.min(vertex.lat),
+                                lng: min.lng.min(vertex.lng),
+                            },
+                            GeoPoint {
+                                lat: max.lat.max(vertex.lat),
+                                lng: max.lng.max(vertex.lng),
+                            },
+                        )
+                    })
+            }
+        }
+    }
+}
+
+impl GeoQuery {
+    /// 🦀
+    /// Parses the filter value of a field into a geospatial query, if it is one.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: The value associated with a field in a filter,
+    ///   e.g. `{ "$near": { "point": { "lat": 48.85, "lng": 2.35 }, "limit": 10 } }`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(GeoQuery))` if the value uses `$geoWithin` or `$near`.
+    /// - `Ok(None)` if the value is not a geospatial operator.
+    /// - `Err(String)` if the operator is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::GeoQuery;
+    ///
+    /// let query = GeoQuery::from_value(&json!({
+    ///     "$geoWithin": { "$circle": { "center": { "lat": 48.85, "lng": 2.35 }, "radius_km": 5 } }
+    /// }));
+    /// assert!(matches!(query, Ok(Some(GeoQuery::Within(_)))));
+    /// ```
+    pub fn from_value(value: &Value) -> Result<Option<GeoQuery>, String> {
+        if let Some(shape) = value.get("$geoWithin") {
+            return GeoShape::from_value(shape).map(|shape| Some(GeoQuery::Within(shape)));
+        }
+        if let Some(near) = value.get("$near") {
+            let point = near
+                .get("point")
+                .and_then(GeoPoint::from_value)
+                .ok_or("$near expects a valid 'point'")?;
+            let max_distance_km = match near.get("max_distance_km") {
+                None => None,
+                Some(distance) => Some(
+                    distance
+                        .as_f64()
+                        .filter(|distance| *distance >= 0.0)
+                        .ok_or("$near expects a non-negative 'max_distance_km'")?,
+                ),
+            };
+            let limit = match near.get("limit") {
+                None => None,
+                Some(limit) => Some(
+                    limit
+                        .as_u64()
+                        .filter(|limit| *limit > 0)
+                        .ok_or("$near expects a positive integer 'limit'")?
+                        as usize,
+                ),
+            };
+            return Ok(Some(GeoQuery::Near {
+                point,
+                max_distance_km,
+                limit,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// 🦀
+    /// Checks whether a point satisfies the query.
+    ///
+    /// `$near` only filters by `max_distance_km`; ordering and `limit` are applied by the query engine.
+    pub fn matches(&self, point: &GeoPoint) -> bool {
+        match self {
+            GeoQuery::Within(shape) => shape.contains(point),
+            GeoQuery::Near {
+                point: origin,
+                max_distance_km,
+                ..
+            } => max_distance_km.is_none_or(|max| origin.distance_km(point) <= max),
+        }
+    }
+
+    /// 🦀
+    /// Returns a box enclosing every point that can match, or `None` if the query is unbounded.
+    pub fn bounding_box(&self) -> Option<(GeoPoint, GeoPoint)> {
+        match self {
+            GeoQuery::Within(shape) => Some(shape.bounding_box()),
+            GeoQuery::Near {
+                point,
+                max_distance_km,
+                ..
+            } => max_distance_km.map(|radius| point.bounding_box_km(radius)),
+        }
+    }
+}
+
+/// Parses a JSON array of points.
+fn parse_points(value: &Value) -> Option<Vec<GeoPoint>> {
+    value.as_array()?.iter().map(GeoPoint::from_value).collect()
+}
@@ -0,0 +1,8 @@
+//! # Geo Model
+//!
+//! This module defines geographic points, shapes and queries used by geospatial filters.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{GeoPoint, GeoQuery, GeoShape};
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// 🦀
+/// A point on Earth, in decimal degrees.
+///
+/// In documents, a point is stored either as `{ "lat": 48.85, "lng": 2.35 }` or as a
+/// GeoJSON point `{ "type": "Point", "coordinates": [2.35, 48.85] }` (longitude first).
+///
+/// # Fields
+///
+/// - `lat`: Latitude, between -90 and 90.
+/// - `lng`: Longitude, between -180 and 180.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+/// 🦀
+/// An area used by the `$geoWithin` operator.
+///
+/// # Variants
+///
+/// - `Circle { center, radius_km }`: Every point within `radius_km` kilometers (haversine distance) of `center`.
+/// - `Box { min, max }`: Every point whose latitude and longitude lie between the `min` (south-west)
+///   and `max` (north-east) corners.
+/// - `Polygon(points)`: Every point inside the polygon described by at least three vertices.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum GeoShape {
+    Circle { center: GeoPoint, radius_km: f64 },
+    Box { min: GeoPoint, max: GeoPoint },
+    Polygon(Vec<GeoPoint>),
+}
+
+/// 🦀
+/// A parsed geospatial filter operator.
+///
+/// # Variants
+///
+/// - `Within(shape)`: From `{ "$geoWithin": { "$circle" | "$box" | "$polygon": ... } }`.
+/// - `Near { point, max_distance_km, limit }`: From `{ "$near": { "point": ..., "max_distance_km": ..., "limit": ... } }`.
+///   Results are ordered by increasing distance from `point`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum GeoQuery {
+    Within(GeoShape),
+    Near {
+        point: GeoPoint,
+        max_distance_km: Option<f64>,
+        limit: Option<usize>,
+    },
+}
@@ -0,0 +1,440 @@
+use super::model::{GeoGrid, Index, IndexData, IndexKind, InvertedIndex};
+use crate::engine::models::document::model::Document;
+use crate::engine::models::geo::model::GeoPoint;
+use crate::engine::models::utils::{get_nested_value, tokenize};
+use crate::engine::models::vector::implem::as_vector;
+use crate::engine::models::vector::model::HnswGraph;
+use chrono::DateTime;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+/// BM25 term frequency saturation parameter.
+const BM25_K1: f64 = 1.2;
+/// BM25 document length normalization parameter.
+const BM25_B: f64 = 0.75;
+
+/// Size of a geo index grid cell, in degrees (about 11 km of latitude).
+const GEO_CELL_DEGREES: f64 = 0.1;
+
+/// TTL field name referring to [`Document::created_at`].
+pub const CREATED_AT_FIELD: &str = "$created_at";
+/// TTL field name referring to [`Document::updated_at`].
+pub const UPDATED_AT_FIELD: &str = "$updated_at";
+
+impl Index {
+    /// 🦀
+    /// Creates a new, empty [`Index`] with the given name and definition.
+    ///
+    /// The returned index holds no data yet; it is filled by [`Index::rebuild`] or
+    /// incrementally by [`Index::insert`].
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the index.
+    /// - `kind`: The [`IndexKind`] definition.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::{Index, IndexKind};
+    ///
+    /// let index = Index::new("content", IndexKind::Text { fields: vec!["body".to_string()] });
+    /// assert_eq!(index.kind, IndexKind::Text { fields: vec!["body".to_string()] });
+    /// ```
+    pub fn new(name: &str, kind: IndexKind) -> Self {
+        let data = match kind {
+            IndexKind::Text { .. } => IndexData::Text(InvertedIndex::default()),
+            IndexKind::Ttl { .. } => IndexData::Empty,
+            IndexKind::Geo { .. } => IndexData::Geo(GeoGrid::default()),
+            IndexKind::Vector { metric, .. } => IndexData::Vector(HnswGraph::new(metric)),
+        };
+        Index {
+            name: name.to_string(),
+            kind,
+            data,
+        }
+    }
+
+    /// 🦀
+    /// Discards the in-memory index structure and rebuilds it from the given documents.
+    ///
+    /// This is called after a database is loaded from disk, since index data is not persisted.
+    ///
+    /// # Parameters
+    ///
+    /// - `documents`: All documents of the collection the index belongs to.
+    pub fn rebuild(&mut self, documents: &[Document]) {
+        *self = Index::new(&self.name, self.kind.clone());
+        for document in documents {
+            self.insert(document);
+        }
+    }
+
+    /// 🦀
+    /// Adds a document to the index.
+    ///
+    /// For a text index, every indexed string field is tokenized with [`tokenize`] and
+    /// the resulting terms are added to the inverted index. Fields that are missing or
+    /// not strings are ignored.
+    ///
+    /// For a geo index, the document is placed in the grid cell of its point. Documents
+    /// whose field is missing or not a valid point are ignored.
+    ///
+    /// For a vector index, the document's vector is added to the HNSW graph. Documents
+    /// whose field is missing or not an array of numbers are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `document`: The document to index. It must not already be indexed.
+    pub fn insert(&mut self, document: &Document) {
+        match (&self.kind, &mut self.data) {
+            (IndexKind::Text { fields }, IndexData::Text(inverted)) => {
+                let terms = text_terms(fields, document);
+
+                for term in &terms {
+                    *inverted
+                        .postings
+                        .entry(term.clone())
+                        .or_default()
+                        .entry(document.id.clone())
+                        .or_insert(0) += 1;
+                }
+                inverted
+                    .doc_lengths
+                    .insert(document.id.clone(), terms.len() as u32);
+                inverted.total_length += terms.len() as u64;
+            }
+            (IndexKind::Geo { field }, IndexData::Geo(grid)) => {
+                let Some(point) =
+                    get_nested_value(&document.data, field).and_then(GeoPoint::from_value)
+                else {
+                    return;
+                };
+                let cell = geo_cell(&point);
+                grid.cells
+                    .entry(cell)
+                    .or_default()
+                    .insert(document.id.clone());
+                grid.positions.insert(document.id.clone(), cell);
+            }
+            (IndexKind::Vector { field, .. }, IndexData::Vector(graph)) => {
+                if let Some(vector) = get_nested_value(&document.data, field).and_then(as_vector) {
+                    graph.insert(&document.id, vector);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 🦀
+    /// Removes a document from the index.
+    ///
+    /// For a text index, only the posting lists of the document's own terms are updated, so the
+    /// document must be given as it was when inserted, not with updated content. Removing a
+    /// document that is not indexed is a no-op.
+    ///
+    /// # Parameters
+    ///
+    /// - `document`: The [`Document`] to remove, as it was indexed.
+    pub fn remove(&mut self, document: &Document) {
+        let document_id = document.id.as_str();
+        match (&self.kind, &mut self.data) {
+            (IndexKind::Text { fields }, IndexData::Text(inverted)) => {
+                if let Some(length) = inverted.doc_lengths.remove(document_id) {
+                    inverted.total_length -= length as u64;
+                    for term in text_terms(fields, document) {
+                        if let Some(docs) = inverted.postings.get_mut(&term) {
+                            docs.remove(document_id);
+                            if docs.is_empty() {
+                                inverted.postings.remove(&term);
+                            }
+                        }
+                    }
+                }
+            }
+            (_, IndexData::Geo(grid)) => {
+                if let Some(cell) = grid.positions.remove(document_id) {
+                    if let Some(docs) = grid.cells.get_mut(&cell) {
+                        docs.remove(document_id);
+                        if docs.is_empty() {
+                            grid.cells.remove(&cell);
+                        }
+                    }
+                }
+            }
+            (_, IndexData::Vector(graph)) => graph.remove(document_id),
+            _ => {}
+        }
+    }
+
+    /// 🦀
+    /// Searches a vector index for the approximate `k` nearest documents to `query`.
+    ///
+    /// # Returns
+    ///
+    /// - Up to `k` pairs of document ID and distance (lower is closer), closest first.
+    ///   Empty if the index is not a vector index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::{Document, Index, IndexKind, VectorMetric};
+    ///
+    /// let mut index = Index::new(
+    ///     "embedding_hnsw",
+    ///     IndexKind::Vector { field: "embedding".to_string(), metric: VectorMetric::L2 },
+    /// );
+    /// let doc = Document::new(json!({ "embedding": [1.0, 0.0] }));
+    /// index.insert(&doc);
+    ///
+    /// let nearest = index.vector_search(&[0.9, 0.1], 1);
+    /// assert_eq!(nearest[0].0, doc.id);
+    /// ```
+    pub fn vector_search(&self, query: &[f64], k: usize) -> Vec<(String, f64)> {
+        match &self.data {
+            IndexData::Vector(graph) => graph.search(query, k),
+            _ => Vec::new(),
+        }
+    }
+
+    /// 🦀
+    /// Returns the IDs of the documents of a geo index whose grid cell intersects a bounding box.
+    ///
+    /// The result is a superset of the documents located inside the box: callers must
+    /// still check the exact position of each candidate.
+    ///
+    /// # Parameters
+    ///
+    /// - `min`: The south-west corner of the box.
+    /// - `max`: The north-east corner of the box.
+    ///
+    /// # Returns
+    ///
+    /// - The candidate document IDs. Empty if the index is not a geo index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::{Document, GeoPoint, Index, IndexKind};
+    ///
+    /// let mut index = Index::new("stores_loc", IndexKind::Geo { field: "loc".to_string() });
+    /// let doc = Document::new(json!({ "loc": { "lat": 48.85, "lng": 2.35 } }));
+    /// index.insert(&doc);
+    ///
+    /// let ids = index.geo_candidates(&GeoPoint { lat: 48.0, lng: 2.0 }, &GeoPoint { lat: 49.0, lng: 3.0 });
+    /// assert!(ids.contains(&doc.id));
+    /// ```
+    pub fn geo_candidates(&self, min: &GeoPoint, max: &GeoPoint) -> HashSet<String> {
+        let IndexData::Geo(grid) = &self.data else {
+            return HashSet::new();
+        };
+
+        let (min_row, min_col) = geo_cell(min);
+        let (max_row, max_col) = geo_cell(max);
+        let area = (max_row - min_row + 1).max(0) as u64 * (max_col - min_col + 1).max(0) as u64;
+
+        if area > grid.cells.len() as u64 {
+            grid.cells
+                .iter()
+                .filter(|((row, col), _)| {
+                    (min_row..=max_row).contains(row) && (min_col..=max_col).contains(col)
+                })
+                .flat_map(|(_, docs)| docs.iter().cloned())
+                .collect()
+        } else {
+            (min_row..=max_row)
+                .flat_map(|row| (min_col..=max_col).map(move |col| (row, col)))
+                .filter_map(|cell| grid.cells.get(&cell))
+                .flat_map(|docs| docs.iter().cloned())
+                .collect()
+        }
+    }
+
+    /// 🦀
+    /// Searches a text index and returns the BM25 relevance score of every matching document.
+    ///
+    /// The query is tokenized exactly like indexed fields. A document matches if it contains
+    /// at least one of the query terms; its score is the sum of the BM25 contributions of each term.
+    ///
+    /// # Parameters
+    ///
+    /// - `query`: The search string (e.g., `"rust database"`).
+    ///
+    /// # Returns
+    ///
+    /// - A map from document ID to relevance score. Empty if the index is not a text index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::{Document, Index, IndexKind};
+    ///
+    /// let mut index = Index::new("content", IndexKind::Text { fields: vec!["body".to_string()] });
+    /// let doc = Document::new(json!({ "body": "Indexing databases in Rust" }));
+    /// index.insert(&doc);
+    ///
+    /// let scores = index.text_search("database");
+    /// assert!(scores[&doc.id] > 0.0);
+    /// ```
+    pub fn text_search(&self, query: &str) -> HashMap<String, f64> {
+        let mut scores = HashMap::new();
+        let inverted = match &self.data {
+            IndexData::Text(inverted) => inverted,
+            _ => return scores,
+        };
+
+        let doc_count = inverted.doc_lengths.len() as f64;
+        if doc_count == 0.0 {
+            return scores;
+        }
+        let avg_length = (inverted.total_length as f64 / doc_count).max(1.0);
+
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+
+        for term in terms {
+            let Some(docs) = inverted.postings.get(&term) else {
+                continue;
+            };
+            let df = docs.len() as f64;
+            let idf = ((doc_count - df + 0.5) / (df + 0.5) + 1.0).ln();
+
+            for (doc_id, tf) in docs {
+                let tf = *tf as f64;
+                let length = inverted.doc_lengths.get(doc_id).copied().unwrap_or(0) as f64;
+                let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * length / avg_length);
+                *scores.entry(doc_id.clone()).or_insert(0.0) +=
+                    idf * tf * (BM25_K1 + 1.0) / (tf + norm);
+            }
+        }
+        scores
+    }
+
+    /// 🦀
+    /// Returns the Unix timestamp (in seconds) at which a document expires under this TTL index.
+    ///
+    /// # Parameters
+    ///
+    /// - `document`: The document to inspect.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(u64)` with the expiry time if this is a TTL index and the document holds a valid timestamp.
+    /// - `None` if this is not a TTL index, or if the field is missing or not a date; such documents never expire.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::models::{Document, Index, IndexKind};
+    ///
+    /// let index = Index::new("sessions_ttl", IndexKind::Ttl { field: "seen_at".to_string(), expire_after_secs: 60 });
+    /// let doc = Document::new(json!({ "seen_at": "2025-01-01T00:00:00Z" }));
+    /// assert_eq!(index.expires_at(&doc), Some(1735689600 + 60));
+    /// ```
+    pub fn expires_at(&self, document: &Document) -> Option<u64> {
+        let IndexKind::Ttl {
+            field,
+            expire_after_secs,
+        } = &self.kind
+        else {
+            return None;
+        };
+
+        let timestamp = match field.as_str() {
+            CREATED_AT_FIELD => document.created_at,
+            UPDATED_AT_FIELD => document.updated_at,
+            path => match get_nested_value(&document.data, path)? {
+                Value::Number(number) => number.as_f64().filter(|secs| *secs >= 0.0)? as u64,
+                Value::String(date) => {
+                    u64::try_from(DateTime::parse_from_rfc3339(date).ok()?.timestamp()).ok()?
+                }
+                _ => return None,
+            },
+        };
+        Some(timestamp.saturating_add(*expire_after_secs))
+    }
+}
+
+impl IndexKind {
+    /// 🦀
+    /// Checks that the index definition is usable.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the definition is valid.
+    /// - `Err(String)` with the reason otherwise (e.g., a text index without fields).
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            IndexKind::Text { fields } if fields.is_empty() => {
+                Err("a text index must cover at least one field".to_string())
+            }
+            IndexKind::Text { .. } => Ok(()),
+            IndexKind::Ttl { field, .. } if field.is_empty() => {
+                Err("a TTL index must name a date field".to_string())
+            }
+            IndexKind::Ttl { .. } => Ok(()),
+            IndexKind::Geo { field } if field.is_empty() => {
+                Err("a geo index must name a point field".to_string())
+            }
+            IndexKind::Geo { .. } => Ok(()),
+            IndexKind::Vector { field, .. } if field.is_empty() => {
+                Err("a vector index must name a vector field".to_string())
+            }
+            IndexKind::Vector { .. } => Ok(()),
+        }
+    }
+}
+
+impl Display for IndexKind {
+    /// 🦀
+    /// Formats the [`IndexKind`] for human-readable display, e.g. `text(title, body)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexKind::Text { fields } => write!(f, "text({})", fields.join(", ")),
+            IndexKind::Ttl {
+                field,
+                expire_after_secs,
+            } => write!(f, "ttl({}, {}s)", field, expire_after_secs),
+            IndexKind::Geo { field } => write!(f, "geo({})", field),
+            IndexKind::Vector { field, metric } => write!(f, "vector({}, {})", field, metric),
+        }
+    }
+}
+
+impl Display for Index {
+    /// 🦀
+    /// Formats the [`Index`] for human-readable display.
+    ///
+    /// # Example Output
+    ///
+    /// ```text
+    /// Index 'content' on text(title, body)
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Index '{}' on {}", self.name, self.kind)
+    }
+}
+
+/// Returns the terms a text index over `fields` holds for a document, one per occurrence.
+fn text_terms(fields: &[String], document: &Document) -> Vec<String> {
+    fields
+        .iter()
+        .filter_map(|field| get_nested_value(&document.data, field))
+        .filter_map(|value| value.as_str())
+        .flat_map(tokenize)
+        .collect()
+}
+
+/// Returns the `(row, column)` grid cell containing a point.
+fn geo_cell(point: &GeoPoint) -> (i32, i32) {
+    (
+        (point.lat / GEO_CELL_DEGREES).floor() as i32,
+        (point.lng / GEO_CELL_DEGREES).floor() as i32,
+    )
+}
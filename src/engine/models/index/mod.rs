@@ -0,0 +1,8 @@
+//! # Index Model
+//!
+//! This module defines secondary indexes that can be attached to a collection.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{GeoGrid, Index, IndexData, IndexKind, InvertedIndex};
@@ -0,0 +1,113 @@
+use crate::engine::models::vector::model::{HnswGraph, VectorMetric};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 🦀
+/// Declares what an [`Index`] indexes and how.
+///
+/// Only the definition is persisted with the database; the in-memory
+/// structures ([`IndexData`]) are rebuilt from the documents when the database is loaded.
+///
+/// # Variants
+///
+/// - `Text { fields }`: A full-text index over one or more string fields (dot paths allowed).
+///   It enables the `$text: { "$search": "..." }` filter operator with BM25 relevance ranking.
+/// - `Ttl { field, expire_after_secs }`: A time-to-live index. A document expires `expire_after_secs`
+///   seconds after the timestamp found in `field`, which is either a data field (dot paths allowed,
+///   holding a Unix timestamp in seconds or an RFC 3339 date string) or one of the document metadata
+///   fields `"$created_at"` / `"$updated_at"`. Expired documents are hidden from reads and purged.
+/// - `Geo { field }`: A spatial grid index over a point field (dot paths allowed). It speeds up
+///   the `$geoWithin` and `$near` filter operators on that field.
+/// - `Vector { field, metric }`: An approximate nearest neighbor (HNSW) index over a vector field.
+///   It speeds up k-nearest queries on that field using the same metric.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::IndexKind;
+///
+/// let kind = IndexKind::Text { fields: vec!["title".to_string(), "body".to_string()] };
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum IndexKind {
+    Text {
+        fields: Vec<String>,
+    },
+    Ttl {
+        field: String,
+        expire_after_secs: u64,
+    },
+    Geo {
+        field: String,
+    },
+    Vector {
+        field: String,
+        metric: VectorMetric,
+    },
+}
+
+/// 🦀
+/// The in-memory structure backing an [`Index`].
+///
+/// This is never serialized: it is derived from the collection's documents.
+#[derive(Debug, Clone, Default)]
+pub enum IndexData {
+    #[default]
+    Empty,
+    Text(InvertedIndex),
+    Geo(GeoGrid),
+    Vector(HnswGraph),
+}
+
+/// 🦀
+/// An inverted index mapping each search term to the documents containing it.
+///
+/// # Fields
+///
+/// - `postings`: For each term, the number of occurrences per document ID.
+/// - `doc_lengths`: The number of terms indexed for each document ID.
+/// - `total_length`: The sum of all document lengths, used for the BM25 average length.
+#[derive(Debug, Clone, Default)]
+pub struct InvertedIndex {
+    pub postings: HashMap<String, HashMap<String, u32>>,
+    pub doc_lengths: HashMap<String, u32>,
+    pub total_length: u64,
+}
+
+/// 🦀
+/// A uniform latitude/longitude grid bucketing documents by the cell their point falls in.
+///
+/// # Fields
+///
+/// - `cells`: For each `(row, column)` cell, the IDs of the documents located in it.
+/// - `positions`: The cell of each indexed document ID, used for removals.
+#[derive(Debug, Clone, Default)]
+pub struct GeoGrid {
+    pub cells: HashMap<(i32, i32), HashSet<String>>,
+    pub positions: HashMap<String, (i32, i32)>,
+}
+
+/// 🦀
+/// Represents a named secondary index attached to a [`crate::engine::models::Collection`].
+///
+/// # Fields
+///
+/// - `name`: The name of the index, unique within its collection.
+/// - `kind`: The [`IndexKind`] definition of the index.
+/// - `data`: The in-memory index structure, rebuilt on load and kept in sync on every write.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::{Index, IndexKind};
+///
+/// let index = Index::new("content", IndexKind::Text { fields: vec!["body".to_string()] });
+/// assert_eq!(index.name, "content");
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Index {
+    pub name: String,
+    pub kind: IndexKind,
+    #[serde(skip)]
+    pub data: IndexData,
+}
@@ -0,0 +1,48 @@
+use crate::engine::error::NosqliteError;
+use crate::engine::models::integrity::model::{IntegrityIssue, IntegrityReport};
+use std::fmt::Display;
+
+impl IntegrityReport {
+    /// 🦀
+    /// Returns `true` if no damage was found.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// 🦀
+    /// Records that the part of the database at `location` could not be read.
+    pub fn add_issue(&mut self, location: impl Into<String>, error: NosqliteError) {
+        self.issues.push(IntegrityIssue {
+            location: location.into(),
+            error,
+        });
+    }
+}
+
+impl Display for IntegrityReport {
+    /// 🦀
+    /// Formats the [`IntegrityReport`] for human-readable display.
+    ///
+    /// # Example Output
+    ///
+    /// ```text
+    /// Collections: 1
+    /// Documents: 3
+    /// Write-ahead log records: 0
+    /// Issues: 1
+    ///   - segment 3f2a… (orders): Corrupted data: '…' does not match its checksum
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Collections: {}", self.collections)?;
+        writeln!(f, "Documents: {}", self.documents)?;
+        writeln!(f, "Write-ahead log records: {}", self.wal_records)?;
+        if self.is_ok() {
+            return writeln!(f, "No issues found");
+        }
+        writeln!(f, "Issues: {}", self.issues.len())?;
+        for issue in &self.issues {
+            writeln!(f, "  - {}: {}", issue.location, issue.error)?;
+        }
+        Ok(())
+    }
+}
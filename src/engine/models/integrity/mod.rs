@@ -0,0 +1,8 @@
+//! # Integrity Model
+//!
+//! This module defines the report produced when checking or salvaging a stored database.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{IntegrityIssue, IntegrityReport};
@@ -0,0 +1,44 @@
+use crate::engine::error::NosqliteError;
+
+/// 🦀
+/// A damaged part of a stored database, found by [`crate::engine::Nosqlite::verify`] or skipped by
+/// [`crate::engine::Nosqlite::salvage`].
+///
+/// # Fields
+///
+/// - `location`: Where the damage is, e.g. `data file`, `segment 3f2a… (users)`,
+///   `collection 'users', document 12` or `write-ahead log`.
+/// - `error`: The [`NosqliteError`] reading that part failed with.
+#[derive(Debug, Clone)]
+pub struct IntegrityIssue {
+    pub location: String,
+    pub error: NosqliteError,
+}
+
+/// 🦀
+/// Represents the result of an integrity check of a stored database.
+///
+/// The counts describe what could be read; everything else is listed in `issues`.
+///
+/// # Fields
+///
+/// - `collections`: The number of readable collections.
+/// - `documents`: The number of readable documents, over all collections.
+/// - `wal_records`: The number of write-ahead log records replayed on top of the data file.
+/// - `issues`: The [`IntegrityIssue`]s found, empty for a healthy database.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::IntegrityReport;
+///
+/// let report = IntegrityReport { collections: 2, documents: 10, ..IntegrityReport::default() };
+/// assert!(report.is_ok());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    pub collections: usize,
+    pub documents: usize,
+    pub wal_records: usize,
+    pub issues: Vec<IntegrityIssue>,
+}
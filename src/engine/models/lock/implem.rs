@@ -0,0 +1,153 @@
+use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
+use crate::engine::models::lock::model::DatabaseLock;
+use std::fs::{self, OpenOptions, TryLockError};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delay between two attempts to take a lock held by another process.
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+impl DatabaseLock {
+    /// 🦀
+    /// Returns the path of the lock file of a database: the database path with `.lock` appended
+    /// (e.g. `data/app.nosqlite.lock` for `data/app.nosqlite`), so that databases differing only by
+    /// extension have their own lock.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::DatabaseLock;
+    ///
+    /// assert_eq!(DatabaseLock::lock_path("data/app.nosqlite"), "data/app.nosqlite.lock");
+    /// assert_ne!(DatabaseLock::lock_path("data/app.db"), DatabaseLock::lock_path("data/app"));
+    /// ```
+    pub fn lock_path(db_path: &str) -> String {
+        format!("{}.lock", db_path)
+    }
+
+    /// 🦀
+    /// Takes the lock of a database, creating its lock file, and the directory of the database, if
+    /// needed. A shared lock reuses an existing lock file read-only.
+    ///
+    /// An exclusive lock is refused while any other lock is held; a shared lock is only refused
+    /// while an exclusive lock is held. Locks are advisory: they only protect against processes
+    /// that also take them, such as other NoSQLite instances.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The path of the database; the lock is held on [`DatabaseLock::lock_path`].
+    /// - `shared`: `true` to take a shared lock, `false` for an exclusive one.
+    /// - `timeout`: How long to wait for the lock to be released; `None` fails at once.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] used to log failures.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(DatabaseLock)` holding the lock until it is dropped.
+    /// - `Err(NosqliteError::DatabaseLocked)` if another instance still holds a conflicting lock
+    ///   once the timeout is over.
+    /// - `Err(NosqliteError::IoError)` if the lock file cannot be opened or locked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::error::{NosqliteError, NosqliteErrorHandler};
+    /// use nosqlite_rust::engine::models::DatabaseLock;
+    ///
+    /// let mut handler = NosqliteErrorHandler::new("temp/data65.nosqlite".to_string());
+    /// let lock = DatabaseLock::acquire("temp/data65.nosqlite", false, None, &mut handler)?;
+    ///
+    /// let second = DatabaseLock::acquire("temp/data65.nosqlite", true, None, &mut handler);
+    /// assert!(matches!(second, Err(NosqliteError::DatabaseLocked(_))));
+    ///
+    /// drop(lock);
+    /// DatabaseLock::acquire("temp/data65.nosqlite", true, None, &mut handler)?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn acquire(
+        db_path: &str,
+        shared: bool,
+        timeout: Option<Duration>,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Self, NosqliteError> {
+        let path = Self::lock_path(db_path);
+        if shared {
+            if let Ok(file) = fs::File::open(&path) {
+                return Self::wait_for_lock(file, path, db_path, shared, timeout, handler);
+            }
+        }
+        if let Some(parent) = Path::new(&path).parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                handler.try_or_log(fs::create_dir_all(parent), |e| {
+                    NosqliteError::IoError(format!(
+                        "cannot create directory '{}': {}",
+                        parent.display(),
+                        e
+                    ))
+                })?;
+            }
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path);
+        let file = handler.try_or_log(file, |e| {
+            NosqliteError::IoError(format!("cannot open lock file '{}': {}", path, e))
+        })?;
+        Self::wait_for_lock(file, path, db_path, shared, timeout, handler)
+    }
+
+    /// Takes the OS lock on an open lock file, retrying until `timeout` while another instance holds it.
+    fn wait_for_lock(
+        file: fs::File,
+        path: String,
+        db_path: &str,
+        shared: bool,
+        timeout: Option<Duration>,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Self, NosqliteError> {
+        // A timeout too large to add to the current instant waits with no deadline.
+        let deadline = Instant::now().checked_add(timeout.unwrap_or_default());
+        loop {
+            let result = match shared {
+                true => file.try_lock_shared(),
+                false => file.try_lock(),
+            };
+            match result {
+                Ok(()) => {
+                    return Ok(Self {
+                        file: Arc::new(file),
+                        path,
+                        shared,
+                    })
+                }
+                Err(TryLockError::WouldBlock)
+                    if deadline.is_none_or(|deadline| Instant::now() < deadline) =>
+                {
+                    thread::sleep(LOCK_RETRY_DELAY);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    let error = NosqliteError::DatabaseLocked(format!(
+                        "'{}' is in use by another instance",
+                        db_path
+                    ));
+                    handler.log_error(error.clone());
+                    return Err(error);
+                }
+                Err(TryLockError::Error(e)) => {
+                    let error = NosqliteError::IoError(format!("cannot lock '{}': {}", path, e));
+                    handler.log_error(error.clone());
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// 🦀
+    /// Returns the open lock file the lock is held on.
+    pub fn file(&self) -> &fs::File {
+        &self.file
+    }
+}
@@ -0,0 +1,8 @@
+//! # Lock Model
+//!
+//! This module defines the advisory lock keeping several processes from writing the same database.
+
+pub mod implem;
+pub mod model;
+
+pub use model::DatabaseLock;
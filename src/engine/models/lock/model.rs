@@ -0,0 +1,20 @@
+use std::fs;
+use std::sync::Arc;
+
+/// 🦀
+/// An advisory OS lock held on the lock file of a database, released when the last clone is dropped.
+///
+/// The lock is taken on a separate `.lock` file rather than on the database itself, since saves
+/// replace the database file by renaming a new one over it.
+///
+/// # Fields
+///
+/// - `file`: The open lock file the OS lock is held on.
+/// - `path`: The path of the lock file.
+/// - `shared`: `true` for a shared lock, which only excludes exclusive locks.
+#[derive(Debug, Clone)]
+pub struct DatabaseLock {
+    pub(crate) file: Arc<fs::File>,
+    pub path: String,
+    pub shared: bool,
+}
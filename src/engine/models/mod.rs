@@ -4,15 +4,54 @@
 //! - `Collection`
 //! - `Database`
 //! - `Document`
+//! - `ExportFormat`
 //! - `File`
+//! - `GeoPoint`
+//! - `Index`
+//! - `IntegrityReport`
+//! - `DatabaseLock`
+//! - `NosqliteOptions`
+//! - `QueryPlan`
+//! - `SegmentCatalog`
+//! - `StorageBackend`
+//! - `Transaction`
+//! - `VacuumReport`
+//! - `VectorMetric`
+//! - `WalRecord`
 
 pub mod collection;
 pub mod database;
 pub mod document;
+pub mod export;
 pub mod file;
+pub mod geo;
+pub mod index;
+pub mod integrity;
+pub mod lock;
+pub mod options;
+pub mod query_plan;
+pub mod segment;
+pub mod storage;
+pub mod transaction;
 pub mod utils;
+pub mod vacuum;
+pub mod vector;
+pub mod wal;
 
 pub use collection::Collection;
 pub use database::Database;
 pub use document::Document;
-pub use file::File;
+pub use export::ExportFormat;
+pub use file::{EncryptionKey, File, FileHeader, PayloadStats};
+pub use geo::{GeoPoint, GeoQuery, GeoShape};
+pub use index::{Index, IndexKind};
+pub use integrity::{IntegrityIssue, IntegrityReport};
+pub use lock::DatabaseLock;
+pub use options::{Compression, Durability, Encoding, Kdf, KeySource, Layout, NosqliteOptions};
+pub use query_plan::{AccessPath, QueryPlan};
+pub use segment::{SegmentCatalog, SegmentEntry, SegmentTable};
+pub use storage::{FaultyBackend, FileBackend, MemoryBackend, StorageBackend};
+pub use transaction::Transaction;
+pub use vacuum::VacuumReport;
+pub use vector::VectorMetric;
+pub use wal::{WalOperation, WalRecord};
@@ -0,0 +1,344 @@
+use super::model::{Compression, Durability, Encoding, Kdf, KeySource, Layout, NosqliteOptions};
+use crate::engine::models::file::implem::DEFAULT_KEY_PATH;
+use argon2::{Algorithm, Argon2, Params, Version};
+use pbkdf2::pbkdf2_hmac;
+use sha2::Sha256;
+use std::fmt::Debug;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Default size of the write-ahead log that triggers a checkpoint: 4 MiB.
+pub const DEFAULT_WAL_CHECKPOINT_BYTES: u64 = 4 * 1024 * 1024;
+
+impl KeySource {
+    /// 🦀
+    /// Returns the path of the key file used by this source, if it is file-based.
+    ///
+    /// # Parameters
+    ///
+    /// - `db_path`: The path of the database, used to locate a [`KeySource::Sidecar`] key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::KeySource;
+    ///
+    /// assert_eq!(KeySource::Sidecar.key_path("data/app.nosqlite"), Some("data/app.key".to_string()));
+    /// assert_eq!(KeySource::Env("KEY".to_string()).key_path("data/app.nosqlite"), None);
+    /// ```
+    pub fn key_path(&self, db_path: &str) -> Option<String> {
+        match self {
+            KeySource::File(path) => Some(path.clone()),
+            KeySource::Sidecar => Some(
+                Path::new(db_path)
+                    .with_extension("key")
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            KeySource::Bytes(_)
+            | KeySource::Env(_)
+            | KeySource::Passphrase(_)
+            | KeySource::Unencrypted => None,
+        }
+    }
+}
+
+impl Default for KeySource {
+    /// 🦀
+    /// Returns [`KeySource::File`] pointing at `db.key` in the current working directory.
+    fn default() -> Self {
+        KeySource::File(DEFAULT_KEY_PATH.to_string())
+    }
+}
+
+impl Debug for KeySource {
+    /// 🦀
+    /// Formats the [`KeySource`] without revealing raw key bytes or passphrases.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeySource::File(path) => f.debug_tuple("File").field(path).finish(),
+            KeySource::Bytes(_) => f.debug_tuple("Bytes").field(&"<redacted>").finish(),
+            KeySource::Env(name) => f.debug_tuple("Env").field(name).finish(),
+            KeySource::Sidecar => write!(f, "Sidecar"),
+            KeySource::Unencrypted => write!(f, "Unencrypted"),
+            KeySource::Passphrase(_) => f.debug_tuple("Passphrase").field(&"<redacted>").finish(),
+        }
+    }
+}
+
+impl Default for NosqliteOptions {
+    /// 🦀
+    /// Returns the options used by [`crate::engine::Nosqlite::open`].
+    fn default() -> Self {
+        NosqliteOptions {
+            key_source: KeySource::default(),
+            kdf: Kdf::default(),
+            fallback_key_sources: Vec::new(),
+            wal: false,
+            wal_checkpoint_bytes: DEFAULT_WAL_CHECKPOINT_BYTES,
+            durability: Durability::default(),
+            read_only: false,
+            lock_timeout: None,
+            encoding: Encoding::default(),
+            compression: Compression::default(),
+            layout: Layout::default(),
+        }
+    }
+}
+
+impl NosqliteOptions {
+    /// 🦀
+    /// Sets the [`KeySource`] of the encryption key.
+    pub fn with_key_source(mut self, key_source: KeySource) -> Self {
+        self.key_source = key_source;
+        self
+    }
+
+    /// 🦀
+    /// Adds an older [`KeySource`] accepted to decrypt the database, e.g. while migrating to a new key.
+    pub fn with_fallback_key(mut self, key_source: KeySource) -> Self {
+        self.fallback_key_sources.push(key_source);
+        self
+    }
+
+    /// 🦀
+    /// Sets the [`Kdf`] used to derive keys from new passphrases.
+    pub fn with_kdf(mut self, kdf: Kdf) -> Self {
+        self.kdf = kdf;
+        self
+    }
+
+    /// 🦀
+    /// Enables or disables the write-ahead log.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::NosqliteOptions;
+    ///
+    /// let options = NosqliteOptions::default().with_wal(true).with_wal_checkpoint_bytes(1 << 20);
+    /// assert!(options.wal);
+    /// ```
+    pub fn with_wal(mut self, enabled: bool) -> Self {
+        self.wal = enabled;
+        self
+    }
+
+    /// 🦀
+    /// Sets the size of the write-ahead log, in bytes, that triggers a checkpoint.
+    pub fn with_wal_checkpoint_bytes(mut self, bytes: u64) -> Self {
+        self.wal_checkpoint_bytes = bytes;
+        self
+    }
+
+    /// 🦀
+    /// Sets the [`Durability`] mode deciding when mutations are written to disk.
+    pub fn with_durability(mut self, durability: Durability) -> Self {
+        self.durability = durability;
+        self
+    }
+
+    /// 🦀
+    /// Opens the database read-only, or read-write (the default).
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// 🦀
+    /// Sets how long opening waits for another instance to release the database.
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = Some(timeout);
+        self
+    }
+
+    /// 🦀
+    /// Sets the [`Encoding`] the data file is written with.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// 🦀
+    /// Sets the [`Compression`] applied to the data file.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// 🦀
+    /// Sets the [`Layout`] the database is written with.
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+}
+
+impl Compression {
+    /// 🦀
+    /// Compresses `data` with this algorithm; [`Compression::None`] returns it unchanged.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<u8>)` with the compressed bytes.
+    /// - `Err(String)` if the compressor fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::Compression;
+    ///
+    /// let data = b"{\"name\":\"Alice\"},".repeat(100);
+    /// let compressed = Compression::Deflate.compress(&data).unwrap();
+    /// assert!(compressed.len() < data.len() / 10);
+    /// assert_eq!(Compression::Deflate.decompress(&compressed).unwrap(), data);
+    /// ```
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Deflate => {
+                let mut encoder =
+                    flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data).map_err(|e| e.to_string())?;
+                encoder.finish().map_err(|e| e.to_string())
+            }
+            Compression::Zstd => {
+                zstd::encode_all(data, zstd::DEFAULT_COMPRESSION_LEVEL).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// 🦀
+    /// Decompresses `data` compressed by [`Compression::compress`] with the same algorithm.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<u8>)` with the original bytes.
+    /// - `Err(String)` if `data` is not a valid stream of this algorithm.
+    pub fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            Compression::None => Ok(data.to_vec()),
+            Compression::Deflate => {
+                let mut decompressed = Vec::new();
+                flate2::read::ZlibDecoder::new(data)
+                    .read_to_end(&mut decompressed)
+                    .map_err(|e| e.to_string())?;
+                Ok(decompressed)
+            }
+            Compression::Zstd => zstd::decode_all(data).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// 🦀
+    /// Returns `true` for [`Compression::None`], which is left out of file headers.
+    pub fn is_none(&self) -> bool {
+        *self == Compression::None
+    }
+}
+
+impl Layout {
+    /// 🦀
+    /// Returns `true` for [`Layout::Single`], which is left out of file headers.
+    pub fn is_single(&self) -> bool {
+        *self == Layout::Single
+    }
+}
+
+impl Encoding {
+    /// 🦀
+    /// Returns `true` for [`Encoding::Json`], which is left out of file headers.
+    pub fn is_json(&self) -> bool {
+        *self == Encoding::Json
+    }
+}
+
+impl Durability {
+    /// 🦀
+    /// Decides whether the unsaved mutations should be written now.
+    ///
+    /// # Parameters
+    ///
+    /// - `unflushed_changes`: The number of mutations not written yet.
+    /// - `since_last_flush`: The time elapsed since the last write.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use nosqlite_rust::engine::models::Durability;
+    ///
+    /// assert!(Durability::Always.should_flush(1, Duration::ZERO));
+    /// assert!(!Durability::Manual.should_flush(1_000, Duration::from_secs(3600)));
+    /// assert!(Durability::EveryN(3).should_flush(3, Duration::ZERO));
+    /// assert!(!Durability::Interval(Duration::from_secs(5)).should_flush(10, Duration::from_secs(1)));
+    /// ```
+    pub fn should_flush(&self, unflushed_changes: usize, since_last_flush: Duration) -> bool {
+        match self {
+            Durability::Always => true,
+            Durability::Manual => false,
+            Durability::EveryN(n) => unflushed_changes >= (*n).max(1),
+            Durability::Interval(interval) => since_last_flush >= *interval,
+        }
+    }
+}
+
+impl Kdf {
+    /// 🦀
+    /// Derives a 256-bit key from a passphrase and a salt.
+    ///
+    /// # Parameters
+    ///
+    /// - `passphrase`: The passphrase to derive the key from.
+    /// - `salt`: A random salt of at least 8 bytes.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok([u8; 32])` with the derived key.
+    /// - `Err(String)` if the cost parameters or the salt are rejected by the algorithm.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::Kdf;
+    ///
+    /// let kdf = Kdf::Pbkdf2 { iterations: 1_000 };
+    /// let key = kdf.derive("correct horse battery staple", b"0123456789abcdef").unwrap();
+    /// assert_eq!(key, kdf.derive("correct horse battery staple", b"0123456789abcdef").unwrap());
+    /// ```
+    pub fn derive(&self, passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+        let mut key = [0u8; 32];
+        match *self {
+            Kdf::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => {
+                let params = Params::new(memory_kib, iterations, parallelism, Some(key.len()))
+                    .map_err(|e| e.to_string())?;
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+                    .map_err(|e| e.to_string())?;
+            }
+            Kdf::Pbkdf2 { iterations } => {
+                if iterations == 0 {
+                    return Err("PBKDF2 needs at least one iteration".to_string());
+                }
+                pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+            }
+        }
+        Ok(key)
+    }
+}
+
+impl Default for Kdf {
+    /// 🦀
+    /// Returns Argon2id with 19 MiB of memory, 2 iterations and no parallelism (OWASP recommendation).
+    fn default() -> Self {
+        Kdf::Argon2id {
+            memory_kib: 19_456,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
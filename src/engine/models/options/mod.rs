@@ -0,0 +1,8 @@
+//! # Options Model
+//!
+//! This module defines the options used to open a database, such as where its encryption key comes from.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{Compression, Durability, Encoding, Kdf, KeySource, Layout, NosqliteOptions};
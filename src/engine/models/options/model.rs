@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 🦀
+/// Where the 256-bit AES key of a database comes from.
+///
+/// # Variants
+///
+/// - `File(path)`: A hex-encoded key file at `path`. It is generated when a new database is created.
+/// - `Bytes(key)`: Raw key bytes supplied by the application. Nothing is written to disk.
+/// - `Env(name)`: A hex-encoded key read from the environment variable `name`.
+/// - `Sidecar`: A hex-encoded key file stored next to the database, with the `.key` extension
+///   (e.g. `data/app.key` for `data/app.nosqlite`). It is generated when a new database is created.
+/// - `Passphrase(passphrase)`: A key derived from a passphrase with a [`Kdf`]. The salt and KDF
+///   parameters are stored in the plaintext header of the database file.
+/// - `Unencrypted`: No key at all: the database is stored as plain JSON, for development, tests and
+///   fixtures. The file header marks it as unencrypted, so it cannot be opened with a key by mistake.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::KeySource;
+///
+/// let source = KeySource::Env("APP_DB_KEY".to_string());
+/// ```
+#[derive(Clone, PartialEq)]
+pub enum KeySource {
+    File(String),
+    Bytes([u8; 32]),
+    Env(String),
+    Sidecar,
+    Passphrase(String),
+    Unencrypted,
+}
+
+/// 🦀
+/// A password-based key derivation function and its cost parameters.
+///
+/// # Variants
+///
+/// - `Argon2id { memory_kib, iterations, parallelism }`: Argon2id, the recommended choice.
+/// - `Pbkdf2 { iterations }`: PBKDF2-HMAC-SHA256, for environments where Argon2 is not acceptable.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::Kdf;
+///
+/// let kdf = Kdf::Pbkdf2 { iterations: 600_000 };
+/// assert_eq!(Kdf::default(), Kdf::Argon2id { memory_kib: 19_456, iterations: 2, parallelism: 1 });
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "algorithm", rename_all = "lowercase")]
+pub enum Kdf {
+    Argon2id {
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+    Pbkdf2 {
+        iterations: u32,
+    },
+}
+
+/// 🦀
+/// How the [`crate::engine::models::Database`] is serialized in the data file.
+///
+/// The encoding is recorded in the [`crate::engine::models::FileHeader`], so a file is always read
+/// with the encoding it was written with.
+///
+/// # Variants
+///
+/// - `Json`: Pretty-printed JSON, base64-encoded once encrypted. This is the default, and the only
+///   encoding of files written before encodings existed.
+/// - `MessagePack`: Compact binary MessagePack, stored as raw bytes (encrypted or not) after the
+///   header line, without base64.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::{Encoding, NosqliteOptions};
+///
+/// let options = NosqliteOptions::default().with_encoding(Encoding::MessagePack);
+/// assert_eq!(NosqliteOptions::default().encoding, Encoding::Json);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+/// 🦀
+/// How the serialized payload of the data file is compressed, before it is encrypted.
+///
+/// Ciphertext does not compress, so compression happens between serialization and encryption.
+/// The algorithm is recorded in the [`crate::engine::models::FileHeader`], so a file is always
+/// decompressed with the algorithm it was written with.
+///
+/// # Variants
+///
+/// - `None`: The payload is stored as serialized. This is the default.
+/// - `Deflate`: DEFLATE (zlib format), at the default level.
+/// - `Zstd`: Zstandard, at the default level; usually faster and smaller than DEFLATE.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::{Compression, NosqliteOptions};
+///
+/// let options = NosqliteOptions::default().with_compression(Compression::Zstd);
+/// assert_eq!(NosqliteOptions::default().compression, Compression::None);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    #[default]
+    None,
+    Deflate,
+    Zstd,
+}
+
+/// 🦀
+/// How a database is laid out in storage.
+///
+/// The layout is recorded in the [`crate::engine::models::FileHeader`], so a database is always
+/// read with the layout it was written with; opened with another layout, it is converted on the
+/// next save.
+///
+/// # Variants
+///
+/// - `Single`: The whole database is one encrypted payload, loaded in full on open. This is the
+///   default.
+/// - `Segmented`: The data file only holds a catalog of the collections; each collection is stored
+///   in its own, separately encrypted segment. Collections are loaded on first access, and a save
+///   only rewrites the segments of the collections changed since the previous save.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::{Layout, NosqliteOptions};
+///
+/// let options = NosqliteOptions::default().with_layout(Layout::Segmented);
+/// assert_eq!(NosqliteOptions::default().layout, Layout::Single);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Layout {
+    #[default]
+    Single,
+    Segmented,
+}
+
+/// 🦀
+/// When the changes made to a database are written to disk.
+///
+/// # Variants
+///
+/// - `Always`: Every mutation is persisted before the method returns. This is the default.
+/// - `Manual`: Mutations stay in memory until [`crate::engine::Nosqlite::flush`] is called.
+/// - `EveryN(n)`: Mutations are persisted together every `n` mutations.
+/// - `Interval(duration)`: Mutations are persisted by the first mutation made at least `duration`
+///   after the previous write. No background thread is involved: call
+///   [`crate::engine::Nosqlite::flush`] before exiting to persist the last changes.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::{Durability, NosqliteOptions};
+///
+/// let options = NosqliteOptions::default().with_durability(Durability::EveryN(100));
+/// assert_eq!(NosqliteOptions::default().durability, Durability::Always);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Durability {
+    #[default]
+    Always,
+    Manual,
+    EveryN(usize),
+    Interval(Duration),
+}
+
+/// 🦀
+/// Options controlling how a database is opened by [`crate::engine::Nosqlite::open_with_options`].
+///
+/// # Fields
+///
+/// - `key_source`: The [`KeySource`] of the encryption key. Defaults to the `db.key` file in the
+///   current working directory, as used by [`crate::engine::Nosqlite::open`].
+/// - `kdf`: The [`Kdf`] used when a new passphrase is set. Existing databases keep the
+///   parameters stored in their header until their passphrase is changed.
+/// - `fallback_key_sources`: Older keys accepted to decrypt the database during a key rotation.
+///   They are never used to encrypt: the next save re-encrypts the database with `key_source`.
+/// - `wal`: Whether mutations are appended to a write-ahead log next to the database instead of
+///   rewriting the whole file. The log is replayed on open and compacted by checkpoints.
+/// - `wal_checkpoint_bytes`: The size of the write-ahead log, in bytes, above which a checkpoint
+///   rewrites the database and empties the log.
+/// - `durability`: The [`Durability`] mode deciding when mutations are written to disk.
+/// - `read_only`: Opens an existing database without ever writing to its directory: every mutation
+///   fails with [`crate::engine::error::NosqliteError::ReadOnly`], and a shared lock lets other
+///   readers in.
+/// - `lock_timeout`: How long opening waits for another instance to release the database; `None`
+///   (the default) fails at once with [`crate::engine::error::NosqliteError::DatabaseLocked`].
+/// - `encoding`: The [`Encoding`] every save writes the data file with. Files in another encoding
+///   are still read, and rewritten in this one by their next save.
+/// - `compression`: The [`Compression`] every save applies to the data file, with the same rules
+///   as `encoding`.
+/// - `layout`: The [`Layout`] every save writes the database with, with the same rules as
+///   `encoding`.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::{KeySource, NosqliteOptions};
+///
+/// let options = NosqliteOptions::default().with_key_source(KeySource::Sidecar);
+/// assert_eq!(options.key_source, KeySource::Sidecar);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NosqliteOptions {
+    pub key_source: KeySource,
+    pub kdf: Kdf,
+    pub fallback_key_sources: Vec<KeySource>,
+    pub wal: bool,
+    pub wal_checkpoint_bytes: u64,
+    pub durability: Durability,
+    pub read_only: bool,
+    pub lock_timeout: Option<Duration>,
+    pub encoding: Encoding,
+    pub compression: Compression,
+    pub layout: Layout,
+}
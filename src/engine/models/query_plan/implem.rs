@@ -0,0 +1,53 @@
+use super::model::{AccessPath, QueryPlan};
+use std::fmt::Display;
+
+impl Display for AccessPath {
+    /// 🦀
+    /// Formats the [`AccessPath`] for human-readable display.
+    ///
+    /// # Example Output
+    ///
+    /// ```text
+    /// FULL SCAN
+    /// INDEX 'body_text'
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessPath::FullScan => write!(f, "FULL SCAN"),
+            AccessPath::Index(name) => write!(f, "INDEX '{}'", name),
+        }
+    }
+}
+
+impl Display for QueryPlan {
+    /// 🦀
+    /// Formats the [`QueryPlan`] for human-readable display.
+    ///
+    /// The output lists the queried collection, the filter, the chosen access path,
+    /// the examined/returned document counts and the time taken. The time uses the same
+    /// `Debug` formatting as the REPL `--timing` flag.
+    ///
+    /// # Example Output
+    ///
+    /// ```text
+    /// Query plan for 'users'
+    ///   Filter: {"name":"Alice"}
+    ///   Access path: FULL SCAN
+    ///   Documents examined: 10
+    ///   Documents returned: 1
+    ///   ⏱ Time taken: 42µs
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`QueryPlan`] — the structure being formatted
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Query plan for '{}'", self.collection)?;
+        writeln!(f, "  Filter: {}", self.filter)?;
+        writeln!(f, "  Access path: {}", self.access_path)?;
+        writeln!(f, "  Documents examined: {}", self.documents_examined)?;
+        writeln!(f, "  Documents returned: {}", self.documents_returned)?;
+        writeln!(f, "  ⏱ Time taken: {:?}", self.execution_time)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+//! # Query Plan Model
+//!
+//! This module defines the report produced when explaining a query.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{AccessPath, QueryPlan};
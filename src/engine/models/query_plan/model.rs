@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+/// 🦀
+/// Describes how the engine reached the documents of a collection while running a query.
+///
+/// # Variants
+///
+/// - `FullScan`: Every document of the collection was visited.
+/// - `Index(name)`: Candidates were taken from the named index before the filter was applied.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum AccessPath {
+    FullScan,
+    Index(String),
+}
+
+/// 🦀
+/// Represents the execution report of a query, as returned by [`crate::engine::Nosqlite::explain`].
+///
+/// A `QueryPlan` records which access path was chosen, how much work the engine did
+/// and how long the query took, so that slow queries can be understood.
+///
+/// # Fields
+///
+/// - `collection`: The name of the queried collection.
+/// - `filter`: The filter that was evaluated.
+/// - `access_path`: The chosen [`AccessPath`] (full scan or index).
+/// - `documents_examined`: The number of documents the filter was evaluated against.
+/// - `documents_returned`: The number of documents that matched the filter.
+/// - `execution_time`: The wall-clock time spent running the query.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use serde_json::json;
+/// use nosqlite_rust::engine::models::{AccessPath, QueryPlan};
+///
+/// let plan = QueryPlan {
+///     collection: "users".to_string(),
+///     filter: json!({ "name": "Alice" }),
+///     access_path: AccessPath::FullScan,
+///     documents_examined: 10,
+///     documents_returned: 1,
+///     execution_time: Duration::from_micros(42),
+/// };
+/// assert_eq!(plan.access_path, AccessPath::FullScan);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QueryPlan {
+    pub collection: String,
+    pub filter: Value,
+    pub access_path: AccessPath,
+    pub documents_examined: usize,
+    pub documents_returned: usize,
+    pub execution_time: Duration,
+}
@@ -0,0 +1,90 @@
+use crate::engine::models::segment::model::{SegmentCatalog, SegmentEntry, SegmentTable};
+use crate::engine::models::Database;
+use uuid::Uuid;
+
+impl SegmentCatalog {
+    /// 🦀
+    /// Builds the catalog of a database from the segments its collections are stored in.
+    ///
+    /// # Parameters
+    ///
+    /// - `db`: The [`Database`], whose collections are all either loaded or in
+    ///   [`SegmentTable::unloaded`].
+    /// - `segments`: The segment of every collection, by name.
+    ///
+    /// # Returns
+    ///
+    /// The catalog listing the collections of [`SegmentTable::stored`] still in the database first,
+    /// in their order, then the collections created since.
+    pub fn from_database(db: &Database, segments: &[SegmentEntry]) -> Self {
+        let mut names: Vec<&str> = db
+            .segments
+            .stored
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .filter(|name| {
+                db.segments.unloaded.contains(*name) || db.get_collection(name).is_some()
+            })
+            .collect();
+        for collection in &db.collections {
+            if !names.contains(&collection.name.as_str()) {
+                names.push(&collection.name);
+            }
+        }
+        let collections = names
+            .into_iter()
+            .filter_map(|name| segments.iter().find(|entry| entry.name == name).cloned())
+            .collect();
+        SegmentCatalog {
+            collections,
+            wal_sequence: db.wal_sequence,
+        }
+    }
+}
+
+impl SegmentTable {
+    /// 🦀
+    /// Starts the table of a database read from `catalog`, with none of its collections loaded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::{SegmentCatalog, SegmentEntry, SegmentTable};
+    ///
+    /// let catalog = SegmentCatalog {
+    ///     collections: vec![SegmentEntry { name: "users".to_string(), segment: "a1".to_string() }],
+    ///     wal_sequence: 0,
+    /// };
+    /// let table = SegmentTable::from_catalog(&catalog);
+    /// assert_eq!(table.segment_of("users"), Some("a1"));
+    /// assert!(table.unloaded.contains("users"));
+    /// ```
+    pub fn from_catalog(catalog: &SegmentCatalog) -> Self {
+        SegmentTable {
+            stored: catalog.collections.clone(),
+            unloaded: catalog
+                .collections
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect(),
+            dirty: Default::default(),
+        }
+    }
+
+    /// 🦀
+    /// Returns the segment the collection `name` was last stored in, if any.
+    pub fn segment_of(&self, name: &str) -> Option<&str> {
+        self.stored
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.segment.as_str())
+    }
+
+    /// 🦀
+    /// Returns a new segment identifier. Segments are never overwritten: every write of a
+    /// collection goes to a new segment, and the previous one is removed once the catalog no longer
+    /// references it.
+    pub fn new_segment_id() -> String {
+        Uuid::new_v4().simple().to_string()
+    }
+}
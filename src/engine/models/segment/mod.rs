@@ -0,0 +1,9 @@
+//! # Segment Model
+//!
+//! This module defines the segmented layout of a database: the catalog stored in the data file,
+//! and the table tracking which collections are loaded and which must be rewritten.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{SegmentCatalog, SegmentEntry, SegmentTable};
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// 🦀
+/// The payload of the data file of a database written with the segmented
+/// [`Layout`](crate::engine::models::Layout): the list of its collections and of the segments
+/// holding them.
+///
+/// # Fields
+///
+/// - `collections`: One [`SegmentEntry`] per collection, in the order of the collections.
+/// - `wal_sequence`: As [`crate::engine::models::Database::wal_sequence`], for the whole database.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SegmentCatalog {
+    pub collections: Vec<SegmentEntry>,
+    #[serde(default)]
+    pub wal_sequence: u64,
+}
+
+/// 🦀
+/// A collection of a segmented database and the segment it is stored in.
+///
+/// # Fields
+///
+/// - `name`: The name of the collection.
+/// - `segment`: The identifier of the segment, unique for every write of the collection.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SegmentEntry {
+    pub name: String,
+    pub segment: String,
+}
+
+/// 🦀
+/// The in-memory state of the segments of a [`crate::engine::models::Database`].
+///
+/// It is empty for a database read from a single data file. It is part of the database, so that a
+/// transaction rolled back also restores which collections are loaded and changed.
+///
+/// # Fields
+///
+/// - `stored`: The [`SegmentEntry`]s of the catalog last read or written.
+/// - `unloaded`: The collections stored in a segment that have not been loaded yet.
+/// - `dirty`: The loaded collections changed since their segment was written.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SegmentTable {
+    pub stored: Vec<SegmentEntry>,
+    pub unloaded: BTreeSet<String>,
+    pub dirty: BTreeSet<String>,
+}
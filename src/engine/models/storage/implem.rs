@@ -0,0 +1,786 @@
+use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
+use crate::engine::models::storage::model::{
+    Faults, FaultyBackend, FileBackend, MemoryBackend, MemoryStorage, StorageBackend,
+};
+use crate::engine::models::{DatabaseLock, File};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delay between two attempts to take a lock held by another [`MemoryBackend`].
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+impl FileBackend {
+    /// 🦀
+    /// Creates a backend for the database file at `path`. Nothing is read, written or locked yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::error::{NosqliteError, NosqliteErrorHandler};
+    /// use nosqlite_rust::engine::models::{FileBackend, StorageBackend};
+    ///
+    /// let mut handler = NosqliteErrorHandler::new("temp/data70.nosqlite".to_string());
+    /// let mut backend = FileBackend::new("temp/data70.nosqlite");
+    /// backend.save(b"content", &mut handler)?;
+    /// assert_eq!(backend.load(&mut handler)?, Some(b"content".to_vec()));
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            lock: None,
+        }
+    }
+
+    /// 🦀
+    /// Flushes the directory containing `path`, making a rename into it durable.
+    ///
+    /// Directories cannot be opened as files on every platform; elsewhere this is a no-op.
+    fn sync_parent_dir(path: &str) -> std::io::Result<()> {
+        #[cfg(unix)]
+        {
+            let parent = match Path::new(path).parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            fs::File::open(parent)?.sync_all()?;
+        }
+        #[cfg(not(unix))]
+        let _ = path;
+        Ok(())
+    }
+
+    /// 🦀
+    /// Returns the path of the segment `id`, in the directory [`File::segments_path`].
+    fn segment_path(&self, id: &str) -> String {
+        Path::new(&File::segments_path(&self.path))
+            .join(id)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// 🦀
+    /// Returns the path of the snapshot `name`, in the directory [`File::snapshots_path`].
+    fn snapshot_path(&self, name: &str) -> String {
+        Path::new(&File::snapshots_path(&self.path))
+            .join(format!("{}.nosqlite", name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// 🦀
+    /// Reads a file, or returns `None` if it does not exist.
+    fn read_if_exists(
+        path: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        if !Path::new(path).exists() {
+            return Ok(None);
+        }
+        handler
+            .try_or_log(fs::read(path), |e| {
+                NosqliteError::IoError(format!("cannot read '{}': {}", path, e))
+            })
+            .map(Some)
+    }
+}
+
+impl StorageBackend for FileBackend {
+    fn name(&self) -> &str {
+        &self.path
+    }
+
+    fn exists(&mut self, _handler: &mut NosqliteErrorHandler) -> Result<bool, NosqliteError> {
+        Ok(Path::new(&self.path).exists())
+    }
+
+    fn load(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Self::read_if_exists(&self.path, handler)
+    }
+
+    /// 🦀
+    /// Replaces the database file without ever leaving it partially written.
+    ///
+    /// The content is written to `<path>.tmp` in the same directory and flushed to disk, then renamed
+    /// over the file, and the directory itself is flushed so that the rename survives a crash. If any
+    /// step fails, the previous file is left untouched and the temporary file is removed.
+    fn save(
+        &mut self,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let path = &self.path;
+        let tmp_path = format!("{}.tmp", path);
+        let result = fs::File::create(&tmp_path)
+            .and_then(|mut file| {
+                file.write_all(bytes)?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&tmp_path, path))
+            .and_then(|_| Self::sync_parent_dir(path));
+        if result.is_err() && Path::new(&tmp_path).is_file() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        handler.try_or_log(result, |e| {
+            NosqliteError::IoError(format!("cannot save '{}': {}", path, e))
+        })
+    }
+
+    fn lock(
+        &mut self,
+        shared: bool,
+        timeout: Option<Duration>,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.lock = Some(DatabaseLock::acquire(&self.path, shared, timeout, handler)?);
+        Ok(())
+    }
+
+    fn append_log(
+        &mut self,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<u64, NosqliteError> {
+        let wal_path = File::wal_path(&self.path);
+        let created = !Path::new(&wal_path).exists();
+        let result = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&wal_path)
+            .and_then(|mut file| {
+                file.write_all(bytes)?;
+                file.sync_data()?;
+                if created {
+                    Self::sync_parent_dir(&wal_path)?;
+                }
+                file.metadata().map(|metadata| metadata.len())
+            });
+        handler.try_or_log(result, |e| {
+            NosqliteError::IoError(format!("cannot append to '{}': {}", wal_path, e))
+        })
+    }
+
+    fn load_log(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Self::read_if_exists(&File::wal_path(&self.path), handler)
+    }
+
+    fn clear_log(&mut self, handler: &mut NosqliteErrorHandler) -> Result<(), NosqliteError> {
+        let wal_path = File::wal_path(&self.path);
+        if !Path::new(&wal_path).exists() {
+            return Ok(());
+        }
+        handler.try_or_log(fs::remove_file(&wal_path), |e| {
+            NosqliteError::IoError(format!("cannot remove '{}': {}", wal_path, e))
+        })
+    }
+
+    fn load_segment(
+        &mut self,
+        id: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Self::read_if_exists(&self.segment_path(id), handler)
+    }
+
+    fn save_segment(
+        &mut self,
+        id: &str,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let dir = File::segments_path(&self.path);
+        let created = !Path::new(&dir).is_dir();
+        let path = self.segment_path(id);
+        let result = fs::create_dir_all(&dir)
+            .and_then(|_| {
+                if created {
+                    Self::sync_parent_dir(&dir)?;
+                }
+                fs::File::create(&path)
+            })
+            .and_then(|mut file| {
+                file.write_all(bytes)?;
+                file.sync_all()
+            })
+            .and_then(|_| Self::sync_parent_dir(&path));
+        handler.try_or_log(result, |e| {
+            NosqliteError::IoError(format!("cannot save '{}': {}", path, e))
+        })
+    }
+
+    fn remove_segment(
+        &mut self,
+        id: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let path = self.segment_path(id);
+        if !Path::new(&path).exists() {
+            return Ok(());
+        }
+        handler.try_or_log(fs::remove_file(&path), |e| {
+            NosqliteError::IoError(format!("cannot remove '{}': {}", path, e))
+        })
+    }
+
+    fn list_segments(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<String>, NosqliteError> {
+        let dir = File::segments_path(&self.path);
+        if !Path::new(&dir).is_dir() {
+            return Ok(Vec::new());
+        }
+        let entries = handler.try_or_log(fs::read_dir(&dir), |e| {
+            NosqliteError::IoError(format!("cannot read '{}': {}", dir, e))
+        })?;
+        let mut ids: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    fn load_snapshot(
+        &mut self,
+        name: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Self::read_if_exists(&self.snapshot_path(name), handler)
+    }
+
+    /// 🦀
+    /// Writes the snapshot atomically, as [`FileBackend::save`] writes the database file.
+    fn save_snapshot(
+        &mut self,
+        name: &str,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let dir = File::snapshots_path(&self.path);
+        if !Path::new(&dir).is_dir() {
+            let result = fs::create_dir_all(&dir).and_then(|_| Self::sync_parent_dir(&dir));
+            handler.try_or_log(result, |e| {
+                NosqliteError::IoError(format!("cannot create '{}': {}", dir, e))
+            })?;
+        }
+        FileBackend::new(&self.snapshot_path(name)).save(bytes, handler)
+    }
+
+    fn remove_snapshot(
+        &mut self,
+        name: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let path = self.snapshot_path(name);
+        if !Path::new(&path).exists() {
+            return Ok(());
+        }
+        handler.try_or_log(fs::remove_file(&path), |e| {
+            NosqliteError::IoError(format!("cannot remove '{}': {}", path, e))
+        })
+    }
+
+    fn list_snapshots(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<String>, NosqliteError> {
+        let dir = File::snapshots_path(&self.path);
+        if !Path::new(&dir).is_dir() {
+            return Ok(Vec::new());
+        }
+        let entries = handler.try_or_log(fs::read_dir(&dir), |e| {
+            NosqliteError::IoError(format!("cannot read '{}': {}", dir, e))
+        })?;
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter_map(|file| file.strip_suffix(".nosqlite").map(str::to_string))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+}
+
+impl MemoryBackend {
+    /// 🦀
+    /// Creates an empty in-memory storage named `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{KeySource, MemoryBackend, NosqliteOptions};
+    ///
+    /// let storage = MemoryBackend::new("users");
+    /// let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([1u8; 32]));
+    ///
+    /// let mut db = Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone())?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// drop(db);
+    ///
+    /// let mut db = Nosqlite::open_with_backend(Box::new(storage.clone()), options)?;
+    /// assert_eq!(db.list_collections().len(), 1);
+    /// assert!(storage.data().is_some());
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            storage: Arc::new(Mutex::new(MemoryStorage::default())),
+            held: None,
+        }
+    }
+
+    /// 🦀
+    /// Returns a copy of the saved data file, or `None` if nothing was saved yet.
+    pub fn data(&self) -> Option<Vec<u8>> {
+        self.storage().data.clone()
+    }
+
+    /// 🦀
+    /// Returns a copy of the write-ahead log, or `None` if there is none.
+    pub fn log(&self) -> Option<Vec<u8>> {
+        self.storage().log.clone()
+    }
+
+    /// 🦀
+    /// Returns the identifiers of the stored segments.
+    pub fn segments(&self) -> Vec<String> {
+        self.storage().segments.keys().cloned().collect()
+    }
+
+    /// Locks the shared storage, ignoring a panic of another thread holding it.
+    fn storage(&self) -> MutexGuard<'_, MemoryStorage> {
+        self.storage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Releases the lock held by this clone, if any.
+    fn release(&mut self) {
+        if let Some(shared) = self.held.take() {
+            let mut storage = self.storage();
+            match shared {
+                true => storage.readers -= 1,
+                false => storage.writer = false,
+            }
+        }
+    }
+}
+
+impl Clone for MemoryBackend {
+    /// 🦀
+    /// Returns a new handle on the same storage, which does not hold the lock of this one.
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            storage: Arc::clone(&self.storage),
+            held: None,
+        }
+    }
+}
+
+impl Drop for MemoryBackend {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn exists(&mut self, _handler: &mut NosqliteErrorHandler) -> Result<bool, NosqliteError> {
+        Ok(self.storage().data.is_some())
+    }
+
+    fn load(
+        &mut self,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Ok(self.data())
+    }
+
+    fn save(
+        &mut self,
+        bytes: &[u8],
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.storage().data = Some(bytes.to_vec());
+        Ok(())
+    }
+
+    fn lock(
+        &mut self,
+        shared: bool,
+        timeout: Option<Duration>,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.release();
+        let deadline = Instant::now() + timeout.unwrap_or_default();
+        loop {
+            let acquired = {
+                let mut storage = self.storage();
+                let free = !storage.writer && (shared || storage.readers == 0);
+                if free {
+                    match shared {
+                        true => storage.readers += 1,
+                        false => storage.writer = true,
+                    }
+                }
+                free
+            };
+            if acquired {
+                self.held = Some(shared);
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                let error = NosqliteError::DatabaseLocked(format!(
+                    "'{}' is in use by another instance",
+                    self.name
+                ));
+                handler.log_error(error.clone());
+                return Err(error);
+            }
+            thread::sleep(LOCK_RETRY_DELAY);
+        }
+    }
+
+    fn append_log(
+        &mut self,
+        bytes: &[u8],
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<u64, NosqliteError> {
+        let mut storage = self.storage();
+        let log = storage.log.get_or_insert_with(Vec::new);
+        log.extend_from_slice(bytes);
+        Ok(log.len() as u64)
+    }
+
+    fn load_log(
+        &mut self,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Ok(self.log())
+    }
+
+    fn clear_log(&mut self, _handler: &mut NosqliteErrorHandler) -> Result<(), NosqliteError> {
+        self.storage().log = None;
+        Ok(())
+    }
+
+    fn load_segment(
+        &mut self,
+        id: &str,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Ok(self.storage().segments.get(id).cloned())
+    }
+
+    fn save_segment(
+        &mut self,
+        id: &str,
+        bytes: &[u8],
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.storage()
+            .segments
+            .insert(id.to_string(), bytes.to_vec());
+        Ok(())
+    }
+
+    fn remove_segment(
+        &mut self,
+        id: &str,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.storage().segments.remove(id);
+        Ok(())
+    }
+
+    fn list_segments(
+        &mut self,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<String>, NosqliteError> {
+        Ok(self.storage().segments.keys().cloned().collect())
+    }
+
+    fn load_snapshot(
+        &mut self,
+        name: &str,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Ok(self.storage().snapshots.get(name).cloned())
+    }
+
+    fn save_snapshot(
+        &mut self,
+        name: &str,
+        bytes: &[u8],
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.storage()
+            .snapshots
+            .insert(name.to_string(), bytes.to_vec());
+        Ok(())
+    }
+
+    fn remove_snapshot(
+        &mut self,
+        name: &str,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.storage().snapshots.remove(name);
+        Ok(())
+    }
+
+    fn list_snapshots(
+        &mut self,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<String>, NosqliteError> {
+        Ok(self.storage().snapshots.keys().cloned().collect())
+    }
+}
+
+impl FaultyBackend {
+    /// 🦀
+    /// Wraps `inner`, without any fault until one is injected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{FaultyBackend, MemoryBackend, NosqliteOptions};
+    ///
+    /// let backend = FaultyBackend::new(Box::new(MemoryBackend::new("users")));
+    /// let mut db = Nosqlite::open_with_backend(Box::new(backend.clone()), NosqliteOptions::default())?;
+    ///
+    /// backend.fail_writes_after(0);
+    /// assert!(db.create_collection("users", json!({ "name": "string" })).is_err());
+    ///
+    /// backend.heal();
+    /// db.flush()?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn new(inner: Box<dyn StorageBackend>) -> Self {
+        Self {
+            name: inner.name().to_string(),
+            inner: Arc::new(Mutex::new(inner)),
+            faults: Arc::new(Mutex::new(Faults::default())),
+        }
+    }
+
+    /// 🦀
+    /// Lets `writes` more writes (saves, log appends and log removals, segment and snapshot writes
+    /// and removals) succeed, then fails every following one.
+    pub fn fail_writes_after(&self, writes: usize) {
+        self.faults().writes_before_failure = Some(writes);
+    }
+
+    /// 🦀
+    /// Makes every read (of the data file, the log, a segment or a snapshot) fail, or succeed again.
+    pub fn fail_reads(&self, fail: bool) {
+        self.faults().fail_reads = fail;
+    }
+
+    /// 🦀
+    /// Removes every injected fault.
+    pub fn heal(&self) {
+        *self.faults() = Faults::default();
+    }
+
+    /// Locks the injected faults, ignoring a panic of another thread holding them.
+    fn faults(&self) -> MutexGuard<'_, Faults> {
+        self.faults.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Locks the wrapped backend, ignoring a panic of another thread holding it.
+    fn inner(&self) -> MutexGuard<'_, Box<dyn StorageBackend>> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Fails with an injected [`NosqliteError::IoError`] if a read fault is set.
+    fn check_read(
+        &self,
+        operation: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        match self.faults().fail_reads {
+            true => Self::fault(operation, handler),
+            false => Ok(()),
+        }
+    }
+
+    /// Counts a write, and fails with an injected [`NosqliteError::IoError`] once writes must fail.
+    fn check_write(
+        &self,
+        operation: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        let mut faults = self.faults();
+        match faults.writes_before_failure.as_mut() {
+            Some(0) => {
+                drop(faults);
+                Self::fault(operation, handler)
+            }
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn fault(operation: &str, handler: &mut NosqliteErrorHandler) -> Result<(), NosqliteError> {
+        let error = NosqliteError::IoError(format!("injected fault: {} failed", operation));
+        handler.log_error(error.clone());
+        Err(error)
+    }
+}
+
+impl StorageBackend for FaultyBackend {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn exists(&mut self, handler: &mut NosqliteErrorHandler) -> Result<bool, NosqliteError> {
+        self.check_read("exists", handler)?;
+        self.inner().exists(handler)
+    }
+
+    fn load(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        self.check_read("load", handler)?;
+        self.inner().load(handler)
+    }
+
+    fn save(
+        &mut self,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.check_write("save", handler)?;
+        self.inner().save(bytes, handler)
+    }
+
+    fn lock(
+        &mut self,
+        shared: bool,
+        timeout: Option<Duration>,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.inner().lock(shared, timeout, handler)
+    }
+
+    fn append_log(
+        &mut self,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<u64, NosqliteError> {
+        self.check_write("append_log", handler)?;
+        self.inner().append_log(bytes, handler)
+    }
+
+    fn load_log(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        self.check_read("load_log", handler)?;
+        self.inner().load_log(handler)
+    }
+
+    fn clear_log(&mut self, handler: &mut NosqliteErrorHandler) -> Result<(), NosqliteError> {
+        self.check_write("clear_log", handler)?;
+        self.inner().clear_log(handler)
+    }
+
+    fn load_segment(
+        &mut self,
+        id: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        self.check_read("load_segment", handler)?;
+        self.inner().load_segment(id, handler)
+    }
+
+    fn save_segment(
+        &mut self,
+        id: &str,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.check_write("save_segment", handler)?;
+        self.inner().save_segment(id, bytes, handler)
+    }
+
+    fn remove_segment(
+        &mut self,
+        id: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.check_write("remove_segment", handler)?;
+        self.inner().remove_segment(id, handler)
+    }
+
+    fn list_segments(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<String>, NosqliteError> {
+        self.check_read("list_segments", handler)?;
+        self.inner().list_segments(handler)
+    }
+
+    fn load_snapshot(
+        &mut self,
+        name: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        self.check_read("load_snapshot", handler)?;
+        self.inner().load_snapshot(name, handler)
+    }
+
+    fn save_snapshot(
+        &mut self,
+        name: &str,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.check_write("save_snapshot", handler)?;
+        self.inner().save_snapshot(name, bytes, handler)
+    }
+
+    fn remove_snapshot(
+        &mut self,
+        name: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.check_write("remove_snapshot", handler)?;
+        self.inner().remove_snapshot(name, handler)
+    }
+
+    fn list_snapshots(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<String>, NosqliteError> {
+        self.check_read("list_snapshots", handler)?;
+        self.inner().list_snapshots(handler)
+    }
+}
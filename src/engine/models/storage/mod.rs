@@ -0,0 +1,9 @@
+//! # Storage Model
+//!
+//! This module defines where the bytes of a database are stored: the [`StorageBackend`] trait and
+//! its file, in-memory and fault-injecting implementations.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{FaultyBackend, FileBackend, MemoryBackend, StorageBackend};
@@ -0,0 +1,240 @@
+use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
+use crate::engine::models::DatabaseLock;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 🦀
+/// Stores the bytes of a database: its data file, its write-ahead log and its lock.
+///
+/// The engine encodes and encrypts the database with [`crate::engine::models::File`], then hands
+/// the resulting bytes to a backend, so that a backend never sees plaintext data (unless the
+/// database is unencrypted). Implement this trait to keep databases somewhere else than in files,
+/// and open them with [`crate::engine::Nosqlite::open_with_backend`].
+///
+/// Key files ([`crate::engine::models::KeySource::File`] and
+/// [`crate::engine::models::KeySource::Sidecar`]) always live on the filesystem, next to
+/// [`StorageBackend::name`].
+///
+/// # Required Methods
+///
+/// - `name`: A name for the database, used in error messages.
+/// - `load`: The content of the data file, or `None` if it does not exist.
+/// - `save`: Replaces the data file. It must be atomic: after a failure or a crash, `load` returns
+///   either the previous or the new content.
+///
+/// # Provided Methods
+///
+/// The other methods have defaults for a backend storing only the data file: reads find nothing,
+/// removals do nothing, and writes fail with [`NosqliteError::IoError`], so that the write-ahead
+/// log, the segmented [`crate::engine::models::Layout`] and snapshots are unavailable until they
+/// are overridden.
+///
+/// - `exists`: Whether the data file has been saved at least once. Defaults to calling `load`.
+/// - `lock`: Takes an exclusive (or `shared`) lock against other instances, held until the backend
+///   is dropped. Fails with [`NosqliteError::DatabaseLocked`] once `timeout` is over. Defaults to
+///   taking no lock.
+/// - `append_log`: Appends to the write-ahead log and returns its new size. The bytes must be
+///   durable when it returns.
+/// - `load_log`: The content of the write-ahead log, or `None` if it does not exist.
+/// - `clear_log`: Deletes the write-ahead log.
+/// - `load_segment`: The content of the segment `id` of a database written with the segmented
+///   [`crate::engine::models::Layout`], or `None` if it does not exist.
+/// - `save_segment`: Writes the segment `id`. Segments are never rewritten in place: the bytes must
+///   be durable when it returns, before the data file referencing them is saved.
+/// - `remove_segment`: Deletes the segment `id`, if it exists.
+/// - `list_segments`: The identifiers of every stored segment, referenced or not.
+/// - `load_snapshot`: The content of the named snapshot, a complete database file, or `None` if it
+///   does not exist.
+/// - `save_snapshot`: Writes the named snapshot, replacing any snapshot with that name. Like `save`,
+///   it must be atomic.
+/// - `remove_snapshot`: Deletes the named snapshot, if it exists.
+/// - `list_snapshots`: The names of the stored snapshots, sorted.
+pub trait StorageBackend: Debug + Send {
+    fn name(&self) -> &str;
+
+    fn load(
+        &mut self,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError>;
+
+    fn save(
+        &mut self,
+        bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError>;
+
+    fn exists(&mut self, handler: &mut NosqliteErrorHandler) -> Result<bool, NosqliteError> {
+        Ok(self.load(handler)?.is_some())
+    }
+
+    fn lock(
+        &mut self,
+        _shared: bool,
+        _timeout: Option<Duration>,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        Ok(())
+    }
+
+    fn append_log(
+        &mut self,
+        _bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<u64, NosqliteError> {
+        Err(unsupported(self.name(), "the write-ahead log", handler))
+    }
+
+    fn load_log(
+        &mut self,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Ok(None)
+    }
+
+    fn clear_log(&mut self, _handler: &mut NosqliteErrorHandler) -> Result<(), NosqliteError> {
+        Ok(())
+    }
+
+    fn load_segment(
+        &mut self,
+        _id: &str,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Ok(None)
+    }
+
+    fn save_segment(
+        &mut self,
+        _id: &str,
+        _bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        Err(unsupported(self.name(), "segments", handler))
+    }
+
+    fn remove_segment(
+        &mut self,
+        _id: &str,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        Ok(())
+    }
+
+    fn list_segments(
+        &mut self,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<String>, NosqliteError> {
+        Ok(Vec::new())
+    }
+
+    fn load_snapshot(
+        &mut self,
+        _name: &str,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Ok(None)
+    }
+
+    fn save_snapshot(
+        &mut self,
+        _name: &str,
+        _bytes: &[u8],
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        Err(unsupported(self.name(), "snapshots", handler))
+    }
+
+    fn remove_snapshot(
+        &mut self,
+        _name: &str,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        Ok(())
+    }
+
+    fn list_snapshots(
+        &mut self,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Vec<String>, NosqliteError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Logs and returns the error of a [`StorageBackend`] write whose default is not overridden.
+fn unsupported(name: &str, feature: &str, handler: &mut NosqliteErrorHandler) -> NosqliteError {
+    let error = NosqliteError::IoError(format!("'{}' does not support {}", name, feature));
+    handler.log_error(error.clone());
+    error
+}
+
+/// 🦀
+/// The default [`StorageBackend`]: a database file, with its write-ahead log and lock file next to it.
+///
+/// # Fields
+///
+/// - `path`: The path of the database file. Its segments, if any, are files in the directory
+///   [`crate::engine::models::File::segments_path`], and its snapshots are `<name>.nosqlite` files
+///   in the directory [`crate::engine::models::File::snapshots_path`].
+/// - `lock`: The [`DatabaseLock`] held once [`StorageBackend::lock`] succeeded.
+#[derive(Debug)]
+pub struct FileBackend {
+    pub path: String,
+    pub(crate) lock: Option<DatabaseLock>,
+}
+
+/// 🦀
+/// A [`StorageBackend`] keeping the bytes of a database in memory.
+///
+/// Clones share the same storage, like several handles on the same file: a database opened on a
+/// clone sees what was saved through another one, which makes it suited to tests of persistence
+/// and recovery. Locks are shared between clones too, but each clone holds its own.
+///
+/// # Fields
+///
+/// - `name`: The name of the database.
+/// - `storage`: The saved bytes and lock state shared by the clones.
+/// - `held`: The lock held by this clone: `Some(true)` if shared, `Some(false)` if exclusive.
+#[derive(Debug)]
+pub struct MemoryBackend {
+    pub name: String,
+    pub(crate) storage: Arc<Mutex<MemoryStorage>>,
+    pub(crate) held: Option<bool>,
+}
+
+/// The bytes and lock state shared by the clones of a [`MemoryBackend`].
+#[derive(Debug, Default)]
+pub(crate) struct MemoryStorage {
+    pub(crate) data: Option<Vec<u8>>,
+    pub(crate) log: Option<Vec<u8>>,
+    pub(crate) segments: BTreeMap<String, Vec<u8>>,
+    pub(crate) snapshots: BTreeMap<String, Vec<u8>>,
+    pub(crate) readers: usize,
+    pub(crate) writer: bool,
+}
+
+/// 🦀
+/// A [`StorageBackend`] wrapping another one, whose operations can be made to fail on demand.
+///
+/// Clones share the wrapped backend and the faults, so that a test can keep a clone to inject
+/// faults into a backend owned by a database.
+///
+/// # Fields
+///
+/// - `name`: The name of the wrapped backend.
+/// - `inner`: The wrapped backend.
+/// - `faults`: The faults to inject.
+#[derive(Debug, Clone)]
+pub struct FaultyBackend {
+    pub name: String,
+    pub(crate) inner: Arc<Mutex<Box<dyn StorageBackend>>>,
+    pub(crate) faults: Arc<Mutex<Faults>>,
+}
+
+/// The faults injected by a [`FaultyBackend`].
+#[derive(Debug, Default)]
+pub(crate) struct Faults {
+    pub(crate) writes_before_failure: Option<usize>,
+    pub(crate) fail_reads: bool,
+}
@@ -0,0 +1,32 @@
+use crate::engine::models::transaction::model::Transaction;
+use crate::engine::models::{Database, WalOperation};
+
+impl Transaction {
+    /// 🦀
+    /// Starts a transaction from the current state of a database.
+    ///
+    /// # Parameters
+    ///
+    /// - `snapshot`: A copy of the [`Database`] to restore on rollback.
+    pub fn new(snapshot: Database) -> Self {
+        Transaction {
+            snapshot,
+            operations: Vec::new(),
+            requires_full_save: false,
+        }
+    }
+
+    /// 🦀
+    /// Stages a mutation made during the transaction.
+    ///
+    /// # Parameters
+    ///
+    /// - `operation`: The [`WalOperation`] describing the mutation, or `None` if it cannot be
+    ///   described, which makes the commit save the whole database.
+    pub fn stage(&mut self, operation: Option<WalOperation>) {
+        match operation {
+            Some(operation) => self.operations.push(operation),
+            None => self.requires_full_save = true,
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! # Transaction Model
+//!
+//! This module defines the state kept while a transaction is open on a database.
+
+pub mod implem;
+pub mod model;
+
+pub use model::Transaction;
@@ -0,0 +1,21 @@
+use crate::engine::models::{Database, WalOperation};
+
+/// 🦀
+/// The state of an open transaction, see [`crate::engine::Nosqlite::begin`].
+///
+/// Mutations made during the transaction are applied to the in-memory database right away, but only
+/// persisted when the transaction is committed.
+///
+/// # Fields
+///
+/// - `snapshot`: The [`Database`] as it was when the transaction began, restored on rollback.
+/// - `operations`: The [`WalOperation`]s staged so far, written as a single write-ahead log record
+///   on commit.
+/// - `requires_full_save`: Whether a staged mutation could not be described as a [`WalOperation`],
+///   in which case the commit saves the whole database.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub snapshot: Database,
+    pub operations: Vec<WalOperation>,
+    pub requires_full_save: bool,
+}
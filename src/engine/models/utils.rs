@@ -1,4 +1,6 @@
+use crate::engine::models::vector::implem::{as_vector, vector_dimension};
 use crate::engine::models::Document;
+use crate::engine::models::GeoPoint;
 use serde_json::Value;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -159,6 +161,8 @@ pub fn validate_against_structure(
 /// - `"boolean"` → [`Value::is_boolean()`]
 /// - `"array"` → [`Value::is_array()`]
 /// - `"object"` → [`Value::is_object()`]
+/// - `"point"` → a geographic point, see [`GeoPoint::from_value`]
+/// - `"vector(N)"` → an array of exactly `N` numbers, e.g. an embedding
 ///
 /// # Returns
 ///
@@ -181,7 +185,9 @@ fn type_matches(expected: &str, val: &Value) -> bool {
         "boolean" => val.is_boolean(),
         "array" => val.is_array(),
         "object" => val.is_object(),
-        _ => false,
+        "point" => GeoPoint::from_value(val).is_some(),
+        other => vector_dimension(other)
+            .is_some_and(|dimension| as_vector(val).is_some_and(|v| v.len() == dimension)),
     }
 }
 
@@ -230,3 +236,85 @@ fn type_matches(expected: &str, val: &Value) -> bool {
 pub fn get_nested_value<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
     path.split('.').try_fold(value, |val, key| val.get(key))
 }
+
+/// 🦀
+/// Splits a text into normalized search terms.
+///
+/// The text is split on every non-alphanumeric character, each token is lowercased and
+/// reduced to its stem with [`stem`]. Empty tokens are discarded.
+///
+/// # Parameters
+///
+/// - `text`: The raw text to tokenize (e.g., the content of a string field).
+///
+/// # Returns
+///
+/// - A `Vec<String>` of terms, in the order in which they appear in the text.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::utils::tokenize;
+///
+/// assert_eq!(tokenize("Fast Databases, in Rust!"), vec!["fast", "database", "in", "rust"]);
+/// ```
+///
+/// # See Also
+///
+/// - [`stem`] — applied to every token
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| stem(&token.to_lowercase()))
+        .collect()
+}
+
+/// 🦀
+/// Reduces a lowercase English word to a simple stem by stripping common suffixes.
+///
+/// This is a deliberately small, rule-based stemmer: it handles plurals (`"-ies"`, `"-sses"`, `"-s"`)
+/// and the `"-ing"`, `"-ed"` and `"-ly"` endings, and never shortens a word below three characters.
+/// It is meant to make `"database"` and `"databases"` match, not to be linguistically exact.
+///
+/// # Parameters
+///
+/// - `word`: A lowercase word.
+///
+/// # Returns
+///
+/// - The stemmed word as a `String`.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::utils::stem;
+///
+/// assert_eq!(stem("queries"), "query");
+/// assert_eq!(stem("indexing"), "index");
+/// assert_eq!(stem("class"), "class");
+/// ```
+pub fn stem(word: &str) -> String {
+    const MIN_STEM: usize = 3;
+
+    let rules: [(&str, &str); 6] = [
+        ("sses", "ss"),
+        ("ies", "y"),
+        ("ing", ""),
+        ("ed", ""),
+        ("ly", ""),
+        ("s", ""),
+    ];
+
+    for (suffix, replacement) in rules {
+        if let Some(base) = word.strip_suffix(suffix) {
+            if suffix == "s" && (base.ends_with('s') || base.ends_with('u')) {
+                return word.to_string();
+            }
+            if base.chars().count() + replacement.chars().count() >= MIN_STEM {
+                return format!("{}{}", base, replacement);
+            }
+            return word.to_string();
+        }
+    }
+    word.to_string()
+}
@@ -0,0 +1,32 @@
+use crate::engine::models::vacuum::model::VacuumReport;
+use std::fmt::Display;
+
+impl VacuumReport {
+    /// 🦀
+    /// Returns the number of bytes freed by the compaction, `0` if the storage grew.
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+impl Display for VacuumReport {
+    /// 🦀
+    /// Formats the [`VacuumReport`] for human-readable display.
+    ///
+    /// # Example Output
+    ///
+    /// ```text
+    /// Size before: 4096 bytes
+    /// Size after: 1024 bytes
+    /// Reclaimed: 3072 bytes
+    /// Expired documents purged: 2
+    /// Indexes rebuilt: 1
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Size before: {} bytes", self.bytes_before)?;
+        writeln!(f, "Size after: {} bytes", self.bytes_after)?;
+        writeln!(f, "Reclaimed: {} bytes", self.reclaimed_bytes())?;
+        writeln!(f, "Expired documents purged: {}", self.purged_documents)?;
+        write!(f, "Indexes rebuilt: {}", self.rebuilt_indexes)
+    }
+}
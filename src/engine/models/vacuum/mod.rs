@@ -0,0 +1,8 @@
+//! # Vacuum Model
+//!
+//! This module defines the report produced when a database is compacted.
+
+pub mod implem;
+pub mod model;
+
+pub use model::VacuumReport;
@@ -0,0 +1,28 @@
+/// 🦀
+/// Represents the result of a compaction, see [`crate::engine::Nosqlite::vacuum`].
+///
+/// Sizes count everything the [`crate::engine::models::StorageBackend`] stores for the database:
+/// the data file, the write-ahead log and the segments. Snapshots are not counted.
+///
+/// # Fields
+///
+/// - `bytes_before`: The stored size before the compaction.
+/// - `bytes_after`: The stored size after the compaction.
+/// - `purged_documents`: The number of expired documents removed.
+/// - `rebuilt_indexes`: The number of indexes rebuilt from the live documents.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::VacuumReport;
+///
+/// let report = VacuumReport { bytes_before: 4096, bytes_after: 1024, ..VacuumReport::default() };
+/// assert_eq!(report.reclaimed_bytes(), 3072);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VacuumReport {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub purged_documents: usize,
+    pub rebuilt_indexes: usize,
+}
@@ -0,0 +1,379 @@
+use super::model::{HnswGraph, HnswNode, VectorMetric};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+/// Number of neighbors linked to each new node on every layer above 0 (twice as many on layer 0).
+const HNSW_M: usize = 16;
+/// Size of the candidate list explored while inserting a node.
+const HNSW_EF_CONSTRUCTION: usize = 64;
+/// Minimum size of the candidate list explored while searching.
+const HNSW_EF_SEARCH: usize = 64;
+/// Highest layer a node can be assigned to.
+const HNSW_MAX_LEVEL: usize = 16;
+
+impl VectorMetric {
+    /// 🦀
+    /// Parses a metric name (`"cosine"`, `"dot"` or `"l2"`, case-insensitive).
+    pub fn from_name(name: &str) -> Option<VectorMetric> {
+        match name.to_lowercase().as_str() {
+            "cosine" => Some(VectorMetric::Cosine),
+            "dot" => Some(VectorMetric::Dot),
+            "l2" => Some(VectorMetric::L2),
+            _ => None,
+        }
+    }
+
+    /// 🦀
+    /// Compares two vectors of the same dimension.
+    ///
+    /// # Returns
+    ///
+    /// - The cosine similarity, the dot product or the Euclidean distance, depending on the metric.
+    ///   A zero vector has a cosine similarity of 0 with every vector.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::VectorMetric;
+    ///
+    /// assert_eq!(VectorMetric::Dot.compare(&[1.0, 2.0], &[3.0, 4.0]), 11.0);
+    /// assert_eq!(VectorMetric::L2.compare(&[0.0, 0.0], &[3.0, 4.0]), 5.0);
+    /// ```
+    pub fn compare(&self, a: &[f64], b: &[f64]) -> f64 {
+        let dot = || a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+        match self {
+            VectorMetric::Cosine => {
+                let norms = (a.iter().map(|x| x * x).sum::<f64>()
+                    * b.iter().map(|y| y * y).sum::<f64>())
+                .sqrt();
+                if norms == 0.0 {
+                    0.0
+                } else {
+                    dot() / norms
+                }
+            }
+            VectorMetric::Dot => dot(),
+            VectorMetric::L2 => a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| (x - y).powi(2))
+                .sum::<f64>()
+                .sqrt(),
+        }
+    }
+
+    /// 🦀
+    /// Converts [`VectorMetric::compare`] into a distance, where lower always means closer.
+    pub fn distance(&self, a: &[f64], b: &[f64]) -> f64 {
+        match self {
+            VectorMetric::Cosine => 1.0 - self.compare(a, b),
+            VectorMetric::Dot => -self.compare(a, b),
+            VectorMetric::L2 => self.compare(a, b),
+        }
+    }
+
+    /// 🦀
+    /// Returns the field added to k-nearest results: `"_score"` for similarities, `"_distance"` for L2.
+    pub fn result_field(&self) -> &'static str {
+        match self {
+            VectorMetric::Cosine | VectorMetric::Dot => "_score",
+            VectorMetric::L2 => "_distance",
+        }
+    }
+}
+
+impl Display for VectorMetric {
+    /// 🦀
+    /// Formats the [`VectorMetric`] by its name, e.g. `cosine`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VectorMetric::Cosine => write!(f, "cosine"),
+            VectorMetric::Dot => write!(f, "dot"),
+            VectorMetric::L2 => write!(f, "l2"),
+        }
+    }
+}
+
+/// 🦀
+/// Parses the dimension of a `vector(N)` schema type.
+///
+/// # Returns
+///
+/// - `Some(N)` if `type_name` is of the form `vector(N)` (case-insensitive) with `N > 0`.
+/// - `None` otherwise.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::vector::implem::vector_dimension;
+///
+/// assert_eq!(vector_dimension("vector(384)"), Some(384));
+/// assert_eq!(vector_dimension("vector"), None);
+/// ```
+pub fn vector_dimension(type_name: &str) -> Option<usize> {
+    let type_name = type_name.trim().to_lowercase();
+    let dimension = type_name.strip_prefix("vector(")?.strip_suffix(')')?;
+    dimension
+        .trim()
+        .parse()
+        .ok()
+        .filter(|dimension| *dimension > 0)
+}
+
+/// 🦀
+/// Reads a JSON array of numbers as a vector.
+///
+/// # Returns
+///
+/// - `Some(Vec<f64>)` if every element of the array is a number.
+/// - `None` otherwise.
+pub fn as_vector(value: &Value) -> Option<Vec<f64>> {
+    value.as_array()?.iter().map(Value::as_f64).collect()
+}
+
+impl HnswGraph {
+    /// 🦀
+    /// Creates an empty graph comparing vectors with the given metric.
+    pub fn new(metric: VectorMetric) -> Self {
+        HnswGraph {
+            metric,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            entry_point: None,
+            max_level: 0,
+        }
+    }
+
+    /// 🦀
+    /// Returns the number of live (non-deleted) vectors in the graph.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// 🦀
+    /// Returns `true` if the graph holds no live vector.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// 🦀
+    /// Inserts the vector of a document into the graph.
+    ///
+    /// A vector whose dimension differs from the vectors already indexed is ignored.
+    /// Inserting a document ID that is already indexed replaces its vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `doc_id`: The ID of the document.
+    /// - `vector`: The vector to index.
+    pub fn insert(&mut self, doc_id: &str, vector: Vec<f64>) {
+        if let Some(&entry) = self.entry_point.as_ref() {
+            if self.nodes[entry].vector.len() != vector.len() {
+                return;
+            }
+        }
+        self.remove(doc_id);
+
+        let level = random_level();
+        let node = self.nodes.len();
+        self.nodes.push(HnswNode {
+            doc_id: doc_id.to_string(),
+            vector,
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.ids.insert(doc_id.to_string(), node);
+
+        let Some(entry) = self.entry_point else {
+            self.entry_point = Some(node);
+            self.max_level = level;
+            return;
+        };
+
+        let query = self.nodes[node].vector.clone();
+        let mut entry_points = vec![entry];
+        for layer in (level + 1..=self.max_level).rev() {
+            entry_points = self.closest(&query, &entry_points, layer);
+        }
+
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let found = self.search_layer(&query, &entry_points, HNSW_EF_CONSTRUCTION, layer);
+            let selected: Vec<usize> = found.iter().take(HNSW_M).map(|(_, n)| *n).collect();
+            let max_connections = if layer == 0 { 2 * HNSW_M } else { HNSW_M };
+
+            for &neighbor in &selected {
+                self.nodes[neighbor].neighbors[layer].push(node);
+                if self.nodes[neighbor].neighbors[layer].len() > max_connections {
+                    self.prune(neighbor, layer, max_connections);
+                }
+            }
+            self.nodes[node].neighbors[layer] = selected;
+            entry_points = found.into_iter().map(|(_, n)| n).collect();
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(node);
+        }
+    }
+
+    /// 🦀
+    /// Removes the vector of a document from the graph.
+    ///
+    /// The node is only marked as deleted so the graph stays connected. The graph
+    /// is rebuilt from its live vectors once deleted nodes outnumber live ones.
+    pub fn remove(&mut self, doc_id: &str) {
+        let Some(node) = self.ids.remove(doc_id) else {
+            return;
+        };
+        self.nodes[node].deleted = true;
+
+        let deleted = self.nodes.len() - self.ids.len();
+        if deleted > HNSW_EF_SEARCH && deleted > self.ids.len() {
+            self.compact();
+        }
+    }
+
+    /// 🦀
+    /// Finds the approximate `k` nearest live vectors to `query`.
+    ///
+    /// # Returns
+    ///
+    /// - Up to `k` pairs of document ID and [`VectorMetric::distance`], closest first.
+    ///   Vectors of another dimension than `query` are never returned.
+    pub fn search(&self, query: &[f64], k: usize) -> Vec<(String, f64)> {
+        let Some(entry) = self.entry_point else {
+            return Vec::new();
+        };
+        if self.nodes[entry].vector.len() != query.len() {
+            return Vec::new();
+        }
+
+        let mut entry_points = vec![entry];
+        for layer in (1..=self.max_level).rev() {
+            entry_points = self.closest(query, &entry_points, layer);
+        }
+
+        let ef = HNSW_EF_SEARCH.max(k) + (self.nodes.len() - self.ids.len());
+        self.search_layer(query, &entry_points, ef, 0)
+            .into_iter()
+            .filter(|(_, node)| !self.nodes[*node].deleted)
+            .take(k)
+            .map(|(distance, node)| (self.nodes[node].doc_id.clone(), distance))
+            .collect()
+    }
+
+    /// Rebuilds the graph from its live nodes, dropping deleted ones.
+    fn compact(&mut self) {
+        let live: Vec<HnswNode> = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .filter(|node| !node.deleted)
+            .collect();
+        *self = HnswGraph::new(self.metric);
+        for node in live {
+            self.insert(&node.doc_id, node.vector);
+        }
+    }
+
+    /// Returns the single closest node to `query` on `layer`, greedily from `entry_points`.
+    fn closest(&self, query: &[f64], entry_points: &[usize], layer: usize) -> Vec<usize> {
+        self.search_layer(query, entry_points, 1, layer)
+            .into_iter()
+            .map(|(_, node)| node)
+            .take(1)
+            .collect()
+    }
+
+    /// Explores `layer` from `entry_points` and returns the `ef` closest nodes found, closest first.
+    fn search_layer(
+        &self,
+        query: &[f64],
+        entry_points: &[usize],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<(f64, usize)> {
+        let mut visited: HashSet<usize> = entry_points.iter().copied().collect();
+        let mut candidates: Vec<(f64, usize)> = entry_points
+            .iter()
+            .map(|&node| (self.metric.distance(query, &self.nodes[node].vector), node))
+            .collect();
+        let mut results = candidates.clone();
+
+        while let Some(position) = position_of(&candidates, |a, b| a < b) {
+            let (distance, node) = candidates.swap_remove(position);
+            let worst = furthest(&results);
+            if results.len() >= ef && distance > worst {
+                break;
+            }
+
+            let Some(neighbors) = self.nodes[node].neighbors.get(layer) else {
+                continue;
+            };
+            for &neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let distance = self.metric.distance(query, &self.nodes[neighbor].vector);
+                if results.len() < ef || distance < furthest(&results) {
+                    candidates.push((distance, neighbor));
+                    results.push((distance, neighbor));
+                    if results.len() > ef {
+                        if let Some(position) = position_of(&results, |a, b| a > b) {
+                            results.swap_remove(position);
+                        }
+                    }
+                }
+            }
+        }
+
+        results.sort_by(|a, b| a.0.total_cmp(&b.0));
+        results
+    }
+
+    /// Keeps only the `max_connections` closest neighbors of `node` on `layer`.
+    fn prune(&mut self, node: usize, layer: usize, max_connections: usize) {
+        let vector = &self.nodes[node].vector;
+        let mut neighbors: Vec<(f64, usize)> = self.nodes[node].neighbors[layer]
+            .iter()
+            .map(|&neighbor| {
+                (
+                    self.metric.distance(vector, &self.nodes[neighbor].vector),
+                    neighbor,
+                )
+            })
+            .collect();
+        neighbors.sort_by(|a, b| a.0.total_cmp(&b.0));
+        neighbors.truncate(max_connections);
+        self.nodes[node].neighbors[layer] = neighbors.into_iter().map(|(_, n)| n).collect();
+    }
+}
+
+/// Draws the top layer of a new node from an exponential distribution.
+fn random_level() -> usize {
+    let uniform = 1.0 - rand::random::<f64>();
+    let level = -uniform.ln() / (HNSW_M as f64).ln();
+    (level.floor() as usize).min(HNSW_MAX_LEVEL)
+}
+
+/// Returns the position of the extreme distance in `nodes` according to `better`.
+fn position_of(nodes: &[(f64, usize)], better: impl Fn(f64, f64) -> bool) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (position, (distance, _)) in nodes.iter().enumerate() {
+        if best.is_none_or(|best| better(*distance, nodes[best].0)) {
+            best = Some(position);
+        }
+    }
+    best
+}
+
+/// Returns the largest distance in `nodes`, or infinity if it is empty.
+fn furthest(nodes: &[(f64, usize)]) -> f64 {
+    if nodes.is_empty() {
+        return f64::INFINITY;
+    }
+    nodes
+        .iter()
+        .map(|(distance, _)| *distance)
+        .fold(f64::NEG_INFINITY, f64::max)
+}
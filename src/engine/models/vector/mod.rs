@@ -0,0 +1,8 @@
+//! # Vector Model
+//!
+//! This module defines embedding vectors, similarity metrics and the HNSW graph used by vector indexes.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{HnswGraph, HnswNode, VectorMetric};
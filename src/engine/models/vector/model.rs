@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 🦀
+/// The similarity measure used to compare embedding vectors.
+///
+/// # Variants
+///
+/// - `Cosine`: Cosine similarity, from -1 to 1. Higher is closer.
+/// - `Dot`: Dot product. Higher is closer.
+/// - `L2`: Euclidean distance. Lower is closer.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::VectorMetric;
+///
+/// assert_eq!(VectorMetric::from_name("cosine"), Some(VectorMetric::Cosine));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum VectorMetric {
+    Cosine,
+    Dot,
+    L2,
+}
+
+/// 🦀
+/// A node of an [`HnswGraph`], holding one indexed vector.
+///
+/// # Fields
+///
+/// - `doc_id`: The ID of the document the vector belongs to.
+/// - `vector`: The indexed vector.
+/// - `neighbors`: The neighbor node positions, one list per layer the node belongs to.
+/// - `deleted`: Whether the document was removed; deleted nodes are still traversed but never returned.
+#[derive(Debug, Clone)]
+pub struct HnswNode {
+    pub doc_id: String,
+    pub vector: Vec<f64>,
+    pub neighbors: Vec<Vec<usize>>,
+    pub deleted: bool,
+}
+
+/// 🦀
+/// A Hierarchical Navigable Small World graph for approximate nearest neighbor search.
+///
+/// # Fields
+///
+/// - `metric`: The [`VectorMetric`] used to measure the distance between vectors.
+/// - `nodes`: Every inserted node, addressed by position.
+/// - `ids`: The node position of each live document ID.
+/// - `entry_point`: The node at which searches start, on the top layer.
+/// - `max_level`: The highest layer of the graph.
+#[derive(Debug, Clone)]
+pub struct HnswGraph {
+    pub metric: VectorMetric,
+    pub nodes: Vec<HnswNode>,
+    pub ids: HashMap<String, usize>,
+    pub entry_point: Option<usize>,
+    pub max_level: usize,
+}
@@ -0,0 +1,136 @@
+use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
+use crate::engine::models::wal::model::WalOperation;
+use crate::engine::models::{Collection, Database};
+
+impl WalOperation {
+    /// 🦀
+    /// Applies the operation to a database, as when replaying the write-ahead log.
+    ///
+    /// # Parameters
+    ///
+    /// - `db`: The [`Database`] to modify.
+    /// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for logging errors.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the operation was applied.
+    /// - `Err(NosqliteError)` if it does not fit the database, e.g. its collection does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+    /// use nosqlite_rust::engine::models::{Collection, Database, WalOperation};
+    /// use serde_json::json;
+    ///
+    /// let mut db = Database::new("temp/data60.nosqlite");
+    /// let mut handler = NosqliteErrorHandler::new("temp/data60.nosqlite".to_string());
+    ///
+    /// let collection = Collection::new("users".to_string(), json!({}));
+    /// WalOperation::CreateCollection { collection }.apply(&mut db, &mut handler)?;
+    /// assert!(db.get_collection("users").is_some());
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn apply(
+        &self,
+        db: &mut Database,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        match self {
+            WalOperation::CreateCollection { collection } => {
+                let mut collection = collection.clone();
+                collection.rebuild_indexes();
+                db.collections.retain(|c| c.name != collection.name);
+                db.collections.push(collection);
+            }
+            WalOperation::DeleteCollection { name } => {
+                db.collections.retain(|c| c.name != *name);
+            }
+            WalOperation::PutDocuments {
+                collection,
+                documents,
+            } => {
+                let collection = Self::collection(db, collection, handler)?;
+                for document in documents {
+                    collection.put_document(document.clone());
+                }
+            }
+            WalOperation::DeleteDocuments { collection, ids } => {
+                Self::collection(db, collection, handler)?.remove_documents(ids);
+            }
+            WalOperation::CreateIndex {
+                collection,
+                name,
+                kind,
+            } => {
+                Self::collection(db, collection, handler)?.add_index(
+                    name,
+                    kind.clone(),
+                    handler,
+                )?;
+            }
+            WalOperation::DropIndex { collection, name } => {
+                Self::collection(db, collection, handler)?.remove_index(name, handler)?;
+            }
+            WalOperation::PurgeExpired { now } => {
+                db.purge_expired(*now);
+            }
+            WalOperation::Transaction { operations } => {
+                for operation in operations {
+                    operation.apply(db, handler)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 🦀
+    /// Returns the names of the collections the operation reads or changes.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(names)`, possibly with duplicates for a transaction.
+    /// - `None` if the operation may touch every collection, as [`WalOperation::PurgeExpired`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::models::WalOperation;
+    ///
+    /// let operation = WalOperation::DeleteCollection { name: "users".to_string() };
+    /// assert_eq!(operation.collections(), Some(vec!["users"]));
+    /// assert_eq!(WalOperation::PurgeExpired { now: 0 }.collections(), None);
+    /// ```
+    pub fn collections(&self) -> Option<Vec<&str>> {
+        match self {
+            WalOperation::CreateCollection { collection } => Some(vec![&collection.name]),
+            WalOperation::DeleteCollection { name } => Some(vec![name]),
+            WalOperation::PutDocuments { collection, .. }
+            | WalOperation::DeleteDocuments { collection, .. }
+            | WalOperation::CreateIndex { collection, .. }
+            | WalOperation::DropIndex { collection, .. } => Some(vec![collection]),
+            WalOperation::PurgeExpired { .. } => None,
+            WalOperation::Transaction { operations } => {
+                let mut names = Vec::new();
+                for operation in operations {
+                    names.extend(operation.collections()?);
+                }
+                Some(names)
+            }
+        }
+    }
+
+    /// Looks up the collection an operation applies to.
+    fn collection<'a>(
+        db: &'a mut Database,
+        name: &str,
+        handler: &mut NosqliteErrorHandler,
+    ) -> Result<&'a mut Collection, NosqliteError> {
+        db.get_collection_mut(name).ok_or_else(|| {
+            let error =
+                NosqliteError::CollectionNotFound(format!("Collection '{}' not found", name));
+            handler.log_error(error.clone());
+            error
+        })
+    }
+}
@@ -0,0 +1,8 @@
+//! # Write-Ahead Log Model
+//!
+//! This module defines the operations recorded in the write-ahead log of a database.
+
+pub mod implem;
+pub mod model;
+
+pub use model::{WalOperation, WalRecord};
@@ -0,0 +1,79 @@
+use crate::engine::models::{Collection, Document, IndexKind};
+use serde::{Deserialize, Serialize};
+
+/// 🦀
+/// A change recorded in the write-ahead log of a database.
+///
+/// Operations record the *result* of a mutation (the documents as stored, with their generated IDs
+/// and timestamps) rather than the call that produced it, so that replaying them always rebuilds
+/// exactly the same state.
+///
+/// # Variants
+///
+/// - `CreateCollection { collection }`: A new, empty collection.
+/// - `DeleteCollection { name }`: A collection and all of its documents were removed.
+/// - `PutDocuments { collection, documents }`: Documents were inserted or updated.
+/// - `DeleteDocuments { collection, ids }`: Documents were deleted.
+/// - `CreateIndex { collection, name, kind }`: An index was created.
+/// - `DropIndex { collection, name }`: An index was dropped.
+/// - `PurgeExpired { now }`: Documents expired at `now` under a TTL index were purged.
+/// - `Transaction { operations }`: The operations of a committed transaction, replayed together.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::models::WalOperation;
+///
+/// let operation = WalOperation::DeleteCollection { name: "users".to_string() };
+/// assert_eq!(
+///     serde_json::to_string(&operation).unwrap(),
+///     r#"{"op":"delete_collection","name":"users"}"#
+/// );
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum WalOperation {
+    CreateCollection {
+        collection: Collection,
+    },
+    DeleteCollection {
+        name: String,
+    },
+    PutDocuments {
+        collection: String,
+        documents: Vec<Document>,
+    },
+    DeleteDocuments {
+        collection: String,
+        ids: Vec<String>,
+    },
+    CreateIndex {
+        collection: String,
+        name: String,
+        kind: IndexKind,
+    },
+    DropIndex {
+        collection: String,
+        name: String,
+    },
+    PurgeExpired {
+        now: u64,
+    },
+    Transaction {
+        operations: Vec<WalOperation>,
+    },
+}
+
+/// 🦀
+/// A numbered entry of the write-ahead log.
+///
+/// # Fields
+///
+/// - `sequence`: Increases by one with every record. A database snapshot stores the sequence of the
+///   last record it includes (see [`crate::engine::models::Database::wal_sequence`]).
+/// - `operation`: The recorded [`WalOperation`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WalRecord {
+    pub sequence: u64,
+    pub operation: WalOperation,
+}
@@ -1,24 +1,57 @@
 //! # NoSQLite Engine Interface
 
-use crate::engine::models::{Collection, Database, Document, File};
+use crate::engine::models::{
+    Collection, Database, Document, Durability, EncryptionKey, ExportFormat, File, FileBackend,
+    Index, IndexKind, IntegrityReport, KeySource, NosqliteOptions, PayloadStats, QueryPlan,
+    SegmentTable, StorageBackend, Transaction, VacuumReport, VectorMetric, WalOperation, WalRecord,
+};
 
 use crate::engine::services::{
     collection_service::*,
-    database_service::{load_or_create_database, save_database},
+    database_service::{
+        load_collections, load_database_from_backend, load_or_create_database, migrate_database,
+        save_database_to_backend, vacuum_database,
+    },
     document_service::*,
+    export_service::{export_documents, import_documents},
+    index_service::*,
+    recovery_service::{salvage_database, verify_database},
 };
 
+use crate::engine::models::utils::{get_nested_value, now};
 use serde_json::Value;
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
 
 use super::error::{NosqliteError, NosqliteErrorHandler};
 
-#[derive(Debug, Clone)]
+/// 🦀
+/// An open NoSQLite database: its in-memory [`Database`], the options it was opened with, and the
+/// storage and lock it writes through.
+///
+/// A `Nosqlite` is the only writer of its storage while it is open, and is deliberately not
+/// [`Clone`]: two handles sharing one lock but each holding its own documents and write-ahead log
+/// would overwrite each other's saves. Open the database again with
+/// [`Nosqlite::open_read_only`] for concurrent readers, or use [`Nosqlite::backup_to`] for a copy.
+#[derive(Debug)]
 pub struct Nosqlite {
     path: String,
     error_handler: NosqliteErrorHandler,
     db: Database,
+    options: NosqliteOptions,
+    key: EncryptionKey,
+    transaction: Option<Transaction>,
+    pending_operations: Vec<WalOperation>,
+    pending_full_save: bool,
+    unflushed_changes: usize,
+    last_flush: Instant,
+    storage: Option<Arc<Mutex<Box<dyn StorageBackend>>>>,
 }
 
+/// The path reported by databases opened with [`Nosqlite::open_in_memory`].
+const IN_MEMORY_PATH: &str = ":memory:";
+
 impl Nosqlite {
     /// 🦀
     /// Opens or initializes a new NoSQLite database from the given file path.
@@ -52,41 +85,36 @@ impl Nosqlite {
     /// - The database is backed by AES-256-GCM encryption.
     /// - Automatically initializes a [`NosqliteErrorHandler`] for this instance.
     /// - Automatically logs and persists error info to `path.replace(".nosqlite", ".log")`
+    /// - Documents expired by a TTL index are purged, and the file is rewritten if any were removed.
+    /// - A write-ahead log left next to the database is replayed, then folded into the file.
+    /// - An exclusive lock is held on a `.lock` file next to the database while the instance lives;
+    ///   opening a database another instance holds fails with [`NosqliteError::DatabaseLocked`].
     ///
     /// # See Also
     ///
     /// - [`File::load_or_create`] — underlying logic
     /// - [`NosqliteErrorHandler`] — error handling system used
+    /// - [`Nosqlite::open_with_options`] — to choose where the encryption key comes from
     pub fn open(path: &str) -> Result<Self, NosqliteError> {
-        let mut error_handler = NosqliteErrorHandler::new(path.to_string());
-        let db = File::load_or_create(path, &mut error_handler)?;
-
-        Ok(Self {
-            db,
-            error_handler,
-            path: path.to_string(),
-        })
+        Self::open_with_options(path, NosqliteOptions::default())
     }
 
     /// 🦀
-    /// Creates a new collection within the current NoSQLite database.
-    ///
-    /// The collection will use the provided structure (schema) to validate all future documents.
-    /// The structure must be a JSON object where each key maps to a type (`"string"`, `"number"`, etc.).
+    /// Opens an existing NoSQLite database read-only, with the default `db.key` file.
     ///
-    /// If the operation succeeds, the database is automatically saved to disk.
+    /// Nothing is ever written next to the database: not the file, its key, its write-ahead log or
+    /// its `.log` file. Only the `.lock` file is created if it is missing. A shared lock is held, so
+    /// several readers can open the database at once, but not while a writer holds it.
     ///
     /// # Parameters
     ///
-    /// - `name`: The unique name for the new collection.
-    /// - `structure`: A [`serde_json::Value`] representing the schema for documents.
+    /// - `path`: The path to the database file, which must exist.
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if the collection is created successfully
-    /// - `Err(NosqliteError)` if:
-    ///   - A collection with the same name already exists
-    ///   - The schema is invalid (not a JSON object)
+    /// - `Ok(Self)` — on which every mutating method fails with [`NosqliteError::ReadOnly`]
+    /// - `Err(NosqliteError::DatabaseNotFound)` — if the file does not exist
+    /// - `Err(NosqliteError)` — for any other failure, as for [`Nosqlite::open`]
     ///
     /// # Example
     ///
@@ -95,91 +123,86 @@ impl Nosqlite {
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
     ///
-    /// let mut db = Nosqlite::open("temp/data2.nosqlite")?;
-    /// db.create_collection("users", json!({ "id": "number", "name": "string" }))?;
-    /// Ok::<(), NosqliteError>(())
-    /// ```
+    /// assert!(Nosqlite::open_read_only("temp/data67.nosqlite").is_err());
     ///
-    /// # Side Effects
+    /// let mut db = Nosqlite::open("temp/data67.nosqlite")?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// drop(db);
     ///
-    /// - Automatically calls `auto_save()` to persist changes after success.
+    /// let mut db = Nosqlite::open_read_only("temp/data67.nosqlite")?;
+    /// assert_eq!(db.list_collections().len(), 1);
+    /// let result = db.insert_document("users", json!({ "name": "Alice" }));
+    /// assert!(matches!(result, Err(NosqliteError::ReadOnly(_))));
+    /// Ok::<(), NosqliteError>(())
+    /// ```
     ///
     /// # See Also
     ///
-    /// - [`delete_collection`] — for removing collections
-    /// - [`insert_document`] — to begin populating the collection
-    pub fn create_collection(&mut self, name: &str, structure: Value) -> Result<(), NosqliteError> {
-        let result = create_collection(&mut self.db, name, structure, &mut self.error_handler);
-        if result.is_ok() {
-            self.auto_save();
-        }
-        result
+    /// - [`NosqliteOptions::with_read_only`] — to open read-only with other options
+    pub fn open_read_only(path: &str) -> Result<Self, NosqliteError> {
+        Self::open_with_options(path, NosqliteOptions::default().with_read_only(true))
     }
 
     /// 🦀
-    /// Deletes a collection from the current NoSQLite database.
-    ///
-    /// This operation removes the collection and all of its documents from memory.
-    /// If the specified collection does not exist, an error is logged and returned.
+    /// Opens or initializes a NoSQLite database with explicit [`NosqliteOptions`].
     ///
-    /// On successful deletion, the database is automatically saved to disk.
+    /// [`Nosqlite::open`] always uses the `db.key` file of the current working directory. This
+    /// method lets the key come from an explicit file, raw bytes, an environment variable, or a
+    /// key file stored next to the database (see [`KeySource`]).
     ///
     /// # Parameters
     ///
-    /// - `name`: The name of the collection to delete.
+    /// - `path`: The path to the encrypted database file (e.g. `"data.nosqlite"`).
+    /// - `options`: The [`NosqliteOptions`] to open the database with. They are kept for every later save.
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if the collection was found and deleted
-    /// - `Err(NosqliteError::CollectionNotFound)` if no matching collection exists
+    /// - `Ok(Self)` — if the database is successfully loaded or created
+    /// - `Err(NosqliteError::InvalidKey)` — if the key is missing or malformed (a key file is only
+    ///   generated for a database that does not exist yet)
+    /// - `Err(NosqliteError::KeyMismatch)` — if the key does not decrypt the existing database
+    /// - `Err(NosqliteError::DatabaseNotFound)` — if the database is opened read-only and does not exist
+    /// - `Err(NosqliteError::DatabaseLocked)` — if another instance has the database open and does
+    ///   not close it within [`NosqliteOptions::lock_timeout`]
+    /// - `Err(NosqliteError)` — for any other I/O or deserialization failure
     ///
     /// # Example
     ///
     /// ```rust
-    /// use serde_json::json;
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{KeySource, NosqliteOptions};
     ///
-    /// let mut db = Nosqlite::open("temp/data3.nosqlite")?;
-    /// db.create_collection("logs", json!({ "level": "string", "message": "string" }))?;
-    /// db.delete_collection("logs")?;
+    /// let options = NosqliteOptions::default().with_key_source(KeySource::Sidecar);
+    /// let db = Nosqlite::open_with_options("temp/data55.nosqlite", options)?;
+    /// assert!(std::path::Path::new("temp/data55.key").exists());
     /// Ok::<(), NosqliteError>(())
     /// ```
-    ///
-    /// # Side Effects
-    ///
-    /// - Automatically triggers `auto_save()` if successful
-    /// - Logs errors via the embedded [`NosqliteErrorHandler`]
-    ///
-    /// # See Also
-    ///
-    /// - [`create_collection`] — for schema creation
-    /// - [`list_collections`] — to inspect what exists
-    pub fn delete_collection(&mut self, name: &str) -> Result<(), NosqliteError> {
-        let result = delete_collection(&mut self.db, name, &mut self.error_handler);
-        if result.is_ok() {
-            self.auto_save();
-        }
-        result
+    pub fn open_with_options(path: &str, options: NosqliteOptions) -> Result<Self, NosqliteError> {
+        let error_handler = match options.read_only {
+            true => NosqliteErrorHandler::in_memory(path.to_string()),
+            false => NosqliteErrorHandler::new(path.to_string()),
+        };
+        Self::open_with_handler(Box::new(FileBackend::new(path)), options, error_handler)
     }
 
     /// 🦀
-    /// Inserts a new document into a specified collection in the database.
-    ///
-    /// The document will be validated against the collection's structure (schema).
-    /// If valid, it is assigned a unique ID and timestamp metadata, then added to the collection.
+    /// Opens or initializes a database stored by a custom [`StorageBackend`].
     ///
-    /// On success, the database is immediately persisted to disk via `auto_save()`.
+    /// The backend stores the database and its write-ahead log, and provides the lock; key files
+    /// are still read from the filesystem, next to the [`StorageBackend::name`] of the backend.
+    /// Errors are not written to a `.log` file.
     ///
     /// # Parameters
     ///
-    /// - `collection`: The name of the target collection.
-    /// - `data`: A [`serde_json::Value`] representing the document content. Must be a JSON object.
+    /// - `backend`: The [`StorageBackend`] holding the database.
+    /// - `options`: The [`NosqliteOptions`] to open the database with.
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if the document is valid and inserted successfully.
-    /// - `Err(NosqliteError)` if the collection does not exist or the document fails schema validation.
+    /// - `Ok(Self)` — if the database is successfully loaded or created
+    /// - `Err(NosqliteError)` — for the same failures as [`Nosqlite::open_with_options`], or any
+    ///   failure reported by the backend
     ///
     /// # Example
     ///
@@ -187,43 +210,65 @@ impl Nosqlite {
     /// use serde_json::json;
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{KeySource, MemoryBackend, NosqliteOptions};
     ///
-    /// let mut db = Nosqlite::open("temp/data4.nosqlite")?;
-    /// db.create_collection("users", json!({ "id": "number", "name": "string" }))?;
-    /// db.insert_document("users", json!({ "id": 1, "name": "Alice" }))?;
+    /// let storage = MemoryBackend::new("orders");
+    /// let options = NosqliteOptions::default().with_key_source(KeySource::Unencrypted);
+    ///
+    /// let mut db = Nosqlite::open_with_backend(Box::new(storage.clone()), options)?;
+    /// db.create_collection("orders", json!({ "total": "number" }))?;
+    /// assert!(storage.data().is_some());
     /// Ok::<(), NosqliteError>(())
     /// ```
-    ///
-    /// # See Also
-    ///
-    /// - [`update_documents`] — for replacing an existing document
-    /// - [`delete_documents`] — for removing one by ID
-    pub fn insert_document(&mut self, collection: &str, data: Value) -> Result<(), NosqliteError> {
-        let result = insert_document(&mut self.db, collection, data, &mut self.error_handler);
-        if result.is_ok() {
-            self.auto_save();
+    pub fn open_with_backend(
+        backend: Box<dyn StorageBackend>,
+        options: NosqliteOptions,
+    ) -> Result<Self, NosqliteError> {
+        let error_handler = NosqliteErrorHandler::in_memory(backend.name().to_string());
+        Self::open_with_handler(backend, options, error_handler)
+    }
+
+    /// Locks `backend`, resolves the key and loads the database, logging failures to `error_handler`.
+    fn open_with_handler(
+        mut backend: Box<dyn StorageBackend>,
+        options: NosqliteOptions,
+        mut error_handler: NosqliteErrorHandler,
+    ) -> Result<Self, NosqliteError> {
+        let name = backend.name().to_string();
+        if options.read_only && !backend.exists(&mut error_handler)? {
+            let error = NosqliteError::DatabaseNotFound(name);
+            error_handler.log_error(error.clone());
+            return Err(error);
         }
-        result
+        backend.lock(options.read_only, options.lock_timeout, &mut error_handler)?;
+        let header = match backend.load(&mut error_handler)? {
+            Some(content) => Some(File::parse_header(&content, &mut error_handler)?),
+            None => None,
+        };
+        let key =
+            File::resolve_key_with_header(&name, header.as_ref(), &options, &mut error_handler)?;
+        let db = load_database_from_backend(
+            backend.as_mut(),
+            &key,
+            options.read_only,
+            &mut error_handler,
+        )?;
+        let has_wal = backend.load_log(&mut error_handler)?.is_some();
+
+        let mut nosqlite = Self::from_parts(&name, db, options, key, error_handler, Some(backend));
+        let purged = nosqlite.db.purge_expired(now());
+        if !nosqlite.options.read_only && (purged > 0 || has_wal) {
+            nosqlite.auto_save()?;
+        }
+        Ok(nosqlite)
     }
 
     /// 🦀
-    /// Updates all documents in a collection where a given field matches a specified value.
-    ///
-    /// This method is part of the public-facing API and delegates the logic to internal services.
-    /// It performs a full replacement of each matching document's content and triggers an automatic save
-    /// if the update is successful.
-    ///
-    /// # Parameters
-    ///
-    /// - `collection`: The name of the collection.
-    /// - `field_name`: The field to match (nested paths supported, e.g., `"profile.birthdate"`).
-    /// - `field_value`: The value to match.
-    /// - `new_data`: A complete JSON object to overwrite the content of matching documents.
-    ///
-    /// # Returns
+    /// Creates an empty database that lives in memory only.
     ///
-    /// - `Ok(())` if at least one document matched and was successfully updated.
-    /// - `Err(NosqliteError)` if the collection is missing, no document matched, or the new data is invalid.
+    /// Nothing is ever read from or written to the filesystem: no database, key, write-ahead log,
+    /// lock or `.log` file. Mutations behave as for a file-backed database, but are lost when the
+    /// instance is dropped, unless it is written to a file with [`Nosqlite::save_to`].
     ///
     /// # Example
     ///
@@ -232,70 +277,44 @@ impl Nosqlite {
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
     ///
-    /// let mut db = Nosqlite::open("temp/data5.nosqlite")?;
-    /// db.create_collection("users", json!({ "id": "number", "name": "string" }))?;
-    /// db.insert_document("users", json!({ "id": 1, "name": "Alice" }))?;
-    /// let mut db_clone = db.clone();
-    /// db.update_documents("users", "id", &json!(1), json!({ "id": 1, "name": "Updated" }))?;
+    /// let mut db = Nosqlite::open_in_memory();
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// db.insert_document("users", json!({ "name": "Alice" }))?;
+    /// assert_eq!(db.get_all_documents("users")?.len(), 1);
     /// Ok::<(), NosqliteError>(())
     /// ```
     ///
     /// # See Also
     ///
-    /// - [`update_documents_field`] — for partial updates
-    /// - [`insert_document`] — for adding new documents
-    pub fn update_documents(
-        &mut self,
-        collection: &str,
-        field_name: &str,
-        field_value: &Value,
-        new_data: Value,
-    ) -> Result<(), NosqliteError> {
-        let result = update_documents(
-            &mut self.db,
-            collection,
-            field_name,
-            field_value,
-            new_data,
-            &mut self.error_handler,
-        );
-        if result.is_ok() {
-            self.auto_save();
-        }
-        result
+    /// - [`Nosqlite::load_into_memory`] — to start from the content of a file
+    pub fn open_in_memory() -> Self {
+        Self::from_parts(
+            IN_MEMORY_PATH,
+            Database::new(IN_MEMORY_PATH),
+            NosqliteOptions::default().with_key_source(KeySource::Unencrypted),
+            EncryptionKey::unencrypted(),
+            NosqliteErrorHandler::in_memory(IN_MEMORY_PATH.to_string()),
+            None,
+        )
     }
 
     /// 🦀
-    /// Updates a specific field in all documents of a collection that match a field condition.
-    ///
-    /// This method performs a **partial update** by locating all documents where `field_name == field_value`
-    /// and setting or inserting the `target_field` with the provided `value`.
+    /// Loads a database file into memory, as by [`Nosqlite::open_in_memory`].
     ///
-    /// It does **not** revalidate the document structure against the collection schema,
-    /// and the rest of the document content remains unchanged. After a successful update,
-    /// the database is automatically saved to disk.
+    /// The file is read as by a read-only open: nothing is written next to it, and a write-ahead
+    /// log, if any, is replayed in memory. The returned instance is independent from the file;
+    /// its mutations are only written back by [`Nosqlite::save_to`].
     ///
     /// # Parameters
     ///
-    /// - `collection`: The name of the collection to modify.
-    /// - `field_name`: The field used to filter documents (supports dot notation, e.g., `"profile.id"`).
-    /// - `field_value`: The value to match against `field_name`.
-    /// - `target_field`: The field key to update or insert in the matched documents.
-    /// - `value`: The new value to assign to `target_field`.
+    /// - `path`: The path of the database file, which must exist.
+    /// - `options`: The [`NosqliteOptions`] describing where the key of the file comes from.
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if one or more documents were successfully updated.
-    /// - `Err(NosqliteError)` if:
-    ///   - The collection does not exist,
-    ///   - No document matched the filter condition,
-    ///   - Or if a document's data is not a valid JSON object.
-    ///
-    /// # Behavior
-    ///
-    /// - Matching documents are updated in place.
-    /// - `updated_at` timestamps are refreshed for each modified document.
-    /// - This method does **not** validate the result against the collection's schema.
+    /// - `Ok(Self)` — an in-memory database holding the content of the file
+    /// - `Err(NosqliteError::DatabaseNotFound)` — if the file does not exist
+    /// - `Err(NosqliteError)` — if the file is locked by a writer, or cannot be decrypted or parsed
     ///
     /// # Example
     ///
@@ -303,69 +322,57 @@ impl Nosqlite {
     /// use serde_json::json;
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{KeySource, NosqliteOptions};
     ///
-    /// let mut db = Nosqlite::open("temp/data6.nosqlite")?;
-    /// db.create_collection("users", json!({
-    ///     "_id": "string",
-    ///     "name": "string",
-    ///     "email": "string"
-    /// }))?;
+    /// let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([3u8; 32]));
+    /// let mut db = Nosqlite::open_in_memory();
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// db.save_to("temp/data68.nosqlite", options.clone())?;
     ///
-    /// db.insert_document("users", json!({ "_id": "u1", "name": "Alice", "email": "a@old.com" }))?;
-    /// db.insert_document("users", json!({ "_id": "u2", "name": "Alice", "email": "a2@old.com" }))?;
+    /// let mut copy = Nosqlite::load_into_memory("temp/data68.nosqlite", options.clone())?;
+    /// copy.delete_collection("users")?;
     ///
-    /// db.update_documents_field("users", "name", &json!("Alice"), "email", json!("a@new.com"))?;
+    /// let mut file = Nosqlite::load_into_memory("temp/data68.nosqlite", options)?;
+    /// assert_eq!(file.list_collections().len(), 1);
     /// Ok::<(), NosqliteError>(())
     /// ```
-    ///
-    /// # Notes
-    ///
-    /// - If you need to enforce strict schema validation, use [`update_documents`] instead.
-    /// - Useful for batch updates by query.
-    ///
-    /// # See Also
-    ///
-    /// - [`get_document`] — for inspecting before or after
-    /// - [`delete_documents`] — for removing by ID
-    pub fn update_documents_field(
-        &mut self,
-        collection: &str,
-        field_name: &str,
-        field_value: &Value,
-        target_field: &str,
-        value: Value,
-    ) -> Result<(), NosqliteError> {
-        let result = update_documents_field(
-            &mut self.db,
-            collection,
-            field_name,
-            field_value,
-            target_field,
-            value,
-            &mut self.error_handler,
-        );
-        if result.is_ok() {
-            self.auto_save();
-        }
-        result
+    pub fn load_into_memory(path: &str, options: NosqliteOptions) -> Result<Self, NosqliteError> {
+        let mut source = Self::open_with_options(path, options.with_read_only(true))?;
+        source.load_collections(None)?;
+        source.db.segments = SegmentTable::default();
+        Ok(Self::from_parts(
+            IN_MEMORY_PATH,
+            source.db,
+            NosqliteOptions::default().with_key_source(KeySource::Unencrypted),
+            EncryptionKey::unencrypted(),
+            NosqliteErrorHandler::in_memory(IN_MEMORY_PATH.to_string()),
+            None,
+        ))
     }
 
     /// 🦀
-    /// Deletes a document from a specified collection by its ID.
+    /// Loads into memory everything that can still be read from a damaged database file.
     ///
-    /// This operation removes the document from memory if it exists, and updates the database file
-    /// via `auto_save()` upon success. If the document or collection is missing, the error is logged.
+    /// Each segment, collection, document and write-ahead log record is read on its own, and the
+    /// damaged ones are skipped and listed in the returned [`IntegrityReport`]. As with
+    /// [`Nosqlite::load_into_memory`], nothing is written next to the file; write the result to a
+    /// new file with [`Nosqlite::save_to`].
+    ///
+    /// An encrypted data file or segment is authenticated as a whole, and is lost entirely if any of
+    /// its bytes is damaged. For an encrypted database in the single
+    /// [`Layout`](crate::engine::models::Layout), that means every collection except what the
+    /// write-ahead log holds; use the segmented layout to lose only the damaged collection.
     ///
     /// # Parameters
     ///
-    /// - `collection`: The name of the collection.
-    /// - `field_name`: The field name to search by (e.g., `"id"`).
-    /// - `field_value`: The value to match against the field (e.g., `"doc-123"`).
+    /// - `path`: The path of the database file, which must exist.
+    /// - `options`: The [`NosqliteOptions`] describing where the key of the file comes from.
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if the document was successfully removed.
-    /// - `Err(NosqliteError)` if the document or collection does not exist.
+    /// - `Ok((Self, IntegrityReport))` — an in-memory database holding what was readable, and the report
+    /// - `Err(NosqliteError::DatabaseNotFound)` — if the file does not exist
+    /// - `Err(NosqliteError)` — if the file is locked by a writer, or its key cannot be obtained
     ///
     /// # Example
     ///
@@ -373,53 +380,72 @@ impl Nosqlite {
     /// use serde_json::json;
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{KeySource, NosqliteOptions};
     ///
-    /// let mut db = Nosqlite::open("temp/data7.nosqlite")?;
-    /// db.create_collection("users", json!({ "id": "string", "name": "string" }))?;
-    /// db.insert_document("users", json!({ "id": "doc-123", "name": "Alice" }))?;
-    /// db.delete_documents("users", "id", &json!("doc-123"))?;
+    /// let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([9u8; 32]));
+    /// let mut db = Nosqlite::open_in_memory();
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// db.save_to("temp/data79.nosqlite", options.clone())?;
+    ///
+    /// let (mut recovered, report) = Nosqlite::salvage("temp/data79.nosqlite", options)?;
+    /// assert!(report.is_ok());
+    /// assert_eq!(recovered.list_collections().len(), 1);
     /// Ok::<(), NosqliteError>(())
     /// ```
-    ///
-    /// # See Also
-    ///
-    /// - [`insert_document`] — to add new documents
-    /// - [`get_document`] — for checking if a document exists before deletion
-    pub fn delete_documents(
-        &mut self,
-        collection: &str,
-        field_name: &str,
-        field_value: &Value,
-    ) -> Result<(), NosqliteError> {
-        let result = delete_documents(
-            &mut self.db,
-            collection,
-            field_name,
-            field_value,
-            &mut self.error_handler,
-        );
-        if result.is_ok() {
-            self.auto_save();
+    pub fn salvage(
+        path: &str,
+        options: NosqliteOptions,
+    ) -> Result<(Self, IntegrityReport), NosqliteError> {
+        let mut error_handler = NosqliteErrorHandler::in_memory(path.to_string());
+        let mut backend = FileBackend::new(path);
+        if !backend.exists(&mut error_handler)? {
+            let error = NosqliteError::DatabaseNotFound(path.to_string());
+            error_handler.log_error(error.clone());
+            return Err(error);
         }
-        result
+        backend.lock(true, options.lock_timeout, &mut error_handler)?;
+        let header = backend
+            .load(&mut error_handler)?
+            .and_then(|content| File::parse_header(&content, &mut error_handler).ok());
+        let key = File::resolve_key_with_header(
+            path,
+            header.as_ref(),
+            &options.with_read_only(true),
+            &mut error_handler,
+        )?;
+        let (mut db, report) = salvage_database(&mut backend, &key, &mut error_handler)?;
+        db.wal_sequence = 0;
+        let nosqlite = Self::from_parts(
+            IN_MEMORY_PATH,
+            db,
+            NosqliteOptions::default().with_key_source(KeySource::Unencrypted),
+            EncryptionKey::unencrypted(),
+            NosqliteErrorHandler::in_memory(IN_MEMORY_PATH.to_string()),
+            None,
+        );
+        Ok((nosqlite, report))
     }
 
     /// 🦀
-    /// Retrieves a document from a collection by its unique ID.
+    /// Writes the current content of the database to a new file, as a snapshot.
     ///
-    /// If the collection and document exist, this returns an immutable reference to the document.
-    /// Otherwise, the error is logged and returned.
+    /// The file is written atomically, encrypted with the key described by `options` as for a new
+    /// database (a missing key file is generated, a passphrase gets a fresh salt). It replaces any
+    /// existing file at `path`, unless another instance has that file open. The instance itself is
+    /// unchanged: an in-memory database stays in memory, and a file-backed one keeps its own file.
     ///
     /// # Parameters
     ///
-    /// - `collection`: The name of the collection.
-    /// - `field_name`: The field name to search by (e.g., `"id"`).
-    /// - `field_value`: The value to match against the field (e.g., `"abc123"`).
+    /// - `path`: The path of the file to write.
+    /// - `options`: The [`NosqliteOptions`] describing the key to encrypt the file with, and its
+    ///   [`Encoding`](crate::engine::models::Encoding), [`Compression`](crate::engine::models::Compression)
+    ///   and [`Layout`](crate::engine::models::Layout).
     ///
     /// # Returns
     ///
-    /// - `Ok(&Document)` if found
-    /// - `Err(NosqliteError)` if the collection or document is not found
+    /// - `Ok(())` once the file is durably written.
+    /// - `Err(NosqliteError::DatabaseLocked)` if another instance has the file open.
+    /// - `Err(NosqliteError)` if the key cannot be obtained or the file cannot be written.
     ///
     /// # Example
     ///
@@ -427,48 +453,58 @@ impl Nosqlite {
     /// use serde_json::json;
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::NosqliteOptions;
     ///
-    /// let mut db = Nosqlite::open("temp/data8.nosqlite")?;
-    /// db.create_collection("users", json!({ "id": "string", "name": "string" }))?;
-    /// db.insert_document("users", json!({ "id": "abc123", "name": "Alice" }))?;
-    /// let doc = db.get_document("users", "id", &json!("abc123"))?;
-    /// println!("Found doc: {}", doc);
+    /// let mut db = Nosqlite::open_in_memory();
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// db.save_to("temp/data69.nosqlite", NosqliteOptions::default())?;
+    ///
+    /// let mut db = Nosqlite::open("temp/data69.nosqlite")?;
+    /// assert_eq!(db.list_collections().len(), 1);
     /// Ok::<(), NosqliteError>(())
     /// ```
-    ///
-    /// # See Also
-    ///
-    /// - [`get_all_documents`] — for bulk access
-    /// - [`update_documents`] — for full mutation
-    pub fn get_document(
-        &mut self,
-        collection: &str,
-        field_name: &str,
-        field_value: &Value,
-    ) -> Result<&Document, NosqliteError> {
-        get_document(
-            &self.db,
-            collection,
-            field_name,
-            field_value,
-            &mut self.error_handler,
+    pub fn save_to(&mut self, path: &str, options: NosqliteOptions) -> Result<(), NosqliteError> {
+        self.ensure_no_transaction("save a snapshot")?;
+        let mut error_handler = NosqliteErrorHandler::new(path.to_string());
+        let mut backend = FileBackend::new(path);
+        backend.lock(false, options.lock_timeout, &mut error_handler)?;
+        let key = File::new_key(path, &options.key_source, options.kdf, &mut error_handler)?
+            .with_encoding(options.encoding)
+            .with_compression(options.compression);
+
+        let mut db = self.consistent_copy()?;
+        save_database_to_backend(
+            &mut backend,
+            &mut db,
+            &key,
+            options.layout,
+            &mut error_handler,
         )
     }
 
     /// 🦀
-    /// Retrieves all documents stored in a specified collection.
+    /// Writes a consistent copy of the database to a new file, while the instance stays open.
     ///
-    /// This function returns a reference to the entire in-memory vector of documents
-    /// associated with a collection. If the collection is not found, logs and returns an error.
+    /// The copy holds every change made through this instance, including those not flushed yet, and
+    /// is written atomically, so a backup can be taken at any time without closing the database.
+    /// It is written with the [`Layout`](crate::engine::models::Layout), encoding and compression
+    /// of the options the database was opened with.
+    ///
+    /// With `key_source` set to `None`, the copy is encrypted with the current key, and opens with
+    /// the same options as the database (a [`KeySource::Sidecar`] key file must be copied next to
+    /// it). Otherwise the key is obtained from `key_source` as for a new database.
     ///
     /// # Parameters
     ///
-    /// - `collection`: The name of the collection to inspect.
+    /// - `path`: The path of the file to write.
+    /// - `key_source`: Where the key of the copy comes from, or `None` to keep the current key.
     ///
     /// # Returns
     ///
-    /// - `Ok(&Vec<Document>)` if the collection exists
-    /// - `Err(NosqliteError)` if not found
+    /// - `Ok(())` once the copy is durably written.
+    /// - `Err(NosqliteError::TransactionError)` if a transaction is open.
+    /// - `Err(NosqliteError::DatabaseLocked)` if another instance has the file open.
+    /// - `Err(NosqliteError)` if the key cannot be obtained or the file cannot be written.
     ///
     /// # Example
     ///
@@ -476,122 +512,1685 @@ impl Nosqlite {
     /// use serde_json::json;
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{KeySource, NosqliteOptions};
     ///
-    /// let mut db = Nosqlite::open("temp/data9.nosqlite")?;
+    /// let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([4u8; 32]));
+    /// let mut db = Nosqlite::open_with_options("temp/data80.nosqlite", options.clone())?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// db.backup_to("temp/data80-backup.nosqlite", None)?;
+    /// db.insert_document("users", json!({ "name": "Alice" }))?;
+    ///
+    /// let mut backup = Nosqlite::open_with_options("temp/data80-backup.nosqlite", options)?;
+    /// assert!(backup.get_all_documents("users")?.is_empty());
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`Nosqlite::create_snapshot`] — to keep named copies next to the database
+    pub fn backup_to(
+        &mut self,
+        path: &str,
+        key_source: Option<KeySource>,
+    ) -> Result<(), NosqliteError> {
+        self.ensure_no_transaction("back up the database")?;
+        let mut error_handler = NosqliteErrorHandler::new(path.to_string());
+        let mut backend = FileBackend::new(path);
+        backend.lock(false, self.options.lock_timeout, &mut error_handler)?;
+        let key = match key_source {
+            Some(key_source) => {
+                File::new_key(path, &key_source, self.options.kdf, &mut error_handler)?
+                    .with_encoding(self.options.encoding)
+                    .with_compression(self.options.compression)
+            }
+            None => self.key.clone(),
+        };
+
+        let mut db = self.consistent_copy()?;
+        save_database_to_backend(
+            &mut backend,
+            &mut db,
+            &key,
+            self.options.layout,
+            &mut error_handler,
+        )
+    }
+
+    /// 🦀
+    /// Saves the current content of the database as a named snapshot, kept next to it.
+    ///
+    /// A snapshot is a complete copy of the database, encrypted with its current key and stored by
+    /// its [`StorageBackend`]: for a file, as `<name>.nosqlite` in the directory
+    /// [`File::snapshots_path`]. A snapshot with the same name is replaced.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the snapshot: letters, digits, `-`, `_` and `.`, not starting with `.`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` once the snapshot is durably written.
+    /// - `Err(NosqliteError::ReadOnly)` if the database was opened read-only.
+    /// - `Err(NosqliteError::InvalidSnapshotName)` if the name is not allowed.
+    /// - `Err(NosqliteError::TransactionError)` if a transaction is open.
+    /// - `Err(NosqliteError)` if the database lives in memory, or the snapshot cannot be written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data81.nosqlite")?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// db.create_snapshot("before-import")?;
+    /// db.insert_document("users", json!({ "name": "Alice" }))?;
+    ///
+    /// assert_eq!(db.list_snapshots()?, vec!["before-import".to_string()]);
+    /// db.restore_snapshot("before-import")?;
+    /// assert!(db.get_all_documents("users")?.is_empty());
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn create_snapshot(&mut self, name: &str) -> Result<(), NosqliteError> {
+        self.ensure_writable("create a snapshot")?;
+        self.ensure_no_transaction("create a snapshot")?;
+        self.ensure_snapshot_name(name)?;
+        let storage = self.stored_backend("create a snapshot")?;
+        let db = self.consistent_copy()?;
+        let content = File::encode_database(&db, &self.key, &mut self.error_handler)?;
+        let mut backend = lock_backend(&storage);
+        backend.save_snapshot(name, &content, &mut self.error_handler)
+    }
+
+    /// 🦀
+    /// Lists the named snapshots of the database, see [`Nosqlite::create_snapshot`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<String>)` with the names of the snapshots, sorted.
+    /// - `Err(NosqliteError)` if the database lives in memory, or the snapshots cannot be listed.
+    pub fn list_snapshots(&mut self) -> Result<Vec<String>, NosqliteError> {
+        let storage = self.stored_backend("list snapshots")?;
+        let mut backend = lock_backend(&storage);
+        backend.list_snapshots(&mut self.error_handler)
+    }
+
+    /// 🦀
+    /// Replaces the content of the database with a named snapshot, and saves it.
+    ///
+    /// The snapshot itself is kept, and can be restored again. Snapshots stay encrypted with the key
+    /// they were taken with: after [`Nosqlite::rotate_key`], the previous key must be given in
+    /// [`NosqliteOptions::fallback_key_sources`] to restore them.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the snapshot.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` once the restored database is saved.
+    /// - `Err(NosqliteError::SnapshotNotFound)` if there is no snapshot with that name.
+    /// - `Err(NosqliteError::ReadOnly)` if the database was opened read-only.
+    /// - `Err(NosqliteError::TransactionError)` if a transaction is open.
+    /// - `Err(NosqliteError)` if the snapshot cannot be read or decrypted, or the save fails.
+    pub fn restore_snapshot(&mut self, name: &str) -> Result<(), NosqliteError> {
+        self.ensure_writable("restore a snapshot")?;
+        self.ensure_no_transaction("restore a snapshot")?;
+        self.ensure_snapshot_name(name)?;
+        let storage = self.stored_backend("restore a snapshot")?;
+        let content = lock_backend(&storage).load_snapshot(name, &mut self.error_handler)?;
+        let Some(content) = content else {
+            let error = NosqliteError::SnapshotNotFound(name.to_string());
+            self.error_handler.log_error(error.clone());
+            return Err(error);
+        };
+
+        let snapshot_name = format!("{} (snapshot {})", self.path, name);
+        let (header, mut payload) =
+            File::decode_payload(&snapshot_name, &content, &self.key, &mut self.error_handler)?;
+        migrate_database(&mut payload, header.version, &mut self.error_handler)?;
+        let mut restored = File::database_from_value(payload, &mut self.error_handler)?;
+        restored.wal_sequence = self.db.wal_sequence;
+        restored.segments = std::mem::take(&mut self.db.segments);
+        restored.segments.unloaded.clear();
+        restored.mark_dirty(None);
+        self.db = restored;
+        self.auto_save()
+    }
+
+    /// 🦀
+    /// Deletes a named snapshot of the database, if it exists.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the snapshot was deleted or did not exist.
+    /// - `Err(NosqliteError::ReadOnly)` if the database was opened read-only.
+    /// - `Err(NosqliteError)` if the database lives in memory, or the snapshot cannot be deleted.
+    pub fn delete_snapshot(&mut self, name: &str) -> Result<(), NosqliteError> {
+        self.ensure_writable("delete a snapshot")?;
+        self.ensure_snapshot_name(name)?;
+        let storage = self.stored_backend("delete a snapshot")?;
+        let mut backend = lock_backend(&storage);
+        backend.remove_snapshot(name, &mut self.error_handler)
+    }
+
+    /// 🦀
+    /// Protects the database with a new passphrase and re-encrypts it.
+    ///
+    /// A new key is derived from `new_passphrase` with a fresh salt and the [`Kdf`](crate::engine::models::Kdf)
+    /// of the options the database was opened with. The database is then saved with the new key,
+    /// and must be reopened with [`KeySource::Passphrase`]. This also converts a database protected by
+    /// a key file or raw key into a passphrase-protected one.
+    ///
+    /// # Parameters
+    ///
+    /// - `new_passphrase`: The new passphrase. It must not be empty.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the database was re-encrypted and saved.
+    /// - `Err(NosqliteError)` if the passphrase is empty or the save fails; the previous key is then kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{Kdf, KeySource, NosqliteOptions};
+    ///
+    /// let options = NosqliteOptions::default()
+    ///     .with_key_source(KeySource::Passphrase("old passphrase".to_string()))
+    ///     .with_kdf(Kdf::Argon2id { memory_kib: 1024, iterations: 1, parallelism: 1 });
+    /// let mut db = Nosqlite::open_with_options("temp/data57.nosqlite", options.clone())?;
+    /// db.change_passphrase("new passphrase")?;
+    /// drop(db);
+    ///
+    /// let reopened = options.with_key_source(KeySource::Passphrase("new passphrase".to_string()));
+    /// let db = Nosqlite::open_with_options("temp/data57.nosqlite", reopened)?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn change_passphrase(&mut self, new_passphrase: &str) -> Result<(), NosqliteError> {
+        self.rotate_key(KeySource::Passphrase(new_passphrase.to_string()))
+    }
+
+    /// 🦀
+    /// Re-encrypts the database with a key from a new [`KeySource`].
+    ///
+    /// The new key is obtained as for a new database: a passphrase gets a fresh salt, and a missing
+    /// key file is generated. Saves are atomic, so a failure leaves the database readable with the
+    /// previous key.
+    ///
+    /// After the rotation, the database must be opened with `new_key_source`. Old keys can still be
+    /// used to open files encrypted with them by adding them to
+    /// [`NosqliteOptions::fallback_key_sources`].
+    ///
+    /// Rotating to [`KeySource::Unencrypted`] converts the database to plain JSON, and rotating an
+    /// unencrypted database to any other source encrypts it.
+    ///
+    /// # Parameters
+    ///
+    /// - `new_key_source`: Where the new key comes from.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the database was re-encrypted and saved.
+    /// - `Err(NosqliteError::InvalidKey)` if the new key cannot be obtained or is the current key.
+    /// - `Err(NosqliteError)` if the save fails; the previous key is then kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{KeySource, NosqliteOptions};
+    ///
+    /// let old = NosqliteOptions::default().with_key_source(KeySource::Bytes([1u8; 32]));
+    /// let mut db = Nosqlite::open_with_options("temp/data58.nosqlite", old)?;
+    /// db.rotate_key(KeySource::Bytes([2u8; 32]))?;
+    /// drop(db);
+    ///
+    /// let new = NosqliteOptions::default().with_key_source(KeySource::Bytes([2u8; 32]));
+    /// let db = Nosqlite::open_with_options("temp/data58.nosqlite", new)?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn rotate_key(&mut self, new_key_source: KeySource) -> Result<(), NosqliteError> {
+        self.ensure_writable("rotate the key")?;
+        self.ensure_no_transaction("rotate the key")?;
+        if self.storage.is_none() {
+            let error = NosqliteError::InvalidKey(
+                "an in-memory database has no key; use save_to to write it encrypted".into(),
+            );
+            self.error_handler.log_error(error.clone());
+            return Err(error);
+        }
+        let key = File::new_key(
+            &self.path,
+            &new_key_source,
+            self.options.kdf,
+            &mut self.error_handler,
+        )?
+        .with_encoding(self.options.encoding)
+        .with_compression(self.options.compression);
+        if key.key == self.key.key && key.header.plaintext == self.key.header.plaintext {
+            let error =
+                NosqliteError::InvalidKey("the new key is the current key of the database".into());
+            self.error_handler.log_error(error.clone());
+            return Err(error);
+        }
+
+        self.load_collections(None)?;
+        self.db.mark_dirty(None);
+        self.db.purge_expired(now());
+        self.save_with_key(&key)?;
+        self.clear_pending();
+        self.key = key;
+        self.options.key_source = new_key_source;
+        self.options.fallback_key_sources.clear();
+        Ok(())
+    }
+
+    /// 🦀
+    /// Creates a new collection within the current NoSQLite database.
+    ///
+    /// The collection will use the provided structure (schema) to validate all future documents.
+    /// The structure must be a JSON object where each key maps to a type (`"string"`, `"number"`, etc.).
+    ///
+    /// If the operation succeeds, the database is automatically saved to disk.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The unique name for the new collection.
+    /// - `structure`: A [`serde_json::Value`] representing the schema for documents.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the collection is created successfully
+    /// - `Err(NosqliteError)` if:
+    ///   - A collection with the same name already exists
+    ///   - The schema is invalid (not a JSON object)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data2.nosqlite")?;
+    /// db.create_collection("users", json!({ "id": "number", "name": "string" }))?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # Side Effects
+    ///
+    /// - Automatically calls `auto_save()` to persist changes after success.
+    ///
+    /// # See Also
+    ///
+    /// - [`delete_collection`] — for removing collections
+    /// - [`insert_document`] — to begin populating the collection
+    pub fn create_collection(&mut self, name: &str, structure: Value) -> Result<(), NosqliteError> {
+        self.ensure_writable("create a collection")?;
+        self.load_collection(name)?;
+        create_collection(&mut self.db, name, structure, &mut self.error_handler)?;
+        self.persist(|db| {
+            db.get_collection(name)
+                .map(|collection| WalOperation::CreateCollection {
+                    collection: collection.clone(),
+                })
+        })
+    }
+
+    /// 🦀
+    /// Deletes a collection from the current NoSQLite database.
+    ///
+    /// This operation removes the collection and all of its documents from memory.
+    /// If the specified collection does not exist, an error is logged and returned.
+    ///
+    /// On successful deletion, the database is automatically saved to disk.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the collection to delete.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the collection was found and deleted
+    /// - `Err(NosqliteError::CollectionNotFound)` if no matching collection exists
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data3.nosqlite")?;
+    /// db.create_collection("logs", json!({ "level": "string", "message": "string" }))?;
+    /// db.delete_collection("logs")?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # Side Effects
+    ///
+    /// - Automatically triggers `auto_save()` if successful
+    /// - Logs errors via the embedded [`NosqliteErrorHandler`]
+    ///
+    /// # See Also
+    ///
+    /// - [`create_collection`] — for schema creation
+    /// - [`list_collections`] — to inspect what exists
+    pub fn delete_collection(&mut self, name: &str) -> Result<(), NosqliteError> {
+        self.ensure_writable("delete a collection")?;
+        self.load_collection(name)?;
+        delete_collection(&mut self.db, name, &mut self.error_handler)?;
+        self.persist(|_| {
+            Some(WalOperation::DeleteCollection {
+                name: name.to_string(),
+            })
+        })
+    }
+
+    /// 🦀
+    /// Inserts a new document into a specified collection in the database.
+    ///
+    /// The document will be validated against the collection's structure (schema).
+    /// If valid, it is assigned a unique ID and timestamp metadata, then added to the collection.
+    ///
+    /// On success, the database is immediately persisted to disk via `auto_save()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the target collection.
+    /// - `data`: A [`serde_json::Value`] representing the document content. Must be a JSON object.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the document is valid and inserted successfully.
+    /// - `Err(NosqliteError)` if the collection does not exist or the document fails schema validation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data4.nosqlite")?;
+    /// db.create_collection("users", json!({ "id": "number", "name": "string" }))?;
+    /// db.insert_document("users", json!({ "id": 1, "name": "Alice" }))?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`update_documents`] — for replacing an existing document
+    /// - [`delete_documents`] — for removing one by ID
+    pub fn insert_document(&mut self, collection: &str, data: Value) -> Result<(), NosqliteError> {
+        self.ensure_writable("insert a document")?;
+        self.load_collection(collection)?;
+        insert_document(&mut self.db, collection, data, &mut self.error_handler)?;
+        self.persist(|db| {
+            let document = db.get_collection(collection)?.documents.last()?.clone();
+            Some(WalOperation::PutDocuments {
+                collection: collection.to_string(),
+                documents: vec![document],
+            })
+        })
+    }
+
+    /// 🦀
+    /// Updates all documents in a collection where a given field matches a specified value.
+    ///
+    /// This method is part of the public-facing API and delegates the logic to internal services.
+    /// It performs a full replacement of each matching document's content and triggers an automatic save
+    /// if the update is successful.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection.
+    /// - `field_name`: The field to match (nested paths supported, e.g., `"profile.birthdate"`).
+    /// - `field_value`: The value to match.
+    /// - `new_data`: A complete JSON object to overwrite the content of matching documents.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if at least one document matched and was successfully updated.
+    /// - `Err(NosqliteError)` if the collection is missing, no document matched, or the new data is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data5.nosqlite")?;
+    /// db.create_collection("users", json!({ "id": "number", "name": "string" }))?;
+    /// db.insert_document("users", json!({ "id": 1, "name": "Alice" }))?;
+    /// db.update_documents("users", "id", &json!(1), json!({ "id": 1, "name": "Updated" }))?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`update_documents_field`] — for partial updates
+    /// - [`insert_document`] — for adding new documents
+    pub fn update_documents(
+        &mut self,
+        collection: &str,
+        field_name: &str,
+        field_value: &Value,
+        new_data: Value,
+    ) -> Result<(), NosqliteError> {
+        self.ensure_writable("update documents")?;
+        self.load_collection(collection)?;
+        let ids = self.matching_ids(collection, field_name, field_value);
+        update_documents(
+            &mut self.db,
+            collection,
+            field_name,
+            field_value,
+            new_data,
+            &mut self.error_handler,
+        )?;
+        self.persist(|db| Self::put_documents(db, collection, &ids))
+    }
+
+    /// 🦀
+    /// Updates a specific field in all documents of a collection that match a field condition.
+    ///
+    /// This method performs a **partial update** by locating all documents where `field_name == field_value`
+    /// and setting or inserting the `target_field` with the provided `value`.
+    ///
+    /// It does **not** revalidate the document structure against the collection schema,
+    /// and the rest of the document content remains unchanged. After a successful update,
+    /// the database is automatically saved to disk.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection to modify.
+    /// - `field_name`: The field used to filter documents (supports dot notation, e.g., `"profile.id"`).
+    /// - `field_value`: The value to match against `field_name`.
+    /// - `target_field`: The field key to update or insert in the matched documents.
+    /// - `value`: The new value to assign to `target_field`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if one or more documents were successfully updated.
+    /// - `Err(NosqliteError)` if:
+    ///   - The collection does not exist,
+    ///   - No document matched the filter condition,
+    ///   - Or if a document's data is not a valid JSON object.
+    ///
+    /// # Behavior
+    ///
+    /// - Matching documents are updated in place.
+    /// - `updated_at` timestamps are refreshed for each modified document.
+    /// - This method does **not** validate the result against the collection's schema.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data6.nosqlite")?;
+    /// db.create_collection("users", json!({
+    ///     "_id": "string",
+    ///     "name": "string",
+    ///     "email": "string"
+    /// }))?;
+    ///
+    /// db.insert_document("users", json!({ "_id": "u1", "name": "Alice", "email": "a@old.com" }))?;
+    /// db.insert_document("users", json!({ "_id": "u2", "name": "Alice", "email": "a2@old.com" }))?;
+    ///
+    /// db.update_documents_field("users", "name", &json!("Alice"), "email", json!("a@new.com"))?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - If you need to enforce strict schema validation, use [`update_documents`] instead.
+    /// - Useful for batch updates by query.
+    ///
+    /// # See Also
+    ///
+    /// - [`get_document`] — for inspecting before or after
+    /// - [`delete_documents`] — for removing by ID
+    pub fn update_documents_field(
+        &mut self,
+        collection: &str,
+        field_name: &str,
+        field_value: &Value,
+        target_field: &str,
+        value: Value,
+    ) -> Result<(), NosqliteError> {
+        self.ensure_writable("update documents")?;
+        self.load_collection(collection)?;
+        let ids = self.matching_ids(collection, field_name, field_value);
+        update_documents_field(
+            &mut self.db,
+            collection,
+            field_name,
+            field_value,
+            target_field,
+            value,
+            &mut self.error_handler,
+        )?;
+        self.persist(|db| Self::put_documents(db, collection, &ids))
+    }
+
+    /// 🦀
+    /// Deletes a document from a specified collection by its ID.
+    ///
+    /// This operation removes the document from memory if it exists, and updates the database file
+    /// via `auto_save()` upon success. If the document or collection is missing, the error is logged.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection.
+    /// - `field_name`: The field name to search by (e.g., `"id"`).
+    /// - `field_value`: The value to match against the field (e.g., `"doc-123"`).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the document was successfully removed.
+    /// - `Err(NosqliteError)` if the document or collection does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data7.nosqlite")?;
+    /// db.create_collection("users", json!({ "id": "string", "name": "string" }))?;
+    /// db.insert_document("users", json!({ "id": "doc-123", "name": "Alice" }))?;
+    /// db.delete_documents("users", "id", &json!("doc-123"))?;
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`insert_document`] — to add new documents
+    /// - [`get_document`] — for checking if a document exists before deletion
+    pub fn delete_documents(
+        &mut self,
+        collection: &str,
+        field_name: &str,
+        field_value: &Value,
+    ) -> Result<(), NosqliteError> {
+        self.ensure_writable("delete documents")?;
+        self.load_collection(collection)?;
+        let ids = self.matching_ids(collection, field_name, field_value);
+        delete_documents(
+            &mut self.db,
+            collection,
+            field_name,
+            field_value,
+            &mut self.error_handler,
+        )?;
+        self.persist(|_| {
+            Some(WalOperation::DeleteDocuments {
+                collection: collection.to_string(),
+                ids,
+            })
+        })
+    }
+
+    /// 🦀
+    /// Retrieves a document from a collection by its unique ID.
+    ///
+    /// If the collection and document exist, this returns an immutable reference to the document.
+    /// Otherwise, the error is logged and returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection.
+    /// - `field_name`: The field name to search by (e.g., `"id"`).
+    /// - `field_value`: The value to match against the field (e.g., `"abc123"`).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&Document)` if found
+    /// - `Err(NosqliteError)` if the collection or document is not found
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data8.nosqlite")?;
+    /// db.create_collection("users", json!({ "id": "string", "name": "string" }))?;
+    /// db.insert_document("users", json!({ "id": "abc123", "name": "Alice" }))?;
+    /// let doc = db.get_document("users", "id", &json!("abc123"))?;
+    /// println!("Found doc: {}", doc);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`get_all_documents`] — for bulk access
+    /// - [`update_documents`] — for full mutation
+    pub fn get_document(
+        &mut self,
+        collection: &str,
+        field_name: &str,
+        field_value: &Value,
+    ) -> Result<&Document, NosqliteError> {
+        self.load_collection(collection)?;
+        get_document(
+            &self.db,
+            collection,
+            field_name,
+            field_value,
+            &mut self.error_handler,
+        )
+    }
+
+    /// 🦀
+    /// Retrieves all documents stored in a specified collection.
+    ///
+    /// This function returns a reference to the entire in-memory vector of documents
+    /// associated with a collection. If the collection is not found, logs and returns an error.
+    ///
+    /// Documents expired by a TTL index are dropped from memory first, so they are never returned,
+    /// even before a save purges them from storage.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection to inspect.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&Vec<Document>)` if the collection exists
+    /// - `Err(NosqliteError)` if not found
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data9.nosqlite")?;
     /// db.create_collection("users", json!({ "id": "string", "name": "string" }))?;
     /// db.insert_document("users", json!({ "id": "abc123", "name": "Alice" }))?;
     /// db.insert_document("users", json!({ "id": "xyz789", "name": "Bob" }))?;
     ///
-    /// for doc in db.get_all_documents("users")? {
-    ///     println!("{}", doc);
+    /// for doc in db.get_all_documents("users")? {
+    ///     println!("{}", doc);
+    /// }
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`get_documents`] — for filtering based on field values
+    pub fn get_all_documents(&mut self, collection: &str) -> Result<&Vec<Document>, NosqliteError> {
+        self.load_collection(collection)?;
+        if let Some(loaded) = self.db.get_collection_mut(collection) {
+            loaded.purge_expired(now());
+        }
+        get_all_documents(&self.db, collection, &mut self.error_handler)
+    }
+
+    /// 🦀
+    /// Retrieves documents matching the specified JSON filter and applies an optional projection.
+    ///
+    /// This function performs a multi-field filter directly in the database engine,
+    /// supporting type-aware comparisons (e.g., strings, numbers, booleans).
+    /// Projection allows you to limit the fields returned in the final result.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection to query.
+    /// - `filter`: A JSON object specifying the filter (e.g., `{ "name": "Alice" }`). Use `{}` for no filtering.
+    /// - `projection`: A JSON object specifying the fields to include (e.g., `{ "name": 1 }`). Use `{}` to return all fields.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<Value>)` with matching documents as JSON objects.
+    /// - `Err(NosqliteError)` if the collection is not found.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data10.nosqlite")?;
+    /// db.create_collection("posts", json!({ "id": "string", "author": "string" }))?;
+    /// db.insert_document("posts", json!({ "id": "post-1", "author": "alice" }))?;
+    /// db.insert_document("posts", json!({ "id": "post-2", "author": "bob" }))?;
+    ///
+    /// // Find posts authored by "alice", return only "id" field.
+    /// let results = db.get_documents(
+    ///     "posts",
+    ///     &json!({ "author": "alice" }),
+    ///     &json!({ "id": 1 })
+    /// )?;
+    ///
+    /// for post in results {
+    ///     println!("{}", post);
+    /// }
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// - Supports exact value comparisons on all JSON types.
+    /// - Use empty `{}` objects for no filtering or full document returns.
+    /// - `{ "$text": { "$search": "..." } }` runs a full-text search on the collection's text index
+    ///   (see [`create_index`]); results are ordered by relevance and carry a `_score` field.
+    /// - Comparison filters (e.g., `$gt`, `$lt`) are not yet supported.
+    ///
+    /// # See Also
+    ///
+    /// - [`get_all_documents`] — fetch all and manually filter.
+    pub fn get_documents(
+        &mut self,
+        collection: &str,
+        filter: &Value,
+        projection: &Value,
+    ) -> Result<Vec<Value>, NosqliteError> {
+        self.load_collection(collection)?;
+        // Appel de la nouvelle méthode générique dans le service
+        get_documents(
+            &self.db,
+            collection,
+            filter,
+            projection,
+            &mut self.error_handler,
+        )
+    }
+
+    /// 🦀
+    /// Finds the `k` documents whose vector field is the most similar to `vector`.
+    ///
+    /// Declare the field as `"vector(N)"` in the collection schema to have every inserted
+    /// document checked for an `N`-dimensional numeric array. Results are exact unless a vector
+    /// index with the same metric exists on the field (see [`IndexKind::Vector`]).
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection to query.
+    /// - `field`: The vector field to compare (e.g., `"embedding"`).
+    /// - `vector`: The query vector.
+    /// - `k`: The maximum number of documents to return.
+    /// - `metric`: [`VectorMetric::Cosine`], [`VectorMetric::Dot`] or [`VectorMetric::L2`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<Value>)` with up to `k` documents, most similar first, carrying their similarity
+    ///   in `_score` (cosine, dot) or their distance in `_distance` (L2).
+    /// - `Err(NosqliteError)` if the collection is not found or the vector dimension is wrong.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::VectorMetric;
+    ///
+    /// let mut db = Nosqlite::open("temp/data53.nosqlite")?;
+    /// db.create_collection("articles", json!({ "embedding": "vector(3)" }))?;
+    /// db.insert_document("articles", json!({ "title": "Rust", "embedding": [0.9, 0.1, 0.0] }))?;
+    /// db.insert_document("articles", json!({ "title": "Cooking", "embedding": [0.0, 0.2, 0.9] }))?;
+    ///
+    /// let results = db.k_nearest("articles", "embedding", &[1.0, 0.0, 0.0], 1, VectorMetric::Cosine)?;
+    /// assert_eq!(results[0]["title"], "Rust");
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn k_nearest(
+        &mut self,
+        collection: &str,
+        field: &str,
+        vector: &[f64],
+        k: usize,
+        metric: VectorMetric,
+    ) -> Result<Vec<Value>, NosqliteError> {
+        self.load_collection(collection)?;
+        k_nearest_documents(
+            &self.db,
+            collection,
+            field,
+            vector,
+            k,
+            metric,
+            &mut self.error_handler,
+        )
+    }
+
+    /// 🦀
+    /// Explains how a query would be executed against a collection.
+    ///
+    /// The query is run exactly like [`get_documents`], but instead of the matching documents
+    /// a [`QueryPlan`] is returned. It reports the chosen access path (full scan or index),
+    /// the number of documents examined versus returned, and the time taken.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection to query.
+    /// - `filter`: A JSON object specifying the filter (e.g., `{ "name": "Alice" }`). Use `{}` for no filtering.
+    /// - `projection`: A JSON object specifying the fields to include, as in [`get_documents`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(QueryPlan)` describing the execution of the query.
+    /// - `Err(NosqliteError)` if the collection is not found.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data45.nosqlite")?;
+    /// db.create_collection("orders", json!({ "status": "string" }))?;
+    /// db.insert_document("orders", json!({ "status": "paid" }))?;
+    /// db.insert_document("orders", json!({ "status": "pending" }))?;
+    ///
+    /// let plan = db.explain("orders", &json!({ "status": "paid" }), &json!({}))?;
+    /// println!("{}", plan);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`get_documents`] — runs the query and returns its results
+    pub fn explain(
+        &mut self,
+        collection: &str,
+        filter: &Value,
+        projection: &Value,
+    ) -> Result<QueryPlan, NosqliteError> {
+        self.load_collection(collection)?;
+        explain_documents(
+            &self.db,
+            collection,
+            filter,
+            projection,
+            &mut self.error_handler,
+        )
+    }
+
+    /// 🦀
+    /// Creates a secondary index on a collection.
+    ///
+    /// The index is built immediately from the existing documents and kept in sync on every
+    /// insert, update and delete. Only its definition is persisted: index data is rebuilt on [`open`].
+    ///
+    /// On success, the database is automatically saved to disk.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection to index.
+    /// - `name`: The name of the index, unique within the collection.
+    /// - `kind`: The [`IndexKind`] describing what to index (e.g., a text index over some fields, a TTL index, a geo index or a vector index).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the index was created.
+    /// - `Err(NosqliteError)` if the collection does not exist, the name is taken or the definition is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::models::IndexKind;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data49.nosqlite")?;
+    /// db.create_collection("articles", json!({ "title": "string", "body": "string" }))?;
+    /// db.insert_document("articles", json!({ "title": "Rust", "body": "Fearless concurrency" }))?;
+    /// db.create_index(
+    ///     "articles",
+    ///     "content",
+    ///     IndexKind::Text { fields: vec!["title".to_string(), "body".to_string()] },
+    /// )?;
+    ///
+    /// let hits = db.get_documents("articles", &json!({ "$text": { "$search": "concurrency" } }), &json!({}))?;
+    /// assert_eq!(hits.len(), 1);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`drop_index`] — to remove an index
+    /// - [`get_documents`] — queries that can use the index
+    pub fn create_index(
+        &mut self,
+        collection: &str,
+        name: &str,
+        kind: IndexKind,
+    ) -> Result<(), NosqliteError> {
+        self.ensure_writable("create an index")?;
+        self.load_collection(collection)?;
+        let definition = kind.clone();
+        create_index(
+            &mut self.db,
+            collection,
+            name,
+            kind,
+            &mut self.error_handler,
+        )?;
+        self.persist(|_| {
+            Some(WalOperation::CreateIndex {
+                collection: collection.to_string(),
+                name: name.to_string(),
+                kind: definition,
+            })
+        })
+    }
+
+    /// 🦀
+    /// Removes a secondary index from a collection.
+    ///
+    /// On success, the database is automatically saved to disk.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection.
+    /// - `name`: The name of the index to drop.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the index was removed.
+    /// - `Err(NosqliteError)` if the collection or the index does not exist.
+    ///
+    /// # See Also
+    ///
+    /// - [`create_index`] — to create an index
+    pub fn drop_index(&mut self, collection: &str, name: &str) -> Result<(), NosqliteError> {
+        self.ensure_writable("drop an index")?;
+        self.load_collection(collection)?;
+        drop_index(&mut self.db, collection, name, &mut self.error_handler)?;
+        self.persist(|_| {
+            Some(WalOperation::DropIndex {
+                collection: collection.to_string(),
+                name: name.to_string(),
+            })
+        })
+    }
+
+    /// 🦀
+    /// Lists the secondary indexes of a collection.
+    ///
+    /// # Parameters
+    ///
+    /// - `collection`: The name of the collection.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&Vec<Index>)` with the indexes of the collection.
+    /// - `Err(NosqliteError)` if the collection does not exist.
+    pub fn list_indexes(&mut self, collection: &str) -> Result<&Vec<Index>, NosqliteError> {
+        self.load_collection(collection)?;
+        list_indexes(&self.db, collection, &mut self.error_handler)
+    }
+
+    /// 🦀
+    /// Lists all collections currently stored in the database.
+    ///
+    /// Returns an immutable reference to all [`Collection`]s registered in the system.
+    /// Useful for introspection, UI display, or admin tools. With the segmented
+    /// [`Layout`](crate::engine::models::Layout), only collections loaded so far are listed.
+    ///
+    /// # Returns
+    ///
+    /// - A `Vec<&Collection>` representing the list of known collections.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data11.nosqlite")?;
+    /// for col in db.list_collections() {
+    ///     println!("Collection: {}", col.name);
+    /// }
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`create_collection`] — to define a new collection
+    /// - [`delete_collection`] — to remove one
+    /// - [`load_all_collections`] — to load every segment before listing
+    pub fn list_collections(&self) -> Vec<&Collection> {
+        list_collections(&self.db)
+    }
+
+    /// 🦀
+    /// Loads every collection not loaded yet, then lists all collections of the database.
+    ///
+    /// With the segmented [`Layout`](crate::engine::models::Layout), collections are read
+    /// from their segments lazily, so [`list_collections`] only shows the ones touched so
+    /// far. This method reads the remaining segments first. With the single-file layout it
+    /// behaves like [`list_collections`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<&Collection>)` with every collection of the database.
+    /// - `Err(NosqliteError)` if a collection cannot be loaded from its segment.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{Layout, NosqliteOptions};
+    ///
+    /// let options = NosqliteOptions::default().with_layout(Layout::Segmented);
+    /// let mut db = Nosqlite::open_with_options("temp/data90.nosqlite", options.clone())?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// drop(db);
+    ///
+    /// let mut db = Nosqlite::open_with_options("temp/data90.nosqlite", options)?;
+    /// assert!(db.list_collections().is_empty());
+    /// assert_eq!(db.load_all_collections()?.len(), 1);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`list_collections`] — to list only the loaded collections
+    pub fn load_all_collections(&mut self) -> Result<Vec<&Collection>, NosqliteError> {
+        self.load_collections(None)?;
+        Ok(list_collections(&self.db))
+    }
+
+    /// 🦀
+    /// Permanently removes every document expired by a TTL index, then saves the database.
+    ///
+    /// Expired documents are already hidden from reads and purged on [`open`] and on every save;
+    /// this method lets long-running processes reclaim them explicitly.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(usize)` with the number of documents removed.
+    /// - `Err(NosqliteError)` if the database could not be saved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::models::IndexKind;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data51.nosqlite")?;
+    /// db.create_collection("sessions", json!({ "user": "string" }))?;
+    /// db.create_index(
+    ///     "sessions",
+    ///     "expiry",
+    ///     IndexKind::Ttl { field: "$created_at".to_string(), expire_after_secs: 3600 },
+    /// )?;
+    /// db.insert_document("sessions", json!({ "user": "alice" }))?;
+    /// assert_eq!(db.purge_expired()?, 0);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`create_index`] — to declare a TTL index
+    pub fn purge_expired(&mut self) -> Result<usize, NosqliteError> {
+        self.ensure_writable("purge expired documents")?;
+        self.load_collections(None)?;
+        let now = now();
+        let purged = self.db.purge_expired(now);
+        if purged > 0 {
+            self.persist(|_| Some(WalOperation::PurgeExpired { now }))?;
+        }
+        Ok(purged)
+    }
+
+    /// 🦀
+    /// Writes the whole database to its file and empties the write-ahead log.
+    ///
+    /// With [`NosqliteOptions::wal`] enabled, mutations are only appended to the log, and a
+    /// checkpoint is made automatically once the log outgrows
+    /// [`NosqliteOptions::wal_checkpoint_bytes`]. Calling this method compacts the log earlier, e.g.
+    /// before a backup. Without the log, every mutation already saves the whole database.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the database was saved and the log removed.
+    /// - `Err(NosqliteError::TransactionError)` if a transaction is open.
+    /// - `Err(NosqliteError)` if the save fails; the log is then kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::models::{File, NosqliteOptions};
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let options = NosqliteOptions::default().with_wal(true);
+    /// let mut db = Nosqlite::open_with_options("temp/data61.nosqlite", options)?;
+    /// db.create_collection("users", json!({}))?;
+    /// assert!(std::path::Path::new(&File::wal_path("temp/data61.nosqlite")).exists());
+    ///
+    /// db.checkpoint()?;
+    /// assert!(!std::path::Path::new(&File::wal_path("temp/data61.nosqlite")).exists());
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn checkpoint(&mut self) -> Result<(), NosqliteError> {
+        self.ensure_writable("checkpoint")?;
+        self.ensure_no_transaction("checkpoint")?;
+        self.auto_save()
+    }
+
+    /// 🦀
+    /// Compacts the database: rewrites its storage from the live data only, and rebuilds its
+    /// indexes.
+    ///
+    /// Expired documents are purged, the write-ahead log is folded into the data file, and with the
+    /// segmented [`Layout`](crate::engine::models::Layout) every segment is rewritten and orphaned
+    /// segments left by an interrupted save are removed. An in-memory database only has its
+    /// documents purged and its indexes rebuilt.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(VacuumReport)` with the stored size before and after, and what was purged and rebuilt.
+    /// - `Err(NosqliteError::ReadOnly)` if the database was opened read-only.
+    /// - `Err(NosqliteError::TransactionError)` if a transaction is open.
+    /// - `Err(NosqliteError)` if the storage cannot be read or written; the previous files are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::models::NosqliteOptions;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let options = NosqliteOptions::default().with_wal(true);
+    /// let mut db = Nosqlite::open_with_options("temp/data83.nosqlite", options)?;
+    /// db.create_collection("users", json!({}))?;
+    /// for _ in 0..10 {
+    ///     db.insert_document("users", json!({ "name": "Alice" }))?;
     /// }
+    /// db.delete_documents("users", "name", &json!("Alice"))?;
+    ///
+    /// let report = db.vacuum()?;
+    /// assert!(report.reclaimed_bytes() > 0);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn vacuum(&mut self) -> Result<VacuumReport, NosqliteError> {
+        self.ensure_writable("vacuum")?;
+        self.ensure_no_transaction("vacuum")?;
+        let Some(storage) = self.storage.clone() else {
+            let purged_documents = self.db.purge_expired(now());
+            self.db.rebuild_indexes();
+            return Ok(VacuumReport {
+                purged_documents,
+                rebuilt_indexes: self.db.collections.iter().map(|c| c.indexes.len()).sum(),
+                ..VacuumReport::default()
+            });
+        };
+        let mut backend = lock_backend(&storage);
+        let report = vacuum_database(
+            backend.as_mut(),
+            &mut self.db,
+            &self.key,
+            self.options.layout,
+            &mut self.error_handler,
+        )?;
+        drop(backend);
+        self.clear_pending();
+        Ok(report)
+    }
+
+    /// 🦀
+    /// Writes the documents of a collection to `writer` in the given [`ExportFormat`].
+    ///
+    /// Only the content of the documents is written, without their identifiers and timestamps, and
+    /// documents expired by a TTL index are skipped. With [`ExportFormat::Csv`], nested fields are
+    /// flattened into columns named by their dot path.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the collection to export.
+    /// - `format`: The [`ExportFormat`] to write.
+    /// - `writer`: Where the documents are written, e.g. a [`std::fs::File`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(usize)` with the number of documents written.
+    /// - `Err(NosqliteError::CollectionNotFound)` if the collection does not exist.
+    /// - `Err(NosqliteError::IoError)` if the writer fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::models::ExportFormat;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data86.nosqlite")?;
+    /// db.create_collection("users", json!({}))?;
+    /// db.insert_document("users", json!({ "name": "Alice" }))?;
+    /// db.insert_document("users", json!({ "name": "Bob" }))?;
+    ///
+    /// let mut output = Vec::new();
+    /// assert_eq!(db.export_collection("users", ExportFormat::Ndjson, &mut output)?, 2);
+    /// assert_eq!(String::from_utf8(output).unwrap(), "{\"name\":\"Alice\"}\n{\"name\":\"Bob\"}\n");
     /// Ok::<(), NosqliteError>(())
     /// ```
     ///
     /// # See Also
     ///
-    /// - [`get_documents`] — for filtering based on field values
-    pub fn get_all_documents(&mut self, collection: &str) -> Result<&Vec<Document>, NosqliteError> {
-        get_all_documents(&self.db, collection, &mut self.error_handler)
+    /// - [`import_collection`] — for reading exported documents back
+    pub fn export_collection(
+        &mut self,
+        name: &str,
+        format: ExportFormat,
+        writer: impl Write,
+    ) -> Result<usize, NosqliteError> {
+        self.load_collection(name)?;
+        export_documents(&self.db, name, format, writer, &mut self.error_handler)
     }
 
     /// 🦀
-    /// Retrieves documents matching the specified JSON filter and applies an optional projection.
+    /// Reads documents in the given [`ExportFormat`] from `reader` and inserts them into a
+    /// collection.
     ///
-    /// This function performs a multi-field filter directly in the database engine,
-    /// supporting type-aware comparisons (e.g., strings, numbers, booleans).
-    /// Projection allows you to limit the fields returned in the final result.
+    /// Every record must be a JSON object matching the collection `structure`; they are all
+    /// validated before any is inserted, so a failed import leaves the collection unchanged. Each
+    /// document gets a new identifier and timestamps. With [`ExportFormat::Csv`], columns named by a
+    /// dot path are nested back, empty cells are left out, and cells are typed by the collection
+    /// `structure`.
     ///
     /// # Parameters
     ///
-    /// - `collection`: The name of the collection to query.
-    /// - `filter`: A JSON object specifying the filter (e.g., `{ "name": "Alice" }`). Use `{}` for no filtering.
-    /// - `projection`: A JSON object specifying the fields to include (e.g., `{ "name": 1 }`). Use `{}` to return all fields.
+    /// - `name`: The name of the collection to insert into.
+    /// - `format`: The [`ExportFormat`] to read.
+    /// - `reader`: Where the documents are read from, e.g. a [`std::fs::File`].
     ///
     /// # Returns
     ///
-    /// - `Ok(Vec<Value>)` with matching documents as JSON objects.
-    /// - `Err(NosqliteError)` if the collection is not found.
+    /// - `Ok(usize)` with the number of documents inserted.
+    /// - `Err(NosqliteError::ReadOnly)` if the database was opened read-only.
+    /// - `Err(NosqliteError::CollectionNotFound)` if the collection does not exist.
+    /// - `Err(NosqliteError::DeserializationError)` if the input cannot be parsed.
+    /// - `Err(NosqliteError::DocumentInvalid)` if a record does not match the collection `structure`.
     ///
     /// # Example
     ///
     /// ```rust
     /// use serde_json::json;
     /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::models::ExportFormat;
     /// use nosqlite_rust::engine::error::NosqliteError;
     ///
-    /// let mut db = Nosqlite::open("temp/data10.nosqlite")?;
-    /// db.create_collection("posts", json!({ "id": "string", "author": "string" }))?;
-    /// db.insert_document("posts", json!({ "id": "post-1", "author": "alice" }))?;
-    /// db.insert_document("posts", json!({ "id": "post-2", "author": "bob" }))?;
+    /// let mut db = Nosqlite::open("temp/data87.nosqlite")?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
     ///
-    /// // Find posts authored by "alice", return only "id" field.
-    /// let results = db.get_documents(
-    ///     "posts",
-    ///     &json!({ "author": "alice" }),
-    ///     &json!({ "id": 1 })
-    /// )?;
+    /// let input = r#"[{ "name": "Alice" }, { "name": "Bob" }]"#;
+    /// assert_eq!(db.import_collection("users", ExportFormat::Json, input.as_bytes())?, 2);
     ///
-    /// for post in results {
-    ///     println!("{}", post);
+    /// let invalid = r#"[{ "name": "Carol" }, { "age": 30 }]"#;
+    /// assert!(db.import_collection("users", ExportFormat::Json, invalid.as_bytes()).is_err());
+    /// assert_eq!(db.get_all_documents("users")?.len(), 2);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    ///
+    /// # See Also
+    ///
+    /// - [`export_collection`] — for writing documents this method can read
+    pub fn import_collection(
+        &mut self,
+        name: &str,
+        format: ExportFormat,
+        reader: impl Read,
+    ) -> Result<usize, NosqliteError> {
+        self.ensure_writable("import documents")?;
+        self.load_collection(name)?;
+        let count = import_documents(&mut self.db, name, format, reader, &mut self.error_handler)?;
+        if count == 0 {
+            return Ok(0);
+        }
+        self.persist(|db| {
+            let documents = &db.get_collection(name)?.documents;
+            Some(WalOperation::PutDocuments {
+                collection: name.to_string(),
+                documents: documents[documents.len() - count..].to_vec(),
+            })
+        })?;
+        Ok(count)
+    }
+
+    /// 🦀
+    /// Measures the payload of the saved database file, to report how well it compresses.
+    ///
+    /// Changes still in the write-ahead log or pending a flush are not measured. A database that
+    /// was never saved, or lives in memory, is measured as it would be saved now.
+    /// With the segmented [`Layout`](crate::engine::models::Layout), only the catalog stored in the
+    /// data file is measured, not the segments.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PayloadStats)` with the sizes, encoding and compression of the payload.
+    /// - `Err(NosqliteError)` if the file cannot be read, decrypted or decompressed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{Compression, NosqliteOptions};
+    ///
+    /// let options = NosqliteOptions::default().with_compression(Compression::Deflate);
+    /// let mut db = Nosqlite::open_with_options("temp/data73.nosqlite", options)?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// for _ in 0..20 {
+    ///     db.insert_document("users", json!({ "name": "Alice" }))?;
     /// }
+    ///
+    /// let stats = db.payload_stats()?;
+    /// assert!(stats.compression_ratio() > 2.0);
     /// Ok::<(), NosqliteError>(())
     /// ```
+    pub fn payload_stats(&mut self) -> Result<PayloadStats, NosqliteError> {
+        let content = match self.storage.clone() {
+            Some(storage) => lock_backend(&storage).load(&mut self.error_handler)?,
+            None => None,
+        };
+        let content = match content {
+            Some(content) => content,
+            None => File::encode_database(&self.db, &self.key, &mut self.error_handler)?,
+        };
+        File::payload_stats(&self.path, &content, &self.key, &mut self.error_handler)
+    }
+
+    /// 🦀
+    /// Checks the integrity of the stored database: every collection, document and write-ahead log
+    /// record is read back and authenticated, and every segment checksum is verified.
     ///
-    /// # Notes
+    /// Changes pending a flush are not checked. A database that was never saved, or lives in
+    /// memory, is reported as it is in memory, without issues.
     ///
-    /// - Supports exact value comparisons on all JSON types.
-    /// - Use empty `{}` objects for no filtering or full document returns.
-    /// - Advanced filters (e.g., `$gt`, `$lt`) are not yet supported.
+    /// # Returns
+    ///
+    /// - `Ok(IntegrityReport)` with what could be read, and the [`IntegrityIssue`](crate::engine::models::IntegrityIssue)s found.
+    /// - `Err(NosqliteError)` if the storage itself cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data78.nosqlite")?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// db.insert_document("users", json!({ "name": "Alice" }))?;
+    ///
+    /// let report = db.verify()?;
+    /// assert!(report.is_ok());
+    /// assert_eq!(report.documents, 1);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
     ///
     /// # See Also
     ///
-    /// - [`get_all_documents`] — fetch all and manually filter.
-    pub fn get_documents(
-        &mut self,
-        collection: &str,
-        filter: &Value,
-        projection: &Value,
-    ) -> Result<Vec<Value>, NosqliteError> {
-        // Appel de la nouvelle méthode générique dans le service
-        get_documents(
-            &self.db,
-            collection,
-            filter,
-            projection,
-            &mut self.error_handler,
-        )
+    /// - [`Nosqlite::salvage`] — to recover what is readable from a damaged database
+    pub fn verify(&mut self) -> Result<IntegrityReport, NosqliteError> {
+        if let Some(storage) = self.storage.clone() {
+            let mut backend = lock_backend(&storage);
+            let stored = backend.exists(&mut self.error_handler)?
+                || backend.load_log(&mut self.error_handler)?.is_some();
+            if stored {
+                return verify_database(backend.as_mut(), &self.key, &mut self.error_handler);
+            }
+        }
+        self.load_collections(None)?;
+        Ok(IntegrityReport {
+            collections: self.db.collections.len(),
+            documents: self.db.collections.iter().map(|c| c.documents.len()).sum(),
+            ..IntegrityReport::default()
+        })
     }
 
     /// 🦀
-    /// Lists all collections currently stored in the database.
+    /// Opens a transaction: the following mutations are applied in memory, but only persisted by
+    /// [`Nosqlite::commit`], and undone by [`Nosqlite::rollback`].
     ///
-    /// Returns an immutable reference to all [`Collection`]s registered in the system.
-    /// Useful for introspection, UI display, or admin tools.
+    /// Prefer [`Nosqlite::transaction`], which commits or rolls back automatically.
     ///
     /// # Returns
     ///
-    /// - A `Vec<&Collection>` representing the list of known collections.
+    /// - `Ok(())` if the transaction was opened.
+    /// - `Err(NosqliteError::TransactionError)` if a transaction is already open; transactions do
+    ///   not nest.
     ///
     /// # Example
     ///
     /// ```rust
+    /// use serde_json::json;
     /// use nosqlite_rust::engine::Nosqlite;
     /// use nosqlite_rust::engine::error::NosqliteError;
     ///
-    /// let mut db = Nosqlite::open("temp/data11.nosqlite")?;
-    /// for col in db.list_collections() {
-    ///     println!("Collection: {}", col.name);
-    /// }
+    /// let mut db = Nosqlite::open("temp/data62.nosqlite")?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    ///
+    /// db.begin()?;
+    /// db.insert_document("users", json!({ "name": "Alice" }))?;
+    /// db.rollback()?;
+    /// assert!(db.get_all_documents("users")?.is_empty());
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn begin(&mut self) -> Result<(), NosqliteError> {
+        self.ensure_writable("begin a transaction")?;
+        self.ensure_no_transaction("begin a transaction")?;
+        self.transaction = Some(Transaction::new(self.db.clone()));
+        Ok(())
+    }
+
+    /// 🦀
+    /// Persists every mutation made since [`Nosqlite::begin`] at once, and closes the transaction.
+    ///
+    /// With the write-ahead log enabled, the mutations are appended as a single record, so that a
+    /// crash never replays part of a transaction. Otherwise, the whole database is saved once.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the mutations were persisted.
+    /// - `Err(NosqliteError::TransactionError)` if no transaction is open.
+    /// - `Err(NosqliteError)` if persisting fails; the in-memory database is then restored to its
+    ///   state before the transaction, as by [`Nosqlite::rollback`]. Once the record is appended to
+    ///   the write-ahead log the commit succeeds, even if the checkpoint that may follow fails.
+    pub fn commit(&mut self) -> Result<(), NosqliteError> {
+        let Transaction {
+            snapshot,
+            operations,
+            requires_full_save,
+        } = self.take_transaction("commit")?;
+        if operations.is_empty() && !requires_full_save {
+            return Ok(());
+        }
+
+        let pending_operations = self.pending_operations.len();
+        let pending_full_save = self.pending_full_save;
+        let unflushed_changes = self.unflushed_changes;
+        let result = self
+            .persist(|_| (!requires_full_save).then_some(WalOperation::Transaction { operations }));
+        if result.is_err() {
+            self.pending_operations.truncate(pending_operations);
+            self.pending_full_save = pending_full_save;
+            self.unflushed_changes = unflushed_changes;
+            self.db = snapshot;
+        }
+        result
+    }
+
+    /// 🦀
+    /// Undoes every mutation made since [`Nosqlite::begin`], and closes the transaction.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the database was restored.
+    /// - `Err(NosqliteError::TransactionError)` if no transaction is open.
+    pub fn rollback(&mut self) -> Result<(), NosqliteError> {
+        let transaction = self.take_transaction("roll back")?;
+        self.db = transaction.snapshot;
+        Ok(())
+    }
+
+    /// 🦀
+    /// Runs `f` in a transaction: its mutations are persisted together if it succeeds, and undone if
+    /// it returns an error.
+    ///
+    /// # Parameters
+    ///
+    /// - `f`: The body of the transaction. It receives the database and may call any of its methods.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(T)` with the result of `f`, once its mutations are committed.
+    /// - `Err(NosqliteError)` with the error of `f`, or of the commit, after rolling back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    ///
+    /// let mut db = Nosqlite::open("temp/data63.nosqlite")?;
+    /// db.create_collection("orders", json!({ "item": "string" }))?;
+    /// db.create_collection("stock", json!({ "item": "string", "count": "number" }))?;
+    /// db.insert_document("stock", json!({ "item": "book", "count": 1 }))?;
+    ///
+    /// db.transaction(|tx| {
+    ///     tx.insert_document("orders", json!({ "item": "book" }))?;
+    ///     tx.update_documents_field("stock", "item", &json!("book"), "count", json!(0))
+    /// })?;
+    ///
+    /// let failed = db.transaction(|tx| {
+    ///     tx.insert_document("orders", json!({ "item": "pen" }))?;
+    ///     tx.update_documents_field("stock", "item", &json!("pen"), "count", json!(0))
+    /// });
+    /// assert!(failed.is_err());
+    /// assert_eq!(db.get_all_documents("orders")?.len(), 1);
+    /// Ok::<(), NosqliteError>(())
+    /// ```
+    pub fn transaction<T, F>(&mut self, f: F) -> Result<T, NosqliteError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, NosqliteError>,
+    {
+        self.begin()?;
+        match f(self) {
+            Ok(value) => self.commit().map(|_| value),
+            Err(error) => {
+                if self.transaction.is_some() {
+                    self.rollback()?;
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// 🦀
+    /// Writes every mutation not persisted yet under the [`Durability`] mode of the database.
+    ///
+    /// With the write-ahead log enabled, the pending mutations are appended to the log in a single
+    /// write. Otherwise the whole database is saved once. Nothing is written if every mutation is
+    /// already on disk.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the pending mutations are on disk.
+    /// - `Err(NosqliteError::TransactionError)` if a transaction is open; use [`Nosqlite::commit`].
+    /// - `Err(NosqliteError)` if writing fails. The mutations stay pending, and the next flush
+    ///   retries them. A failed checkpoint after the log was appended is only logged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_json::json;
+    /// use nosqlite_rust::engine::Nosqlite;
+    /// use nosqlite_rust::engine::error::NosqliteError;
+    /// use nosqlite_rust::engine::models::{Durability, NosqliteOptions};
+    ///
+    /// let options = NosqliteOptions::default().with_durability(Durability::Manual);
+    /// let mut db = Nosqlite::open_with_options("temp/data64.nosqlite", options)?;
+    /// db.create_collection("users", json!({ "name": "string" }))?;
+    /// db.insert_document("users", json!({ "name": "Alice" }))?;
+    /// db.flush()?;
+    /// drop(db);
+    ///
+    /// let mut db = Nosqlite::open("temp/data64.nosqlite")?;
+    /// assert_eq!(db.get_all_documents("users")?.len(), 1);
     /// Ok::<(), NosqliteError>(())
     /// ```
     ///
     /// # See Also
     ///
-    /// - [`create_collection`] — to define a new collection
-    /// - [`delete_collection`] — to remove one
-    pub fn list_collections(&self) -> Vec<&Collection> {
-        list_collections(&self.db)
+    /// - [`NosqliteOptions::with_durability`] — to batch writes
+    pub fn flush(&mut self) -> Result<(), NosqliteError> {
+        self.ensure_writable("flush")?;
+        self.ensure_no_transaction("flush")?;
+        if self.unflushed_changes == 0 {
+            return Ok(());
+        }
+        if !self.options.wal || self.pending_full_save || self.storage.is_none() {
+            return self.auto_save();
+        }
+
+        let records: Vec<WalRecord> = self
+            .pending_operations
+            .iter()
+            .enumerate()
+            .map(|(offset, operation)| WalRecord {
+                sequence: self.db.wal_sequence + 1 + offset as u64,
+                operation: operation.clone(),
+            })
+            .collect();
+        let content = File::encode_wal(&records, &self.key, &mut self.error_handler)?;
+        let size = match self.storage.clone() {
+            Some(storage) => {
+                lock_backend(&storage).append_log(&content, &mut self.error_handler)?
+            }
+            None => 0,
+        };
+        self.db.wal_sequence += records.len() as u64;
+        self.clear_pending();
+        if size >= self.options.wal_checkpoint_bytes {
+            self.checkpoint_log();
+        }
+        Ok(())
+    }
+
+    /// Saves the whole database once the write-ahead log outgrows
+    /// [`NosqliteOptions::wal_checkpoint_bytes`].
+    ///
+    /// The mutations are already durable in the log, so a failure is only logged: the log is kept,
+    /// replayed on the next open, and checkpointed again by a later flush.
+    fn checkpoint_log(&mut self) {
+        if let Err(error) = self.auto_save() {
+            self.error_handler.log_error(NosqliteError::IoError(format!(
+                "checkpoint failed, the write-ahead log is kept: {}",
+                error
+            )));
+        }
     }
 
     /// 🦀
@@ -600,8 +2199,10 @@ impl Nosqlite {
     /// This internal utility is automatically invoked after successful mutations
     /// (e.g., inserting, updating, or deleting documents or collections).
     ///
-    /// It delegates to [`save_database`], using the path associated with the current instance
-    /// and logs any encountered errors using the internal [`NosqliteErrorHandler`].
+    /// It purges documents expired by a TTL index, then writes the database to its [`StorageBackend`]
+    /// with the key associated with the current instance, removes the write-ahead log, and logs
+    /// any encountered errors using the internal [`NosqliteErrorHandler`]. An in-memory database
+    /// only drops its pending changes.
     ///
     /// # Returns
     ///
@@ -614,9 +2215,250 @@ impl Nosqlite {
     ///
     /// # See Also
     ///
-    /// - [`save_database`] — internal implementation
+    /// - [`File::encode_database`] — the encoding of the saved bytes
     fn auto_save(&mut self) -> Result<(), NosqliteError> {
-        save_database(&self.path, &self.db, &mut self.error_handler)?;
+        self.db.purge_expired(now());
+        let key = self.key.clone();
+        self.save_with_key(&key)?;
+        self.clear_pending();
+        Ok(())
+    }
+
+    /// Writes the database encrypted with `key` to its backend in the [`Layout`](crate::engine::models::Layout)
+    /// of the options, then removes the write-ahead log.
+    fn save_with_key(&mut self, key: &EncryptionKey) -> Result<(), NosqliteError> {
+        let Some(storage) = self.storage.clone() else {
+            return Ok(());
+        };
+        let mut backend = lock_backend(&storage);
+        save_database_to_backend(
+            backend.as_mut(),
+            &mut self.db,
+            key,
+            self.options.layout,
+            &mut self.error_handler,
+        )
+    }
+
+    /// Loads the collection `name` from its segment, if it is stored in one and not loaded yet.
+    fn load_collection(&mut self, name: &str) -> Result<(), NosqliteError> {
+        self.load_collections(Some(vec![name]))
+    }
+
+    /// Loads the collections `names` (or all, for `None`) not loaded from their segments yet.
+    fn load_collections(&mut self, names: Option<Vec<&str>>) -> Result<(), NosqliteError> {
+        if self.db.segments.unloaded.is_empty() {
+            return Ok(());
+        }
+        let Some(storage) = self.storage.clone() else {
+            return Ok(());
+        };
+        let mut backend = lock_backend(&storage);
+        load_collections(
+            backend.as_mut(),
+            &mut self.db,
+            names,
+            &self.key,
+            &mut self.error_handler,
+        )?;
+        Ok(())
+    }
+
+    /// 🦀
+    /// Persists a successful mutation, described by the [`WalOperation`] built by `operation`.
+    ///
+    /// The mutation is recorded as pending, then written by [`Nosqlite::flush`] when the
+    /// [`Durability`] mode asks for it. With the write-ahead log enabled, the operation is appended
+    /// to the log, and a checkpoint is made once the log outgrows
+    /// [`NosqliteOptions::wal_checkpoint_bytes`]. Otherwise, or if the operation cannot be
+    /// described, the whole database is saved with [`Nosqlite::auto_save`].
+    ///
+    /// With the segmented [`Layout`](crate::engine::models::Layout), the collections the operation
+    /// touches (all of them if it cannot be described) are marked to be rewritten on the next save.
+    fn persist(
+        &mut self,
+        operation: impl FnOnce(&Database) -> Option<WalOperation>,
+    ) -> Result<(), NosqliteError> {
+        let segmented = !self.options.layout.is_single();
+        let operation = match self.transaction.is_some() || self.options.wal || segmented {
+            true => operation(&self.db),
+            false => None,
+        };
+        if segmented {
+            let collections = operation.as_ref().and_then(WalOperation::collections);
+            self.db.mark_dirty(collections);
+        }
+        if let Some(transaction) = &mut self.transaction {
+            transaction.stage(operation);
+            return Ok(());
+        }
+        if self.options.wal {
+            match operation {
+                Some(operation) => self.pending_operations.push(operation),
+                None => self.pending_full_save = true,
+            }
+        }
+        self.unflushed_changes += 1;
+
+        if self
+            .options
+            .durability
+            .should_flush(self.unflushed_changes, self.last_flush.elapsed())
+        {
+            self.flush()?;
+        }
         Ok(())
     }
+
+    /// Forgets the pending mutations, once they are on disk.
+    fn clear_pending(&mut self) {
+        self.pending_operations.clear();
+        self.pending_full_save = false;
+        self.unflushed_changes = 0;
+        self.last_flush = Instant::now();
+    }
+
+    /// Assembles an instance around a loaded database, with no pending mutation.
+    fn from_parts(
+        path: &str,
+        db: Database,
+        options: NosqliteOptions,
+        key: EncryptionKey,
+        error_handler: NosqliteErrorHandler,
+        backend: Option<Box<dyn StorageBackend>>,
+    ) -> Self {
+        Self {
+            storage: backend.map(|backend| Arc::new(Mutex::new(backend))),
+            path: path.to_string(),
+            error_handler,
+            db,
+            options,
+            key,
+            transaction: None,
+            pending_operations: Vec::new(),
+            pending_full_save: false,
+            unflushed_changes: 0,
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Returns a copy of the database with every collection loaded and expired documents purged,
+    /// as written by [`Nosqlite::save_to`], [`Nosqlite::backup_to`] and snapshots.
+    fn consistent_copy(&mut self) -> Result<Database, NosqliteError> {
+        self.load_collections(None)?;
+        let mut db = self.db.clone();
+        db.purge_expired(now());
+        db.wal_sequence = 0;
+        db.segments = SegmentTable::default();
+        Ok(db)
+    }
+
+    /// Returns the backend of the database, or fails if it lives in memory only.
+    fn stored_backend(
+        &mut self,
+        action: &str,
+    ) -> Result<Arc<Mutex<Box<dyn StorageBackend>>>, NosqliteError> {
+        if let Some(storage) = self.storage.clone() {
+            return Ok(storage);
+        }
+        let error = NosqliteError::IoError(format!(
+            "cannot {}: an in-memory database has no storage; use save_to or backup_to",
+            action
+        ));
+        self.error_handler.log_error(error.clone());
+        Err(error)
+    }
+
+    /// Fails with a [`NosqliteError::InvalidSnapshotName`] unless `name` can name a snapshot file.
+    fn ensure_snapshot_name(&mut self, name: &str) -> Result<(), NosqliteError> {
+        let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.');
+        if !name.is_empty() && !name.starts_with('.') && name.chars().all(allowed) {
+            return Ok(());
+        }
+        let error = NosqliteError::InvalidSnapshotName(format!(
+            "`{}` must be made of letters, digits, '-', '_' and '.', and not start with '.'",
+            name
+        ));
+        self.error_handler.log_error(error.clone());
+        Err(error)
+    }
+
+    /// Fails with a [`NosqliteError::ReadOnly`] if the database was opened read-only.
+    fn ensure_writable(&mut self, action: &str) -> Result<(), NosqliteError> {
+        if !self.options.read_only {
+            return Ok(());
+        }
+        let error = NosqliteError::ReadOnly(format!(
+            "cannot {}: '{}' is opened read-only",
+            action, self.path
+        ));
+        self.error_handler.log_error(error.clone());
+        Err(error)
+    }
+
+    /// Fails with a [`NosqliteError::TransactionError`] if a transaction is open.
+    fn ensure_no_transaction(&mut self, action: &str) -> Result<(), NosqliteError> {
+        if self.transaction.is_none() {
+            return Ok(());
+        }
+        let error = NosqliteError::TransactionError(format!(
+            "cannot {} while a transaction is open",
+            action
+        ));
+        self.error_handler.log_error(error.clone());
+        Err(error)
+    }
+
+    /// Closes the open transaction, or fails with a [`NosqliteError::TransactionError`] if there is none.
+    fn take_transaction(&mut self, action: &str) -> Result<Transaction, NosqliteError> {
+        self.transaction.take().ok_or_else(|| {
+            let error = NosqliteError::TransactionError(format!(
+                "cannot {}: no transaction is open",
+                action
+            ));
+            self.error_handler.log_error(error.clone());
+            error
+        })
+    }
+
+    /// Returns the IDs of the documents an update or delete by `field_name == field_value` applies to.
+    fn matching_ids(&self, collection: &str, field_name: &str, field_value: &Value) -> Vec<String> {
+        self.db
+            .get_collection(collection)
+            .map(|collection| {
+                let now = now();
+                collection
+                    .documents
+                    .iter()
+                    .filter(|doc| {
+                        get_nested_value(&doc.data, field_name) == Some(field_value)
+                            && !collection.is_expired(doc, now)
+                    })
+                    .map(|doc| doc.id.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Describes the current state of the documents with the given IDs as a [`WalOperation`].
+    fn put_documents(db: &Database, collection: &str, ids: &[String]) -> Option<WalOperation> {
+        let documents = db
+            .get_collection(collection)?
+            .documents
+            .iter()
+            .filter(|doc| ids.contains(&doc.id))
+            .cloned()
+            .collect();
+        Some(WalOperation::PutDocuments {
+            collection: collection.to_string(),
+            documents,
+        })
+    }
+}
+
+/// Locks the shared backend of a database, ignoring a panic of another thread holding it.
+fn lock_backend(
+    storage: &Mutex<Box<dyn StorageBackend>>,
+) -> MutexGuard<'_, Box<dyn StorageBackend>> {
+    storage.lock().unwrap_or_else(|e| e.into_inner())
 }
@@ -1,8 +1,55 @@
 use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
 use crate::engine::models::database::model::Database;
-use crate::engine::models::file::model::File;
-use std::fs;
-use std::path::Path;
+use crate::engine::models::file::implem::FORMAT_VERSION;
+use crate::engine::models::file::model::{EncryptionKey, File};
+use crate::engine::models::options::model::{Layout, NosqliteOptions};
+use crate::engine::models::segment::model::{SegmentCatalog, SegmentEntry, SegmentTable};
+use crate::engine::models::storage::model::{FileBackend, StorageBackend};
+use crate::engine::models::utils::now;
+use crate::engine::models::vacuum::model::VacuumReport;
+use serde_json::Value;
+
+/// 🦀
+/// A step upgrading the decrypted JSON of a database from one format version to the next.
+///
+/// # Fields
+///
+/// - `from_version`: The format version the step applies to; it produces `from_version + 1`.
+/// - `description`: What the step changes, reported in errors.
+/// - `apply`: The upgrade itself, operating on the JSON payload of the [`Database`].
+pub struct Migration {
+    pub from_version: u32,
+    pub description: &'static str,
+    pub apply: fn(&mut Value) -> Result<(), String>,
+}
+
+/// 🦀
+/// The migrations applied on load, one per format version below [`FORMAT_VERSION`].
+///
+/// When the serialized model changes, bump [`FORMAT_VERSION`] and append a step here: files written
+/// by every earlier version then keep loading.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from_version: 0,
+        description: "add the versioned file header",
+        apply: |_| Ok(()),
+    },
+    Migration {
+        from_version: 1,
+        description: "add the encoding flag to the header",
+        apply: |_| Ok(()),
+    },
+    Migration {
+        from_version: 2,
+        description: "add the compression flag to the header",
+        apply: |_| Ok(()),
+    },
+    Migration {
+        from_version: 3,
+        description: "add the segmented layout to the header",
+        apply: |_| Ok(()),
+    },
+];
 
 /// 🦀
 /// Loads a [`Database`] instance from disk, or creates a new one if the file doesn't exist.
@@ -29,6 +76,8 @@ use std::path::Path;
 /// - `Ok(Database)` if the file is successfully read and parsed, or a new instance is created
 /// - `Err(NosqliteError)` if the file cannot be read, decrypted, or deserialized
 ///
+/// Files written by an older format version are upgraded on load, see [`migrate_database`].
+///
 /// # Example
 ///
 /// ```rust
@@ -56,13 +105,525 @@ pub fn load_or_create_database(
     path: &str,
     error_handler: &mut NosqliteErrorHandler,
 ) -> Result<Database, NosqliteError> {
-    match File::load_or_create(path, error_handler) {
-        Ok(db) => Ok(db),
-        Err(e) => {
-            error_handler.log_error(e.clone());
-            Err(e)
+    load_or_create_database_with_options(path, &NosqliteOptions::default(), error_handler)
+}
+
+/// 🦀
+/// Loads a [`Database`] like [`load_or_create_database`], with the encryption key taken from `options`.
+///
+/// # Parameters
+///
+/// - `path`: The file path to the encrypted `.nosqlite` database file.
+/// - `options`: The [`NosqliteOptions`] describing where the key comes from.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(Database)` if the file is successfully read and parsed, or a new instance is created
+/// - `Err(NosqliteError)` if the key is unavailable or wrong, or the file cannot be read or parsed
+///
+/// # See Also
+///
+/// - [`File::load_or_create_with_options`] — internal loader implementation
+pub fn load_or_create_database_with_options(
+    path: &str,
+    options: &NosqliteOptions,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<Database, NosqliteError> {
+    let key = File::resolve_key(path, options, error_handler)?;
+    load_or_create_database_with_key(path, &key, error_handler)
+}
+
+/// 🦀
+/// Loads a [`Database`] like [`load_or_create_database`], with a key already resolved by [`File::resolve_key`].
+///
+/// Files written by an older format version are upgraded with [`migrate_database`], then written
+/// back in the current format. The write-ahead log next to the file, if any, is then replayed with
+/// [`replay_wal`].
+///
+/// # Parameters
+///
+/// - `path`: The file path to the `.nosqlite` database file.
+/// - `key`: The [`EncryptionKey`] to decrypt with, and to re-encrypt with after a migration.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(Database)` if the file is successfully read, migrated and parsed, or a new instance is created
+/// - `Err(NosqliteError::UnsupportedFormatVersion)` if the file was written by a newer version
+/// - `Err(NosqliteError)` if the file cannot be read, decrypted, migrated or parsed
+///
+/// # See Also
+///
+/// - [`File::read_payload`] — internal loader implementation
+pub fn load_or_create_database_with_key(
+    path: &str,
+    key: &EncryptionKey,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<Database, NosqliteError> {
+    load_database_from_backend(&mut FileBackend::new(path), key, false, error_handler)
+}
+
+/// 🦀
+/// Loads an existing [`Database`] without writing anything to disk, as for a read-only open.
+///
+/// Files written by an older format version are upgraded with [`migrate_database`] in memory only,
+/// and the write-ahead log, if any, is replayed with [`replay_wal`] but left in place.
+///
+/// # Parameters
+///
+/// - `path`: The file path to the `.nosqlite` database file.
+/// - `key`: The [`EncryptionKey`] to decrypt with.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(Database)` if the file is successfully read, migrated and parsed
+/// - `Err(NosqliteError::DatabaseNotFound)` if the file does not exist
+/// - `Err(NosqliteError)` if the file cannot be read, decrypted, migrated or parsed
+pub fn load_database_with_key(
+    path: &str,
+    key: &EncryptionKey,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<Database, NosqliteError> {
+    load_database_from_backend(&mut FileBackend::new(path), key, true, error_handler)
+}
+
+/// 🦀
+/// Loads a [`Database`] from a [`StorageBackend`], migrating it and replaying its write-ahead log.
+///
+/// This is the loader behind [`load_or_create_database_with_key`] and [`load_database_with_key`].
+///
+/// For a database stored with the segmented [`Layout`], only the catalog is read: the collections
+/// stay in [`SegmentTable::unloaded`] until [`load_collections`] is called for them, except those
+/// touched by the write-ahead log.
+///
+/// # Parameters
+///
+/// - `backend`: The [`StorageBackend`] holding the database.
+/// - `key`: The [`EncryptionKey`] to decrypt with, and to re-encrypt with after a migration.
+/// - `read_only`: If `true`, a missing database is an error, and a migrated database is not written back.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(Database)` if the database is loaded, or created empty when missing and not `read_only`
+/// - `Err(NosqliteError::DatabaseNotFound)` if the database is missing and `read_only` is set
+/// - `Err(NosqliteError)` if the content cannot be read, decrypted, migrated or parsed
+pub fn load_database_from_backend(
+    backend: &mut dyn StorageBackend,
+    key: &EncryptionKey,
+    read_only: bool,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<Database, NosqliteError> {
+    let name = backend.name().to_string();
+    let mut db = match backend.load(error_handler)? {
+        Some(content) => {
+            let (header, mut payload) = File::decode_payload(&name, &content, key, error_handler)?;
+            if !header.layout.is_single() {
+                let catalog = File::catalog_from_value(payload, error_handler)?;
+                let mut db = Database::new(&name);
+                db.wal_sequence = catalog.wal_sequence;
+                db.segments = SegmentTable::from_catalog(&catalog);
+                replay_wal_from_backend(backend, &mut db, key, error_handler)?;
+                return Ok(db);
+            }
+            migrate_database(&mut payload, header.version, error_handler)?;
+            let db = File::database_from_value(payload, error_handler)?;
+            if header.version < FORMAT_VERSION && !read_only {
+                let content = File::encode_database(&db, key, error_handler)?;
+                backend.save(&content, error_handler)?;
+            }
+            db
+        }
+        None if read_only => {
+            let error = NosqliteError::DatabaseNotFound(name);
+            error_handler.log_error(error.clone());
+            return Err(error);
+        }
+        None => Database::new(&name),
+    };
+    replay_wal_from_backend(backend, &mut db, key, error_handler)?;
+    Ok(db)
+}
+
+/// 🦀
+/// Applies the records of the write-ahead log of a database that are newer than its
+/// [`Database::wal_sequence`].
+///
+/// The log is left in place: it is only removed once the database has been saved with the
+/// replayed records (a checkpoint).
+///
+/// # Parameters
+///
+/// - `path`: The path of the database, next to which the log is stored.
+/// - `db`: The [`Database`] loaded from the file, updated in place.
+/// - `key`: The [`EncryptionKey`] the log records are encrypted with.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(n)` with the number of records applied.
+/// - `Err(NosqliteError)` if the log is corrupted or a record cannot be applied.
+///
+/// # See Also
+///
+/// - [`File::read_wal`] — reads and authenticates the records
+pub fn replay_wal(
+    path: &str,
+    db: &mut Database,
+    key: &EncryptionKey,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<usize, NosqliteError> {
+    replay_wal_from_backend(&mut FileBackend::new(path), db, key, error_handler)
+}
+
+/// 🦀
+/// Applies the records of the write-ahead log of a [`StorageBackend`], as [`replay_wal`] does for a file.
+///
+/// # Parameters
+///
+/// - `backend`: The [`StorageBackend`] holding the log.
+/// - `db`: The [`Database`] loaded from the backend, updated in place.
+/// - `key`: The [`EncryptionKey`] the log records are encrypted with.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(n)` with the number of records applied.
+/// - `Err(NosqliteError)` if the log is corrupted or a record cannot be applied.
+pub fn replay_wal_from_backend(
+    backend: &mut dyn StorageBackend,
+    db: &mut Database,
+    key: &EncryptionKey,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<usize, NosqliteError> {
+    let Some(content) = backend.load_log(error_handler)? else {
+        return Ok(0);
+    };
+    let wal_name = File::wal_path(backend.name());
+    let mut applied = 0;
+    for record in File::decode_wal(&wal_name, &content, key, error_handler)? {
+        if record.sequence <= db.wal_sequence {
+            continue;
+        }
+        let collections = record.operation.collections();
+        load_collections(backend, db, collections.clone(), key, error_handler)?;
+        record.operation.apply(db, error_handler)?;
+        db.mark_dirty(collections);
+        db.wal_sequence = record.sequence;
+        applied += 1;
+    }
+    Ok(applied)
+}
+
+/// 🦀
+/// Loads collections of a database stored with the segmented [`Layout`] from their segments.
+///
+/// Each segment is decrypted and upgraded with [`migrate_database`] like a whole database, then
+/// its collection is added to the database and removed from [`SegmentTable::unloaded`].
+/// Collections already loaded, or unknown, are skipped.
+///
+/// # Parameters
+///
+/// - `backend`: The [`StorageBackend`] holding the segments.
+/// - `db`: The [`Database`] loaded from the catalog, updated in place.
+/// - `names`: The collections to load, or `None` for all of them.
+/// - `key`: The [`EncryptionKey`] the segments are encrypted with.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(n)` with the number of collections loaded.
+/// - `Err(NosqliteError)` if a segment is missing, or cannot be decrypted, migrated or parsed.
+pub fn load_collections(
+    backend: &mut dyn StorageBackend,
+    db: &mut Database,
+    names: Option<Vec<&str>>,
+    key: &EncryptionKey,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<usize, NosqliteError> {
+    let names: Vec<String> = match names {
+        Some(names) => names
+            .into_iter()
+            .filter(|name| db.segments.unloaded.contains(*name))
+            .map(str::to_string)
+            .collect(),
+        None => db.segments.unloaded.iter().cloned().collect(),
+    };
+    let mut loaded = 0;
+    for entry in db.segments.stored.clone() {
+        if !names.contains(&entry.name) || !db.segments.unloaded.contains(&entry.name) {
+            continue;
+        }
+        let (header, payload) = File::read_segment(backend, &entry.segment, key, error_handler)?;
+        let mut payload = serde_json::json!({ "collections": [payload] });
+        migrate_database(&mut payload, header.version, error_handler)?;
+        let collection = payload["collections"][0].take();
+        db.collections
+            .push(File::collection_from_value(collection, error_handler)?);
+        db.segments.unloaded.remove(&entry.name);
+        loaded += 1;
+    }
+    Ok(loaded)
+}
+
+/// 🦀
+/// Saves a [`Database`] to a [`StorageBackend`] with the given [`Layout`], then removes the
+/// write-ahead log.
+///
+/// - [`Layout::Single`]: the whole database is written to the data file, after loading its
+///   unloaded collections; the segments, if any, are then removed.
+/// - [`Layout::Segmented`]: a new segment is written for each collection that changed or has none
+///   yet, then the [`SegmentCatalog`] is written to the data file. Segments the catalog no longer
+///   references are removed last, so that a failure at any step leaves the previous catalog and
+///   all of its segments in place.
+///
+/// # Parameters
+///
+/// - `backend`: The [`StorageBackend`] to write to.
+/// - `db`: The [`Database`] to save; its [`SegmentTable`] is updated to what was written.
+/// - `key`: The [`EncryptionKey`] to encrypt with.
+/// - `layout`: The [`Layout`] to write.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(())` once the database is written and the log removed.
+/// - `Err(NosqliteError)` if a collection cannot be loaded, or serialization, encryption or a
+///   write fails.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+/// use nosqlite_rust::engine::models::{Database, EncryptionKey, Layout, MemoryBackend};
+/// use nosqlite_rust::engine::services::database_service::{load_database_from_backend, save_database_to_backend};
+///
+/// let mut handler = NosqliteErrorHandler::new("temp/data76.nosqlite".to_string());
+/// let key = EncryptionKey::new([6u8; 32], None);
+/// let mut backend = MemoryBackend::new("shop");
+/// let mut db = Database::default();
+/// db.add_collection("users", json!({}), &mut handler)?;
+/// db.add_collection("orders", json!({}), &mut handler)?;
+/// save_database_to_backend(&mut backend, &mut db, &key, Layout::Segmented, &mut handler)?;
+/// assert_eq!(backend.segments().len(), 2);
+///
+/// let db = load_database_from_backend(&mut backend, &key, true, &mut handler)?;
+/// assert!(db.collections.is_empty());
+/// assert_eq!(db.segments.unloaded.len(), 2);
+/// Ok::<(), NosqliteError>(())
+/// ```
+pub fn save_database_to_backend(
+    backend: &mut dyn StorageBackend,
+    db: &mut Database,
+    key: &EncryptionKey,
+    layout: Layout,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<(), NosqliteError> {
+    if layout.is_single() {
+        load_collections(backend, db, None, key, error_handler)?;
+        let content = File::encode_database(db, key, error_handler)?;
+        backend.save(&content, error_handler)?;
+        backend.clear_log(error_handler)?;
+        db.segments = SegmentTable::default();
+        for id in backend.list_segments(error_handler)? {
+            backend.remove_segment(&id, error_handler)?;
+        }
+        return Ok(());
+    }
+
+    let mut entries: Vec<SegmentEntry> = db
+        .segments
+        .stored
+        .iter()
+        .filter(|entry| db.segments.unloaded.contains(&entry.name))
+        .cloned()
+        .collect();
+    for collection in &db.collections {
+        let segment = match db.segments.segment_of(&collection.name) {
+            Some(id) if !db.segments.dirty.contains(&collection.name) => id.to_string(),
+            _ => {
+                let id = SegmentTable::new_segment_id();
+                let content = File::encode_segment(collection, key, error_handler)?;
+                backend.save_segment(&id, &content, error_handler)?;
+                id
+            }
+        };
+        entries.push(SegmentEntry {
+            name: collection.name.clone(),
+            segment,
+        });
+    }
+    let catalog = SegmentCatalog::from_database(db, &entries);
+    let content = File::encode_catalog(&catalog, key, error_handler)?;
+    backend.save(&content, error_handler)?;
+    backend.clear_log(error_handler)?;
+    db.segments.stored = catalog.collections;
+    db.segments.dirty.clear();
+
+    for id in backend.list_segments(error_handler)? {
+        if !db.segments.stored.iter().any(|entry| entry.segment == id) {
+            backend.remove_segment(&id, error_handler)?;
+        }
+    }
+    Ok(())
+}
+
+/// 🦀
+/// Rewrites a database stored in a [`StorageBackend`] from its live data only.
+///
+/// Every collection is loaded, expired documents are purged and indexes are rebuilt, then the
+/// whole database is saved with [`save_database_to_backend`]: every segment is rewritten, segments
+/// no longer referenced are removed and the write-ahead log is folded into the data file.
+///
+/// # Parameters
+///
+/// - `backend`: The [`StorageBackend`] holding the database.
+/// - `db`: The [`Database`] loaded from the backend, compacted in place.
+/// - `key`: The [`EncryptionKey`] of the database.
+/// - `layout`: The [`Layout`] to write.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(VacuumReport)` with the stored size before and after, and what was purged and rebuilt.
+/// - `Err(NosqliteError)` if a collection cannot be loaded, or the storage cannot be read or written.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+/// use nosqlite_rust::engine::models::{Database, EncryptionKey, Layout, MemoryBackend, StorageBackend};
+/// use nosqlite_rust::engine::services::database_service::{save_database_to_backend, vacuum_database};
+///
+/// let mut handler = NosqliteErrorHandler::new("temp/data82.nosqlite".to_string());
+/// let key = EncryptionKey::new([8u8; 32], None);
+/// let mut backend = MemoryBackend::new("shop");
+/// let mut db = Database::default();
+/// db.add_collection("users", json!({}), &mut handler)?;
+/// save_database_to_backend(&mut backend, &mut db, &key, Layout::Single, &mut handler)?;
+/// backend.append_log(&[b'x'; 1024], &mut handler)?;
+///
+/// let report = vacuum_database(&mut backend, &mut db, &key, Layout::Single, &mut handler)?;
+/// assert_eq!(report.reclaimed_bytes(), 1024);
+/// Ok::<(), NosqliteError>(())
+/// ```
+pub fn vacuum_database(
+    backend: &mut dyn StorageBackend,
+    db: &mut Database,
+    key: &EncryptionKey,
+    layout: Layout,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<VacuumReport, NosqliteError> {
+    load_collections(backend, db, None, key, error_handler)?;
+    let purged_documents = db.purge_expired(now());
+    db.rebuild_indexes();
+    db.mark_dirty(None);
+
+    let bytes_before = stored_bytes(backend, error_handler)?;
+    save_database_to_backend(backend, db, key, layout, error_handler)?;
+    let bytes_after = stored_bytes(backend, error_handler)?;
+    Ok(VacuumReport {
+        bytes_before,
+        bytes_after,
+        purged_documents,
+        rebuilt_indexes: db.collections.iter().map(|c| c.indexes.len()).sum(),
+    })
+}
+
+/// 🦀
+/// Returns the number of bytes a [`StorageBackend`] stores for a database: its data file, its
+/// write-ahead log and all of its segments. Snapshots are not counted.
+///
+/// # Returns
+///
+/// - `Ok(bytes)` with the total size.
+/// - `Err(NosqliteError)` if the storage cannot be read.
+pub fn stored_bytes(
+    backend: &mut dyn StorageBackend,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<u64, NosqliteError> {
+    let mut bytes = 0;
+    if let Some(content) = backend.load(error_handler)? {
+        bytes += content.len() as u64;
+    }
+    if let Some(content) = backend.load_log(error_handler)? {
+        bytes += content.len() as u64;
+    }
+    for id in backend.list_segments(error_handler)? {
+        if let Some(content) = backend.load_segment(&id, error_handler)? {
+            bytes += content.len() as u64;
         }
     }
+    Ok(bytes)
+}
+
+/// 🦀
+/// Upgrades the JSON payload of a database from `version` to [`FORMAT_VERSION`], applying the
+/// [`MIGRATIONS`] in order.
+///
+/// # Parameters
+///
+/// - `payload`: The decrypted JSON of the database, upgraded in place.
+/// - `version`: The format version the payload was written with.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(n)` with the number of migrations applied (`0` if the payload is already current).
+/// - `Err(NosqliteError::UnsupportedFormatVersion)` if `version` is newer than [`FORMAT_VERSION`],
+///   or if no migration exists for an intermediate version.
+/// - `Err(NosqliteError::InvalidDatabaseFormat)` if a migration fails.
+///
+/// # Example
+///
+/// ```rust
+/// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+/// use nosqlite_rust::engine::services::database_service::migrate_database;
+/// use serde_json::json;
+///
+/// let mut handler = NosqliteErrorHandler::new("temp/data59.nosqlite".to_string());
+/// let mut payload = json!({ "collections": [] });
+/// assert_eq!(migrate_database(&mut payload, 0, &mut handler)?, 4);
+/// assert!(migrate_database(&mut payload, 99, &mut handler).is_err());
+/// Ok::<(), NosqliteError>(())
+/// ```
+pub fn migrate_database(
+    payload: &mut Value,
+    version: u32,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<usize, NosqliteError> {
+    if version > FORMAT_VERSION {
+        let error = NosqliteError::UnsupportedFormatVersion(format!(
+            "format version {} is newer than the supported version {}",
+            version, FORMAT_VERSION
+        ));
+        error_handler.log_error(error.clone());
+        return Err(error);
+    }
+
+    let mut applied = 0;
+    for current in version..FORMAT_VERSION {
+        let Some(migration) = MIGRATIONS.iter().find(|m| m.from_version == current) else {
+            let error = NosqliteError::UnsupportedFormatVersion(format!(
+                "no migration from format version {}",
+                current
+            ));
+            error_handler.log_error(error.clone());
+            return Err(error);
+        };
+        error_handler.try_or_log((migration.apply)(payload), |e| {
+            NosqliteError::InvalidDatabaseFormat(format!(
+                "migration from version {} ({}) failed: {}",
+                current, migration.description, e
+            ))
+        })?;
+        applied += 1;
+    }
+    Ok(applied)
 }
 
 /// 🦀
@@ -112,7 +673,69 @@ pub fn save_database(
     db: &Database,
     error_handler: &mut NosqliteErrorHandler,
 ) -> Result<(), NosqliteError> {
-    match File::save(path, db, error_handler) {
+    save_database_with_options(path, db, &NosqliteOptions::default(), error_handler)
+}
+
+/// 🦀
+/// Saves the database like [`save_database`], with the encryption key taken from `options`.
+///
+/// # Parameters
+///
+/// - `path`: The file path to write the encrypted database to.
+/// - `db`: A reference to the [`Database`] instance to be saved.
+/// - `options`: The [`NosqliteOptions`] describing where the key comes from.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for logging errors.
+///
+/// # Returns
+///
+/// - `Ok(())` if the database is successfully saved
+/// - `Err(NosqliteError)` if the key is unavailable, or serialization, encryption, or file write fails
+///
+/// # See Also
+///
+/// - [`File::save_with_options`] — internal save logic
+pub fn save_database_with_options(
+    path: &str,
+    db: &Database,
+    options: &NosqliteOptions,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<(), NosqliteError> {
+    match File::save_with_options(path, db, options, error_handler) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error_handler.log_error(e.clone());
+            Err(e)
+        }
+    }
+}
+
+/// 🦀
+/// Saves the database like [`save_database`], with a key already resolved by [`File::resolve_key`].
+///
+/// This avoids reading a key file or running a key derivation function on every save.
+///
+/// # Parameters
+///
+/// - `path`: The file path to write the encrypted database to.
+/// - `db`: A reference to the [`Database`] instance to be saved.
+/// - `key`: The [`EncryptionKey`] to encrypt with; its header is written along with the data.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for logging errors.
+///
+/// # Returns
+///
+/// - `Ok(())` if the database is successfully saved
+/// - `Err(NosqliteError)` if serialization, encryption, or file write fails
+///
+/// # See Also
+///
+/// - [`File::save_with_key`] — internal save logic
+pub fn save_database_with_key(
+    path: &str,
+    db: &Database,
+    key: &EncryptionKey,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<(), NosqliteError> {
+    match File::save_with_key(path, db, key, error_handler) {
         Ok(_) => Ok(()),
         Err(e) => {
             error_handler.log_error(e.clone());
@@ -1,7 +1,15 @@
 use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
+use crate::engine::models::collection::model::Collection;
 use crate::engine::models::database::model::Database;
 use crate::engine::models::document::model::Document;
+use crate::engine::models::geo::model::{GeoPoint, GeoQuery, GeoShape};
+use crate::engine::models::query_plan::model::{AccessPath, QueryPlan};
+use crate::engine::models::utils::{get_nested_value, now};
+use crate::engine::models::vector::implem::{as_vector, vector_dimension};
+use crate::engine::models::vector::model::VectorMetric;
 use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::time::Instant;
 
 /// 🦀
 /// Inserts a new document into the specified collection.
@@ -364,6 +372,7 @@ pub fn get_document<'a>(
 /// # See Also
 ///
 /// - [`get_documents`] — for conditional filtering
+/// - [`get_live_documents`] — without documents expired by a TTL index
 pub fn get_all_documents<'a>(
     db: &'a Database,
     collection_name: &str,
@@ -381,6 +390,59 @@ pub fn get_all_documents<'a>(
     Ok(collection.all_documents())
 }
 
+/// 🦀
+/// Retrieves the documents of a collection that have not expired under a TTL index.
+///
+/// Unlike [`get_all_documents`], documents expired but not purged yet are skipped, as they are by
+/// queries.
+///
+/// # Parameters
+///
+/// - `db`: A reference to the [`Database`] instance.
+/// - `collection_name`: The name of the collection to inspect.
+/// - `handler`: Logs collection lookup failures.
+///
+/// # Returns
+///
+/// - `Ok(Vec<&Document>)` — if the collection exists
+/// - `Err(NosqliteError::CollectionNotFound)` otherwise
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::{error::{NosqliteErrorHandler, NosqliteError}, models::{Database, IndexKind}};
+/// use nosqlite_rust::engine::services::document_service::{get_all_documents, get_live_documents};
+///
+/// let mut db = Database::new("temp/data88.nosqlite");
+/// let mut handler = NosqliteErrorHandler::new("temp/data88.nosqlite".to_string());
+/// db.add_collection("sessions", json!({}), &mut handler)?;
+/// let col = db.get_collection_mut("sessions").unwrap();
+/// col.add_document(json!({ "seen_at": 0 }), &mut handler)?;
+/// let kind = IndexKind::Ttl { field: "seen_at".to_string(), expire_after_secs: 60 };
+/// col.add_index("expiry", kind, &mut handler)?;
+///
+/// assert_eq!(get_all_documents(&db, "sessions", &mut handler)?.len(), 1);
+/// assert!(get_live_documents(&db, "sessions", &mut handler)?.is_empty());
+/// Ok::<(), NosqliteError>(())
+/// ```
+pub fn get_live_documents<'a>(
+    db: &'a Database,
+    collection_name: &str,
+    handler: &mut NosqliteErrorHandler,
+) -> Result<Vec<&'a Document>, NosqliteError> {
+    let collection = db.get_collection(collection_name).ok_or_else(|| {
+        let error = NosqliteError::CollectionNotFound(format!(
+            "Collection '{}' not found",
+            collection_name
+        ));
+        handler.log_error(error.clone());
+        error
+    })?;
+
+    Ok(collection.live_documents())
+}
+
 /// 🦀
 /// Retrieves documents matching the specified JSON filter and applies an optional projection.
 ///
@@ -401,7 +463,8 @@ pub fn get_all_documents<'a>(
 /// # Returns
 ///
 /// - `Ok(Vec<Value>)` containing the filtered and projected documents as JSON objects.
-/// - `Err(NosqliteError)` if the collection is not found.
+/// - `Err(NosqliteError)` if the collection is not found, if `$text` is used without a text index,
+///   or if a geospatial operator is malformed.
 ///
 /// # Example
 ///
@@ -438,7 +501,14 @@ pub fn get_all_documents<'a>(
 /// - Supports exact match comparisons on all JSON types (`string`, `number`, `bool`, etc.).
 /// - If `filter` is empty, all documents are returned.
 /// - If `projection` is empty, full documents are returned.
-/// - No advanced operators (e.g., `$gt`, `$lt`) are supported yet.
+/// - `{ "$text": { "$search": "..." } }` performs a full-text search through the collection's text index.
+///   Results are then ordered by relevance and carry their BM25 score in the `_score` field.
+/// - `{ "loc": { "$geoWithin": { "$circle": { "center": { "lat": .., "lng": .. }, "radius_km": 5 } } } }`
+///   matches documents whose point field lies inside a circle; `$box` and `$polygon` shapes are also supported.
+/// - `{ "loc": { "$near": { "point": { "lat": .., "lng": .. }, "max_distance_km": 5, "limit": 10 } } }`
+///   orders documents by increasing haversine distance and reports it in kilometers in the `_distance` field.
+/// - A geo index on the queried field avoids examining documents far from the searched area.
+/// - Comparison operators (e.g., `$gt`, `$lt`) are not supported yet.
 ///
 /// # See Also
 ///
@@ -459,7 +529,272 @@ pub fn get_documents(
         error
     })?;
 
-    let docs = collection.all_documents();
+    let (filtered_docs, _, _) =
+        handler.try_or_log(execute_query(collection, filter, projection), |e| e)?;
+
+    Ok(filtered_docs)
+}
+
+/// 🦀
+/// Runs a query like [`get_documents`] and reports how it was executed instead of its results.
+///
+/// The returned [`QueryPlan`] contains the chosen access path, the number of documents
+/// examined versus returned, and the time spent evaluating the query.
+///
+/// # Parameters
+///
+/// - `db`: A reference to the [`Database`] instance.
+/// - `collection_name`: The name of the collection to query.
+/// - `filter`: The JSON filter, with the same semantics as in [`get_documents`].
+/// - `projection`: The JSON projection, with the same semantics as in [`get_documents`].
+/// - `handler`: The [`NosqliteErrorHandler`] used for logging errors (e.g., missing collections).
+///
+/// # Returns
+///
+/// - `Ok(QueryPlan)` describing the execution of the query.
+/// - `Err(NosqliteError)` if the collection is not found.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::{
+///     error::{NosqliteErrorHandler, NosqliteError},
+///     models::{AccessPath, Database}
+/// };
+/// use nosqlite_rust::engine::services::document_service::explain_documents;
+///
+/// let mut db = Database::new("temp/data44.nosqlite");
+/// let mut handler = NosqliteErrorHandler::new("temp/data44.nosqlite".to_string());
+/// db.add_collection("posts", json!({}), &mut handler)?;
+/// let col = db.get_collection_mut("posts").unwrap();
+/// col.add_document(json!({ "author": "alice" }), &mut handler)?;
+/// col.add_document(json!({ "author": "bob" }), &mut handler)?;
+///
+/// let plan = explain_documents(&db, "posts", &json!({ "author": "alice" }), &json!({}), &mut handler)?;
+/// assert_eq!(plan.access_path, AccessPath::FullScan);
+/// assert_eq!(plan.documents_examined, 2);
+/// assert_eq!(plan.documents_returned, 1);
+/// Ok::<(), NosqliteError>(())
+/// ```
+///
+/// # See Also
+///
+/// - [`get_documents`] — runs the same query and returns its results
+pub fn explain_documents(
+    db: &Database,
+    collection_name: &str,
+    filter: &Value,
+    projection: &Value,
+    handler: &mut NosqliteErrorHandler,
+) -> Result<QueryPlan, NosqliteError> {
+    let collection = db.get_collection(collection_name).ok_or_else(|| {
+        let error = NosqliteError::CollectionNotFound(format!(
+            "Collection '{}' not found",
+            collection_name
+        ));
+        handler.log_error(error.clone());
+        error
+    })?;
+
+    let start = Instant::now();
+    let (results, access_path, documents_examined) =
+        handler.try_or_log(execute_query(collection, filter, projection), |e| e)?;
+    let execution_time = start.elapsed();
+
+    Ok(QueryPlan {
+        collection: collection_name.to_string(),
+        filter: filter.clone(),
+        access_path,
+        documents_examined,
+        documents_returned: results.len(),
+        execution_time,
+    })
+}
+
+/// 🦀
+/// Finds the `k` documents whose vector field is the most similar to a query vector.
+///
+/// Without a vector index, every document is compared to the query (exact results). If the
+/// collection has a vector index on `field` with the same metric, the HNSW graph is searched
+/// instead, which is much faster on large collections but approximate.
+///
+/// # Parameters
+///
+/// - `db`: A reference to the [`Database`] instance.
+/// - `collection_name`: The name of the collection to query.
+/// - `field`: The vector field to compare (dot paths allowed), e.g. `"embedding"`.
+/// - `vector`: The query vector.
+/// - `k`: The maximum number of documents to return.
+/// - `metric`: The [`VectorMetric`] used to compare vectors.
+/// - `handler`: The [`NosqliteErrorHandler`] used for logging errors.
+///
+/// # Returns
+///
+/// - `Ok(Vec<Value>)` with up to `k` documents, most similar first. Each one carries its cosine
+///   or dot similarity in the `_score` field, or its L2 distance in the `_distance` field.
+/// - `Err(NosqliteError)` if the collection is not found, or if the query vector is empty or
+///   does not match the `vector(N)` dimension declared in the schema.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::{
+///     error::{NosqliteErrorHandler, NosqliteError},
+///     models::{Database, VectorMetric}
+/// };
+/// use nosqlite_rust::engine::services::document_service::k_nearest_documents;
+///
+/// let mut db = Database::new("temp/data52.nosqlite");
+/// let mut handler = NosqliteErrorHandler::new("temp/data52.nosqlite".to_string());
+/// db.add_collection("docs", json!({ "embedding": "vector(2)" }), &mut handler)?;
+/// let col = db.get_collection_mut("docs").unwrap();
+/// col.add_document(json!({ "title": "cats", "embedding": [1.0, 0.0] }), &mut handler)?;
+/// col.add_document(json!({ "title": "cars", "embedding": [0.0, 1.0] }), &mut handler)?;
+///
+/// let results = k_nearest_documents(&db, "docs", "embedding", &[0.9, 0.2], 1, VectorMetric::Cosine, &mut handler)?;
+/// assert_eq!(results[0]["title"], "cats");
+/// Ok::<(), NosqliteError>(())
+/// ```
+pub fn k_nearest_documents(
+    db: &Database,
+    collection_name: &str,
+    field: &str,
+    vector: &[f64],
+    k: usize,
+    metric: VectorMetric,
+    handler: &mut NosqliteErrorHandler,
+) -> Result<Vec<Value>, NosqliteError> {
+    let collection = db.get_collection(collection_name).ok_or_else(|| {
+        let error = NosqliteError::CollectionNotFound(format!(
+            "Collection '{}' not found",
+            collection_name
+        ));
+        handler.log_error(error.clone());
+        error
+    })?;
+
+    let declared = get_nested_value(&collection.structure, field)
+        .and_then(Value::as_str)
+        .and_then(vector_dimension);
+    if vector.is_empty() || declared.is_some_and(|dimension| dimension != vector.len()) {
+        let error = NosqliteError::InvalidVector(format!(
+            "Query vector of dimension {} does not match field '{}'",
+            vector.len(),
+            field
+        ));
+        handler.log_error(error.clone());
+        return Err(error);
+    }
+
+    let candidates: Vec<&Document> = match collection.vector_index(field, metric) {
+        Some(index) => {
+            let now = now();
+            index
+                .vector_search(vector, k)
+                .iter()
+                .filter_map(|(id, _)| collection.document_position(id))
+                .map(|position| &collection.documents[position])
+                .filter(|doc| !collection.is_expired(doc, now))
+                .collect()
+        }
+        None => collection.live_documents(),
+    };
+
+    let mut nearest: Vec<(f64, f64, &Document)> = candidates
+        .into_iter()
+        .filter_map(|doc| {
+            let candidate = get_nested_value(&doc.data, field).and_then(as_vector)?;
+            if candidate.len() != vector.len() {
+                return None;
+            }
+            Some((
+                metric.distance(vector, &candidate),
+                metric.compare(vector, &candidate),
+                doc,
+            ))
+        })
+        .collect();
+    nearest.sort_by(|a, b| a.0.total_cmp(&b.0));
+    nearest.truncate(k);
+
+    Ok(nearest
+        .into_iter()
+        .map(|(_, value, doc)| {
+            let mut result = doc.data.clone();
+            if let Value::Object(result) = &mut result {
+                result.insert(metric.result_field().to_string(), Value::from(value));
+            }
+            result
+        })
+        .collect())
+}
+
+/// Field added to the results of a `$text` query, holding the BM25 relevance score.
+pub const SCORE_FIELD: &str = "_score";
+/// Field added to the results of a `$near` query, holding the distance in kilometers.
+pub const DISTANCE_FIELD: &str = "_distance";
+
+/// Initial search radius of an unbounded `$near` query served by a geo index, in kilometers.
+const NEAR_START_RADIUS_KM: f64 = 10.0;
+/// Radius beyond which a `$near` search covers the whole Earth, in kilometers.
+const NEAR_MAX_RADIUS_KM: f64 = 20_100.0;
+
+/// Evaluates `filter` against the collection and returns the projected matches,
+/// the access path that was used and the number of documents examined.
+fn execute_query(
+    collection: &Collection,
+    filter: &Value,
+    projection: &Value,
+) -> Result<(Vec<Value>, AccessPath, usize), NosqliteError> {
+    if let Some(search) = text_search_terms(filter)? {
+        let index = collection.text_index().ok_or_else(|| {
+            NosqliteError::IndexNotFound(format!(
+                "The $text operator requires a text index on collection '{}'",
+                collection.name
+            ))
+        })?;
+        let scores = index.text_search(search);
+
+        let now = now();
+        let mut scored: Vec<(f64, usize, &Document)> = scores
+            .iter()
+            .filter_map(|(id, score)| {
+                let position = collection.document_position(id)?;
+                let doc = &collection.documents[position];
+                if collection.is_expired(doc, now) || !matches_filter(&doc.data, filter) {
+                    return None;
+                }
+                Some((*score, position, doc))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let results = scored
+            .into_iter()
+            .map(|(score, _, doc)| {
+                let mut projected = apply_projection(&doc.data, projection);
+                if let (Value::Object(projected), Value::Object(proj_obj)) =
+                    (&mut projected, projection)
+                {
+                    if proj_obj.is_empty() || proj_obj.contains_key(SCORE_FIELD) {
+                        projected.insert(SCORE_FIELD.to_string(), Value::from(score));
+                    }
+                }
+                projected
+            })
+            .collect();
+        return Ok((results, AccessPath::Index(index.name.clone()), scores.len()));
+    }
+
+    if let Some((field, query)) = geo_filter(filter)? {
+        return Ok(execute_geo_query(
+            collection, field, &query, filter, projection,
+        ));
+    }
+
+    let docs = collection.live_documents();
 
     let filtered_docs = docs
         .iter()
@@ -473,12 +808,158 @@ pub fn get_documents(
         })
         .collect();
 
-    Ok(filtered_docs)
+    Ok((filtered_docs, AccessPath::FullScan, docs.len()))
+}
+
+/// Extracts the search string of a `{ "$text": { "$search": "..." } }` filter, if present.
+fn text_search_terms(filter: &Value) -> Result<Option<&str>, NosqliteError> {
+    match filter.get("$text") {
+        None => Ok(None),
+        Some(text) => text
+            .get("$search")
+            .and_then(Value::as_str)
+            .map(Some)
+            .ok_or_else(|| {
+                NosqliteError::InvalidFilter(
+                    "$text expects an object of the form { \"$search\": \"...\" }".to_string(),
+                )
+            }),
+    }
+}
+
+/// Runs a query containing a `$geoWithin` or `$near` operator on `field`.
+///
+/// With a geo index on the field, only documents in the grid cells around the searched area
+/// are examined. An unbounded `$near` query with a `limit` widens its search radius until
+/// enough documents are found.
+fn execute_geo_query(
+    collection: &Collection,
+    field: &str,
+    query: &GeoQuery,
+    filter: &Value,
+    projection: &Value,
+) -> (Vec<Value>, AccessPath, usize) {
+    let index = collection.geo_index(field);
+
+    let (origin, limit) = match query {
+        GeoQuery::Near { point, limit, .. } => (Some(*point), *limit),
+        GeoQuery::Within(_) => (None, None),
+    };
+    let mut radius_km = match (index, query) {
+        (
+            Some(_),
+            GeoQuery::Near {
+                max_distance_km: None,
+                limit: Some(_),
+                ..
+            },
+        ) => Some(NEAR_START_RADIUS_KM),
+        _ => None,
+    };
+
+    loop {
+        let bounding_box = match (radius_km, origin) {
+            (Some(radius_km), Some(center)) => {
+                Some(GeoShape::Circle { center, radius_km }.bounding_box())
+            }
+            _ => query.bounding_box(),
+        };
+        let candidates: Vec<&Document> = match (index, bounding_box) {
+            (Some(index), Some((min, max))) => {
+                let now = now();
+                let mut candidate_positions: Vec<usize> = index
+                    .geo_candidates(&min, &max)
+                    .iter()
+                    .filter_map(|id| collection.document_position(id))
+                    .collect();
+                candidate_positions.sort_unstable();
+                candidate_positions
+                    .into_iter()
+                    .map(|position| &collection.documents[position])
+                    .filter(|doc| !collection.is_expired(doc, now))
+                    .collect()
+            }
+            _ => collection.live_documents(),
+        };
+
+        let mut matched: Vec<(f64, &Document)> = candidates
+            .iter()
+            .filter_map(|doc| {
+                let point = get_nested_value(&doc.data, field).and_then(GeoPoint::from_value)?;
+                if !query.matches(&point) || !matches_filter(&doc.data, filter) {
+                    return None;
+                }
+                let distance = origin.map_or(0.0, |origin| origin.distance_km(&point));
+                Some((distance, *doc))
+            })
+            .collect();
+
+        if let (Some(radius), Some(limit)) = (radius_km, limit) {
+            let within_radius = matched
+                .iter()
+                .filter(|(distance, _)| *distance <= radius)
+                .count();
+            if within_radius < limit && radius < NEAR_MAX_RADIUS_KM {
+                radius_km = Some(radius * 4.0);
+                continue;
+            }
+        }
+
+        let access_path = match (index, bounding_box) {
+            (Some(index), Some(_)) => AccessPath::Index(index.name.clone()),
+            _ => AccessPath::FullScan,
+        };
+
+        if origin.is_some() {
+            matched.sort_by(|a, b| a.0.total_cmp(&b.0));
+        }
+        if let Some(limit) = limit {
+            matched.truncate(limit);
+        }
+
+        let results = matched
+            .into_iter()
+            .map(|(distance, doc)| {
+                let mut projected = apply_projection(&doc.data, projection);
+                if let (Some(_), Value::Object(projected), Value::Object(proj_obj)) =
+                    (origin, &mut projected, projection)
+                {
+                    if proj_obj.is_empty() || proj_obj.contains_key(DISTANCE_FIELD) {
+                        projected.insert(DISTANCE_FIELD.to_string(), Value::from(distance));
+                    }
+                }
+                projected
+            })
+            .collect();
+        return (results, access_path, candidates.len());
+    }
+}
+
+/// Finds the first field of the filter using a geospatial operator and parses it.
+fn geo_filter(filter: &Value) -> Result<Option<(&str, GeoQuery)>, NosqliteError> {
+    let Value::Object(filter_obj) = filter else {
+        return Ok(None);
+    };
+    for (key, value) in filter_obj {
+        if let Some(query) = GeoQuery::from_value(value).map_err(NosqliteError::InvalidFilter)? {
+            return Ok(Some((key.as_str(), query)));
+        }
+    }
+    Ok(None)
 }
 
 fn matches_filter(doc: &Value, filter: &Value) -> bool {
     if let (Value::Object(doc_obj), Value::Object(filter_obj)) = (doc, filter) {
         for (key, expected_val) in filter_obj {
+            if key == "$text" {
+                continue;
+            }
+            if let Ok(Some(query)) = GeoQuery::from_value(expected_val) {
+                match get_nested_value(doc, key).and_then(GeoPoint::from_value) {
+                    Some(point) if query.matches(&point) => continue,
+                    _ => return false,
+                }
+            }
             match doc_obj.get(key) {
                 Some(actual_val) if actual_val == expected_val => continue,
                 _ => return false,
@@ -0,0 +1,375 @@
+use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
+use crate::engine::models::database::model::Database;
+use crate::engine::models::export::model::ExportFormat;
+use crate::engine::models::utils::validate_against_structure;
+use serde_json::{Map, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// 🦀
+/// Writes the live documents of a collection in the given [`ExportFormat`].
+///
+/// Only the content of each document is written, without its identifier and timestamps. Documents
+/// expired by a TTL index are skipped.
+///
+/// With [`ExportFormat::Csv`], the columns are the dot paths of every field found in the
+/// documents, in the order they first appear. Strings are written as they are, `null` as an empty
+/// cell, and other values (numbers, booleans, arrays and empty objects) as JSON text.
+///
+/// # Parameters
+///
+/// - `db`: A reference to the [`Database`] instance.
+/// - `collection_name`: The name of the collection to export.
+/// - `format`: The [`ExportFormat`] to write.
+/// - `writer`: Where the documents are written.
+/// - `handler`: The error handler used for logging failures.
+///
+/// # Returns
+///
+/// - `Ok(usize)` with the number of documents written.
+/// - `Err(NosqliteError::CollectionNotFound)` if the collection does not exist.
+/// - `Err(NosqliteError::IoError)` if the writer fails.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+/// use nosqlite_rust::engine::models::{Database, ExportFormat};
+/// use nosqlite_rust::engine::services::export_service::export_documents;
+///
+/// let mut handler = NosqliteErrorHandler::new("temp/data84.nosqlite".to_string());
+/// let mut db = Database::default();
+/// db.add_collection("users", json!({}), &mut handler)?;
+/// let users = db.get_collection_mut("users").unwrap();
+/// users.add_document(json!({ "name": "Alice", "address": { "city": "Paris" } }), &mut handler)?;
+///
+/// let mut output = Vec::new();
+/// export_documents(&db, "users", ExportFormat::Csv, &mut output, &mut handler)?;
+/// assert_eq!(String::from_utf8(output).unwrap(), "address.city,name\nParis,Alice\n");
+/// Ok::<(), NosqliteError>(())
+/// ```
+///
+/// # See Also
+///
+/// - [`import_documents`] — reads documents written by this function
+pub fn export_documents(
+    db: &Database,
+    collection_name: &str,
+    format: ExportFormat,
+    mut writer: impl Write,
+    handler: &mut NosqliteErrorHandler,
+) -> Result<usize, NosqliteError> {
+    let collection = db.get_collection(collection_name).ok_or_else(|| {
+        let error = NosqliteError::CollectionNotFound(format!(
+            "Collection '{}' not found",
+            collection_name
+        ));
+        handler.log_error(error.clone());
+        error
+    })?;
+    let records: Vec<&Value> = collection
+        .live_documents()
+        .into_iter()
+        .map(|document| &document.data)
+        .collect();
+
+    let written = match format {
+        ExportFormat::Json => serde_json::to_writer_pretty(&mut writer, &records)
+            .map_err(json_write_error)
+            .and_then(|_| writeln!(writer).map_err(NosqliteError::from)),
+        ExportFormat::Ndjson => records.iter().try_for_each(|record| {
+            serde_json::to_writer(&mut writer, record).map_err(json_write_error)?;
+            writeln!(writer).map_err(NosqliteError::from)
+        }),
+        ExportFormat::Csv => write_csv(&records, &mut writer),
+    }
+    .and_then(|_| writer.flush().map_err(NosqliteError::from));
+
+    match written {
+        Ok(()) => Ok(records.len()),
+        Err(error) => {
+            handler.log_error(error.clone());
+            Err(error)
+        }
+    }
+}
+
+/// 🦀
+/// Reads documents in the given [`ExportFormat`] and inserts them into a collection.
+///
+/// Every record is validated against the collection `structure` before any is inserted, so an
+/// import either inserts all of its records or none. Each inserted document gets a new identifier
+/// and timestamps.
+///
+/// With [`ExportFormat::Csv`], each column is a dot path and empty cells are left out. A cell is
+/// read as the type the collection `structure` gives its path: text for `"string"`, and JSON for
+/// the other types. A cell whose path has no type is read as a JSON number, boolean, array or
+/// object if it parses as one, and as text otherwise.
+///
+/// # Parameters
+///
+/// - `db`: A mutable reference to the [`Database`] instance.
+/// - `collection_name`: The name of the collection to insert into.
+/// - `format`: The [`ExportFormat`] to read.
+/// - `reader`: Where the documents are read from.
+/// - `handler`: The error handler used for logging failures.
+///
+/// # Returns
+///
+/// - `Ok(usize)` with the number of documents inserted.
+/// - `Err(NosqliteError::CollectionNotFound)` if the collection does not exist.
+/// - `Err(NosqliteError::DeserializationError)` if the input cannot be parsed.
+/// - `Err(NosqliteError::DocumentInvalid)` if a record is not an object or does not match the
+///   collection `structure`; nothing is inserted.
+/// - `Err(NosqliteError::IoError)` if the reader fails.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+/// use nosqlite_rust::engine::models::{Database, ExportFormat};
+/// use nosqlite_rust::engine::services::export_service::import_documents;
+///
+/// let mut handler = NosqliteErrorHandler::new("temp/data85.nosqlite".to_string());
+/// let mut db = Database::default();
+/// db.add_collection("users", json!({ "name": "string", "age": "number" }), &mut handler)?;
+///
+/// let input = "name,age,address.city\nAlice,30,Paris\nBob,25,\n";
+/// assert_eq!(import_documents(&mut db, "users", ExportFormat::Csv, input.as_bytes(), &mut handler)?, 2);
+///
+/// let users = db.get_collection("users").unwrap();
+/// assert_eq!(users.documents[0].data, json!({ "name": "Alice", "age": 30, "address": { "city": "Paris" } }));
+/// assert_eq!(users.documents[1].data, json!({ "name": "Bob", "age": 25 }));
+/// Ok::<(), NosqliteError>(())
+/// ```
+///
+/// # See Also
+///
+/// - [`export_documents`] — writes documents this function can read
+pub fn import_documents(
+    db: &mut Database,
+    collection_name: &str,
+    format: ExportFormat,
+    reader: impl Read,
+    handler: &mut NosqliteErrorHandler,
+) -> Result<usize, NosqliteError> {
+    let collection = db.get_collection_mut(collection_name).ok_or_else(|| {
+        let error = NosqliteError::CollectionNotFound(format!(
+            "Collection '{}' not found",
+            collection_name
+        ));
+        handler.log_error(error.clone());
+        error
+    })?;
+
+    let records = match format {
+        ExportFormat::Json => read_json(reader),
+        ExportFormat::Ndjson => read_ndjson(reader),
+        ExportFormat::Csv => read_csv(reader, &collection.structure),
+    }
+    .and_then(|records| {
+        for (position, record) in records.iter().enumerate() {
+            check_record(record, &collection.structure, position + 1)?;
+        }
+        Ok(records)
+    });
+    let records = match records {
+        Ok(records) => records,
+        Err(error) => {
+            handler.log_error(error.clone());
+            return Err(error);
+        }
+    };
+
+    let count = records.len();
+    for record in records {
+        collection.add_document(record, handler)?;
+    }
+    Ok(count)
+}
+
+/// Maps a failure to write JSON: an I/O error of the writer, or else a serialization error.
+fn json_write_error(error: serde_json::Error) -> NosqliteError {
+    match error.is_io() {
+        true => NosqliteError::IoError(error.to_string()),
+        false => NosqliteError::SerializationError(error.to_string()),
+    }
+}
+
+/// Maps a CSV failure: an I/O error of the reader or writer, or else a parse error.
+fn csv_error(error: csv::Error) -> NosqliteError {
+    match error.is_io_error() {
+        true => NosqliteError::IoError(error.to_string()),
+        false => NosqliteError::DeserializationError(error.to_string()),
+    }
+}
+
+/// Writes records as CSV, one column per dot path.
+fn write_csv(records: &[&Value], writer: &mut impl Write) -> Result<(), NosqliteError> {
+    let rows: Vec<Vec<(String, String)>> = records
+        .iter()
+        .map(|record| {
+            let mut cells = Vec::new();
+            flatten("", record, &mut cells);
+            cells
+        })
+        .collect();
+    let mut columns: Vec<&str> = Vec::new();
+    for (path, _) in rows.iter().flatten() {
+        if !columns.contains(&path.as_str()) {
+            columns.push(path);
+        }
+    }
+
+    let mut csv = csv::Writer::from_writer(writer);
+    if !columns.is_empty() {
+        csv.write_record(&columns).map_err(csv_error)?;
+    }
+    for cells in &rows {
+        let row = columns.iter().map(|column| {
+            cells
+                .iter()
+                .find(|(path, _)| path == column)
+                .map_or("", |(_, cell)| cell.as_str())
+        });
+        csv.write_record(row).map_err(csv_error)?;
+    }
+    csv.flush().map_err(NosqliteError::from)
+}
+
+/// Collects the cells of a value under `prefix`, recursing into non-empty objects.
+fn flatten(prefix: &str, value: &Value, cells: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) if !fields.is_empty() => {
+            for (key, field) in fields {
+                let path = match prefix.is_empty() {
+                    true => key.clone(),
+                    false => format!("{}.{}", prefix, key),
+                };
+                flatten(&path, field, cells);
+            }
+        }
+        Value::Object(_) if prefix.is_empty() => {}
+        Value::String(text) => cells.push((prefix.to_string(), text.clone())),
+        Value::Null => cells.push((prefix.to_string(), String::new())),
+        other => cells.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+/// Reads a JSON array of records.
+fn read_json(reader: impl Read) -> Result<Vec<Value>, NosqliteError> {
+    match serde_json::from_reader(reader) {
+        Ok(Value::Array(records)) => Ok(records),
+        Ok(_) => Err(NosqliteError::DeserializationError(
+            "Expected a JSON array of documents".to_string(),
+        )),
+        Err(e) if e.is_io() => Err(NosqliteError::IoError(e.to_string())),
+        Err(e) => Err(NosqliteError::DeserializationError(e.to_string())),
+    }
+}
+
+/// Reads one JSON record per line, skipping blank lines.
+fn read_ndjson(reader: impl Read) -> Result<Vec<Value>, NosqliteError> {
+    let mut records = Vec::new();
+    for (position, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line).map_err(|e| {
+            NosqliteError::DeserializationError(format!("Line {}: {}", position + 1, e))
+        })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Reads CSV rows into records, typing each cell by the `structure` at its column path.
+fn read_csv(reader: impl Read, structure: &Value) -> Result<Vec<Value>, NosqliteError> {
+    let mut csv = csv::Reader::from_reader(reader);
+    let columns: Vec<String> = csv
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(str::to_string)
+        .collect();
+
+    let mut records = Vec::new();
+    for row in csv.records() {
+        let row = row.map_err(csv_error)?;
+        let mut record = Map::new();
+        for (path, cell) in columns.iter().zip(row.iter()) {
+            if cell.is_empty() {
+                continue;
+            }
+            let value = parse_cell(cell, structure_type(structure, path));
+            unflatten(&mut record, path, value);
+        }
+        records.push(Value::Object(record));
+    }
+    Ok(records)
+}
+
+/// Returns the type the `structure` gives a dot path, if any.
+fn structure_type<'a>(structure: &'a Value, path: &str) -> Option<&'a str> {
+    path.split('.')
+        .try_fold(structure, |node, key| node.get(key))?
+        .as_str()
+}
+
+/// Reads a CSV cell as text for `"string"` fields, and as JSON otherwise when it parses.
+fn parse_cell(cell: &str, expected: Option<&str>) -> Value {
+    let text = || Value::String(cell.to_string());
+    match expected.map(str::to_lowercase).as_deref() {
+        Some("string") => text(),
+        Some(_) => serde_json::from_str(cell).unwrap_or_else(|_| text()),
+        None => match serde_json::from_str(cell) {
+            Ok(
+                value @ (Value::Number(_) | Value::Bool(_) | Value::Array(_) | Value::Object(_)),
+            ) => value,
+            _ => text(),
+        },
+    }
+}
+
+/// Sets `value` at a dot path of `record`, creating the intermediate objects.
+fn unflatten(record: &mut Map<String, Value>, path: &str, value: Value) {
+    match path.split_once('.') {
+        Some((key, rest)) => {
+            let entry = record
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = Value::Object(Map::new());
+            }
+            if let Value::Object(fields) = entry {
+                unflatten(fields, rest, value);
+            }
+        }
+        None => {
+            record.insert(path.to_string(), value);
+        }
+    }
+}
+
+/// Checks a record the way [`Collection::add_document`](crate::engine::models::Collection::add_document)
+/// does, naming the record in the error.
+fn check_record(record: &Value, structure: &Value, position: usize) -> Result<(), NosqliteError> {
+    let Value::Object(structure) = structure else {
+        return Err(NosqliteError::InvalidCollectionStructure(
+            "Collection structure is not a valid JSON object".into(),
+        ));
+    };
+    match record {
+        Value::Object(fields) if validate_against_structure(fields, structure) => Ok(()),
+        Value::Object(_) => Err(NosqliteError::DocumentInvalid(format!(
+            "Record {} does not match the collection's structure",
+            position
+        ))),
+        _ => Err(NosqliteError::DocumentInvalid(format!(
+            "Record {} must be a JSON object",
+            position
+        ))),
+    }
+}
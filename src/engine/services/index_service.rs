@@ -0,0 +1,146 @@
+use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
+use crate::engine::models::database::model::Database;
+use crate::engine::models::index::model::{Index, IndexKind};
+
+/// 🦀
+/// Creates a secondary index on a collection and builds it from the existing documents.
+///
+/// This function locates the collection and delegates to [`Collection::add_index`].
+/// Any failure (missing collection, duplicate name, invalid definition) is logged and returned.
+///
+/// # Parameters
+///
+/// - `db`: A mutable reference to the [`Database`] instance.
+/// - `collection_name`: The name of the collection to index.
+/// - `index_name`: The name of the new index, unique within the collection.
+/// - `kind`: The [`IndexKind`] describing what to index.
+/// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for logging errors.
+///
+/// # Returns
+///
+/// - `Ok(())` if the index was created.
+/// - `Err(NosqliteError)` if the collection is missing or the index cannot be created.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::models::{Database, IndexKind};
+/// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+/// use nosqlite_rust::engine::services::index_service::create_index;
+///
+/// let mut db = Database::new("temp/data47.nosqlite");
+/// let mut handler = NosqliteErrorHandler::new("temp/data47.nosqlite".to_string());
+/// db.add_collection("articles", json!({ "body": "string" }), &mut handler)?;
+/// create_index(
+///     &mut db,
+///     "articles",
+///     "content",
+///     IndexKind::Text { fields: vec!["body".to_string()] },
+///     &mut handler,
+/// )?;
+/// Ok::<(), NosqliteError>(())
+/// ```
+///
+/// # See Also
+///
+/// - [`drop_index`] — to remove an index
+/// - [`Collection::add_index`] — the underlying logic
+///
+/// [`Collection::add_index`]: crate::engine::models::Collection::add_index
+pub fn create_index(
+    db: &mut Database,
+    collection_name: &str,
+    index_name: &str,
+    kind: IndexKind,
+    handler: &mut NosqliteErrorHandler,
+) -> Result<(), NosqliteError> {
+    let collection = db.get_collection_mut(collection_name).ok_or_else(|| {
+        let error = NosqliteError::CollectionNotFound(format!(
+            "Collection '{}' not found",
+            collection_name
+        ));
+        handler.log_error(error.clone());
+        error
+    })?;
+
+    collection.add_index(index_name, kind, handler)
+}
+
+/// 🦀
+/// Removes a secondary index from a collection.
+///
+/// # Parameters
+///
+/// - `db`: A mutable reference to the [`Database`] instance.
+/// - `collection_name`: The name of the collection.
+/// - `index_name`: The name of the index to drop.
+/// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for logging errors.
+///
+/// # Returns
+///
+/// - `Ok(())` if the index was removed.
+/// - `Err(NosqliteError)` if the collection or the index does not exist.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::models::{Database, IndexKind};
+/// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+/// use nosqlite_rust::engine::services::index_service::{create_index, drop_index};
+///
+/// let mut db = Database::new("temp/data48.nosqlite");
+/// let mut handler = NosqliteErrorHandler::new("temp/data48.nosqlite".to_string());
+/// db.add_collection("articles", json!({}), &mut handler)?;
+/// create_index(&mut db, "articles", "content", IndexKind::Text { fields: vec!["body".to_string()] }, &mut handler)?;
+/// drop_index(&mut db, "articles", "content", &mut handler)?;
+/// Ok::<(), NosqliteError>(())
+/// ```
+pub fn drop_index(
+    db: &mut Database,
+    collection_name: &str,
+    index_name: &str,
+    handler: &mut NosqliteErrorHandler,
+) -> Result<(), NosqliteError> {
+    let collection = db.get_collection_mut(collection_name).ok_or_else(|| {
+        let error = NosqliteError::CollectionNotFound(format!(
+            "Collection '{}' not found",
+            collection_name
+        ));
+        handler.log_error(error.clone());
+        error
+    })?;
+
+    collection.remove_index(index_name, handler)
+}
+
+/// 🦀
+/// Lists the secondary indexes of a collection.
+///
+/// # Parameters
+///
+/// - `db`: A reference to the [`Database`] instance.
+/// - `collection_name`: The name of the collection.
+/// - `handler`: A mutable reference to a [`NosqliteErrorHandler`] for logging errors.
+///
+/// # Returns
+///
+/// - `Ok(&Vec<Index>)` with the indexes of the collection.
+/// - `Err(NosqliteError::CollectionNotFound)` if the collection does not exist.
+pub fn list_indexes<'a>(
+    db: &'a Database,
+    collection_name: &str,
+    handler: &mut NosqliteErrorHandler,
+) -> Result<&'a Vec<Index>, NosqliteError> {
+    let collection = db.get_collection(collection_name).ok_or_else(|| {
+        let error = NosqliteError::CollectionNotFound(format!(
+            "Collection '{}' not found",
+            collection_name
+        ));
+        handler.log_error(error.clone());
+        error
+    })?;
+
+    Ok(&collection.indexes)
+}
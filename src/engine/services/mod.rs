@@ -5,3 +5,6 @@
 pub mod collection_service;
 pub mod database_service;
 pub mod document_service;
+pub mod export_service;
+pub mod index_service;
+pub mod recovery_service;
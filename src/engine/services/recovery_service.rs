@@ -0,0 +1,294 @@
+use crate::engine::error::{NosqliteError, NosqliteErrorHandler};
+use crate::engine::models::collection::model::Collection;
+use crate::engine::models::database::model::Database;
+use crate::engine::models::document::model::Document;
+use crate::engine::models::file::model::{EncryptionKey, File, FileHeader};
+use crate::engine::models::index::model::Index;
+use crate::engine::models::integrity::model::IntegrityReport;
+use crate::engine::models::storage::model::StorageBackend;
+use crate::engine::services::database_service::migrate_database;
+use serde_json::Value;
+
+/// 🦀
+/// Reads everything that can still be read from a stored database, and reports the rest.
+///
+/// Unlike [`load_database_from_backend`](crate::engine::services::database_service::load_database_from_backend),
+/// which fails on the first damaged byte, each part of the database is read on its own:
+///
+/// - the data file, or with the segmented [`Layout`](crate::engine::models::Layout) its catalog
+///   and then each segment, whose checksum is verified;
+/// - each collection, and if it cannot be read as a whole, each of its documents and indexes;
+/// - the write-ahead log, whose records are replayed up to the first corrupted one.
+///
+/// Every part that cannot be read is recorded as an issue of the returned [`IntegrityReport`] and
+/// skipped. An unencrypted part that fails its checksum is recorded too, but still read if it
+/// parses. Nothing is written to the backend.
+///
+/// Encryption authenticates a data file or segment as a whole, so one damaged byte of an encrypted
+/// part makes all of it unreadable. With the single [`Layout`](crate::engine::models::Layout), that
+/// loses the whole data file and only the write-ahead log is replayed; the segmented layout confines
+/// the loss to the collection of the damaged segment.
+///
+/// # Parameters
+///
+/// - `backend`: The [`StorageBackend`] holding the database.
+/// - `key`: The [`EncryptionKey`] of the database.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok((Database, IntegrityReport))` with every collection loaded, and what was skipped.
+/// - `Err(NosqliteError::DatabaseNotFound)` if the backend holds neither a data file nor a log.
+/// - `Err(NosqliteError)` if the backend itself cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+/// use nosqlite_rust::engine::error::{NosqliteErrorHandler, NosqliteError};
+/// use nosqlite_rust::engine::models::{Database, EncryptionKey, Layout, MemoryBackend, StorageBackend};
+/// use nosqlite_rust::engine::services::database_service::save_database_to_backend;
+/// use nosqlite_rust::engine::services::recovery_service::salvage_database;
+///
+/// let mut handler = NosqliteErrorHandler::new("temp/data77.nosqlite".to_string());
+/// let key = EncryptionKey::new([7u8; 32], None);
+/// let mut backend = MemoryBackend::new("shop");
+/// let mut db = Database::default();
+/// db.add_collection("users", json!({}), &mut handler)?;
+/// db.add_collection("orders", json!({}), &mut handler)?;
+/// save_database_to_backend(&mut backend, &mut db, &key, Layout::Segmented, &mut handler)?;
+///
+/// let lost = backend.segments()[0].clone();
+/// backend.save_segment(&lost, b"garbage", &mut handler)?;
+///
+/// let (db, report) = salvage_database(&mut backend, &key, &mut handler)?;
+/// assert_eq!(db.collections.len(), 1);
+/// assert_eq!(report.issues.len(), 1);
+/// Ok::<(), NosqliteError>(())
+/// ```
+pub fn salvage_database(
+    backend: &mut dyn StorageBackend,
+    key: &EncryptionKey,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<(Database, IntegrityReport), NosqliteError> {
+    let name = backend.name().to_string();
+    let content = backend.load(error_handler)?;
+    let log = backend.load_log(error_handler)?;
+    if content.is_none() && log.is_none() {
+        let error = NosqliteError::DatabaseNotFound(name);
+        error_handler.log_error(error.clone());
+        return Err(error);
+    }
+
+    let mut db = Database::new(&name);
+    let mut report = IntegrityReport::default();
+    let decoded = content.and_then(|content| {
+        decode_content(
+            &name,
+            &content,
+            key,
+            "data file",
+            &mut report,
+            error_handler,
+        )
+    });
+    match decoded {
+        Some((header, payload)) if !header.layout.is_single() => {
+            match File::catalog_from_value(payload, error_handler) {
+                Ok(catalog) => {
+                    db.wal_sequence = catalog.wal_sequence;
+                    for entry in catalog.collections {
+                        let location = format!("segment {} ({})", entry.segment, entry.name);
+                        if let Some(value) = salvage_segment(
+                            backend,
+                            &entry.segment,
+                            key,
+                            &location,
+                            &mut report,
+                            error_handler,
+                        ) {
+                            db.collections.extend(salvage_collection(
+                                value,
+                                &location,
+                                &mut report,
+                            ));
+                        }
+                    }
+                }
+                Err(error) => report.add_issue("data file", error),
+            }
+        }
+        Some((header, mut payload)) => {
+            match migrate_database(&mut payload, header.version, error_handler) {
+                Ok(_) => {
+                    db.wal_sequence = payload["wal_sequence"].as_u64().unwrap_or(0);
+                    let collections = match payload["collections"].take() {
+                        Value::Array(collections) => collections,
+                        _ => Vec::new(),
+                    };
+                    for (position, value) in collections.into_iter().enumerate() {
+                        let location = match value["name"].as_str() {
+                            Some(name) => format!("collection '{}'", name),
+                            None => format!("collection {}", position + 1),
+                        };
+                        db.collections
+                            .extend(salvage_collection(value, &location, &mut report));
+                    }
+                }
+                Err(error) => report.add_issue("data file", error),
+            }
+        }
+        None => {}
+    }
+
+    if let Some(content) = log {
+        let wal_name = File::wal_path(&name);
+        let (records, corrupted) = File::decode_wal_prefix(&wal_name, &content, key, error_handler);
+        for record in records {
+            if record.sequence <= db.wal_sequence {
+                continue;
+            }
+            match record.operation.apply(&mut db, error_handler) {
+                Ok(()) => report.wal_records += 1,
+                Err(error) => report.add_issue(
+                    format!("write-ahead log, record {}", record.sequence),
+                    error,
+                ),
+            }
+            db.wal_sequence = record.sequence;
+        }
+        if let Some(error) = corrupted {
+            report.add_issue("write-ahead log", error);
+        }
+    }
+
+    report.collections = db.collections.len();
+    report.documents = db.collections.iter().map(|c| c.documents.len()).sum();
+    Ok((db, report))
+}
+
+/// 🦀
+/// Checks that every part of a stored database can be read, without keeping its content.
+///
+/// # Parameters
+///
+/// - `backend`: The [`StorageBackend`] holding the database.
+/// - `key`: The [`EncryptionKey`] of the database.
+/// - `error_handler`: A mutable reference to a [`NosqliteErrorHandler`] for structured logging.
+///
+/// # Returns
+///
+/// - `Ok(IntegrityReport)` listing the damaged parts, if any.
+/// - `Err(NosqliteError)` for the same failures as [`salvage_database`].
+pub fn verify_database(
+    backend: &mut dyn StorageBackend,
+    key: &EncryptionKey,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Result<IntegrityReport, NosqliteError> {
+    salvage_database(backend, key, error_handler).map(|(_, report)| report)
+}
+
+/// Decodes the content of a data file or segment, recording a failure in `report`. A checksum
+/// mismatch is recorded too, but the content is then decoded anyway, in case it still parses.
+fn decode_content(
+    name: &str,
+    content: &[u8],
+    key: &EncryptionKey,
+    location: &str,
+    report: &mut IntegrityReport,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Option<(FileHeader, Value)> {
+    match File::decode_payload(name, content, key, error_handler) {
+        Ok(decoded) => Some(decoded),
+        Err(error) => {
+            let corrupted = matches!(error, NosqliteError::Corrupted(_));
+            report.add_issue(location, error);
+            match corrupted {
+                true => File::decode_unverified_payload(name, content, key, error_handler).ok(),
+                false => None,
+            }
+        }
+    }
+}
+
+/// Reads, decodes and migrates a segment into the JSON of its collection, recording a failure in
+/// `report`.
+fn salvage_segment(
+    backend: &mut dyn StorageBackend,
+    id: &str,
+    key: &EncryptionKey,
+    location: &str,
+    report: &mut IntegrityReport,
+    error_handler: &mut NosqliteErrorHandler,
+) -> Option<Value> {
+    let name = format!("{} (segment {})", backend.name(), id);
+    let content = match backend.load_segment(id, error_handler) {
+        Ok(Some(content)) => content,
+        Ok(None) => {
+            let error = NosqliteError::InvalidDatabaseFormat(format!("{} is missing", name));
+            error_handler.log_error(error.clone());
+            report.add_issue(location, error);
+            return None;
+        }
+        Err(error) => {
+            report.add_issue(location, error);
+            return None;
+        }
+    };
+    let (header, payload) = decode_content(&name, &content, key, location, report, error_handler)?;
+    let mut payload = serde_json::json!({ "collections": [payload] });
+    if let Err(error) = migrate_database(&mut payload, header.version, error_handler) {
+        report.add_issue(location, error);
+        return None;
+    }
+    Some(payload["collections"][0].take())
+}
+
+/// Reads a collection as a whole, or else document by document and index by index, recording
+/// what is skipped in `report`. Returns `None` if not even its name and structure can be read.
+fn salvage_collection(
+    mut value: Value,
+    location: &str,
+    report: &mut IntegrityReport,
+) -> Option<Collection> {
+    if let Ok(mut collection) = serde_json::from_value::<Collection>(value.clone()) {
+        collection.rebuild_indexes();
+        return Some(collection);
+    }
+
+    let Some(name) = value["name"].as_str() else {
+        report.add_issue(
+            location,
+            NosqliteError::Corrupted("the collection has no name".to_string()),
+        );
+        return None;
+    };
+    let mut collection = Collection::new(name.to_string(), value["structure"].take());
+    if let Some(created_at) = value["created_at"].as_u64() {
+        collection.created_at = created_at;
+    }
+    if let Value::Array(documents) = value["documents"].take() {
+        for (position, document) in documents.into_iter().enumerate() {
+            match serde_json::from_value::<Document>(document) {
+                Ok(document) => collection.documents.push(document),
+                Err(e) => report.add_issue(
+                    format!("{}, document {}", location, position + 1),
+                    NosqliteError::Corrupted(e.to_string()),
+                ),
+            }
+        }
+    }
+    if let Value::Array(indexes) = value["indexes"].take() {
+        for (position, index) in indexes.into_iter().enumerate() {
+            match serde_json::from_value::<Index>(index) {
+                Ok(index) => collection.indexes.push(index),
+                Err(e) => report.add_issue(
+                    format!("{}, index {}", location, position + 1),
+                    NosqliteError::Corrupted(e.to_string()),
+                ),
+            }
+        }
+    }
+    collection.rebuild_indexes();
+    Some(collection)
+}
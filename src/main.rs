@@ -71,5 +71,8 @@ use tempfile::NamedTempFile;
 // }
 
 fn main() {
+    if let Some(code) = cli::subcommands::dispatch() {
+        std::process::exit(code);
+    }
     cli::repl::start_repl();
 }
@@ -0,0 +1,54 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+#[test]
+fn test_repl_backup_should_write_a_copy_of_the_database() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+    let backup = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain");
+    cmd.write_stdin(format!(
+        r#"
+        db.createCollection("testCollection");
+        db.insertDocument("testCollection", {{ "name": "Alice" }});
+        .backup {backup}
+        .exit
+        "#
+    ))
+    .assert()
+    .stdout(contains(format!("Backup written to '{backup}'")));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&backup).arg("--plain");
+    cmd.write_stdin("db.findDocuments(\"testCollection\");\n.exit\n")
+        .assert()
+        .stdout(contains("Alice"));
+}
+
+#[test]
+fn test_repl_restore_should_bring_back_a_snapshot() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        .snapshot empty
+        db.insertDocument("testCollection", { "name": "Alice" });
+        .snapshots
+        .restore empty
+        db.findDocuments("testCollection");
+        .restore missing
+        .exit
+        "#,
+    )
+    .assert()
+    .stdout(contains("Snapshot 'empty' created successfully"))
+    .stdout(contains("empty\n"))
+    .stdout(contains("Snapshot 'empty' restored successfully"))
+    .stdout(contains("Alice").not())
+    .stderr(contains("Snapshot not found: `missing`"));
+}
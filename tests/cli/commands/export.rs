@@ -0,0 +1,46 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+#[test]
+fn test_repl_export_and_import_should_copy_documents() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+    let export = format!("./temp/test_export_{}.csv", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain");
+    cmd.write_stdin(format!(
+        r#"
+        db.createCollection("source");
+        db.insertDocument("source", {{ "name": "Alice", "address": {{ "city": "Paris" }} }});
+        .export source {export}
+        db.createCollection("target");
+        .import target {export}
+        db.findDocuments("target");
+        .exit
+        "#
+    ))
+    .assert()
+    .stdout(contains("1 document(s) exported"))
+    .stdout(contains("1 document(s) imported into 'target'"))
+    .stdout(contains("Paris"));
+
+    let content = std::fs::read_to_string(&export).unwrap();
+    assert!(content.starts_with("address.city,name\n"));
+}
+
+#[test]
+fn test_repl_import_should_reject_unknown_format() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("target");
+        .import target ./temp/documents.txt
+        .exit
+        "#,
+    )
+    .assert()
+    .stderr(contains("Usage: .import"));
+}
@@ -138,3 +138,25 @@ fn test_repl_find_documents_should_fail() {
     .assert()
     .stderr(contains("Error: Error retrieving documents, Collection not found: `Collection \'nonExistentCollection\' not found`"));
 }
+
+#[test]
+fn test_repl_find_documents_explain_should_succeed() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path);
+
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        db.insertDocument("testCollection", { "name": "John Doe", "age": 30 });
+        db.insertDocument("testCollection", { "name": "Jane Doe", "age": 25 });
+        db.findDocuments("testCollection", { "age": 30 }).explain();
+        .exit
+        "#,
+    )
+    .assert()
+    .stdout(contains("Access path: FULL SCAN"))
+    .stdout(contains("Documents examined: 2"))
+    .stdout(contains("Documents returned: 1"));
+}
@@ -1,4 +1,7 @@
+pub mod backup;
 pub mod create_collection;
+pub mod export;
 pub mod find_documents;
 pub mod insert_document;
 pub mod list_collections;
+pub mod vacuum;
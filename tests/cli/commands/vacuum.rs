@@ -0,0 +1,24 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+#[test]
+fn test_repl_vacuum_should_report_reclaimed_bytes() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        db.insertDocument("testCollection", { "name": "Alice" });
+        .vacuum
+        db.findDocuments("testCollection");
+        .exit
+        "#,
+    )
+    .assert()
+    .stdout(contains("Size before:"))
+    .stdout(contains("Reclaimed:"))
+    .stdout(contains("Indexes rebuilt: 0"))
+    .stdout(contains("Alice"));
+}
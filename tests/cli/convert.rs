@@ -0,0 +1,57 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+#[test]
+fn test_convert_should_switch_between_plain_and_encrypted() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        .exit
+        "#,
+    )
+    .assert()
+    .success();
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("convert")
+        .arg(&path)
+        .arg("--to=plain")
+        .arg("--key-sidecar")
+        .assert()
+        .success()
+        .stdout(contains("converted to plain successfully"));
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("\"testCollection\""));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain");
+    cmd.write_stdin("db.listCollections();\n.exit\n")
+        .assert()
+        .stdout(contains("testCollection"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("convert")
+        .arg(&path)
+        .arg("--to=encrypted")
+        .arg("--key-sidecar")
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(!content.contains("testCollection"));
+}
+
+#[test]
+fn test_convert_without_target_should_fail() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("convert")
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(contains("Usage: nosqlite-cli convert"));
+}
@@ -0,0 +1,158 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+#[test]
+fn test_repl_key_env_flag_should_detect_wrong_key() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path)
+        .arg("--key-env=NOSQLITE_CLI_TEST_KEY")
+        .env("NOSQLITE_CLI_TEST_KEY", "11".repeat(32));
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        .exit
+        "#,
+    )
+    .assert()
+    .stdout(contains("Collection 'testCollection' created successfully"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path)
+        .arg("--key-env=NOSQLITE_CLI_TEST_KEY")
+        .env("NOSQLITE_CLI_TEST_KEY", "22".repeat(32));
+    cmd.write_stdin(".exit\n")
+        .assert()
+        .stderr(contains("Failed to open or create database: Key mismatch"));
+}
+
+#[test]
+fn test_repl_key_sidecar_flag_should_create_key_next_to_database() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        .exit
+        "#,
+    )
+    .assert()
+    .success();
+
+    assert!(std::path::Path::new(&path.replace(".nosqlite", ".key")).exists());
+}
+
+#[test]
+fn test_repl_passphrase_env_flag_should_unlock_database() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path)
+        .arg("--passphrase-env=NOSQLITE_CLI_TEST_PASSPHRASE")
+        .arg("--kdf=pbkdf2:1000")
+        .env("NOSQLITE_CLI_TEST_PASSPHRASE", "correct horse");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        .exit
+        "#,
+    )
+    .assert()
+    .stdout(contains("Collection 'testCollection' created successfully"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path)
+        .arg("--passphrase-env=NOSQLITE_CLI_TEST_PASSPHRASE")
+        .env("NOSQLITE_CLI_TEST_PASSPHRASE", "wrong horse");
+    cmd.write_stdin(".exit\n")
+        .assert()
+        .stderr(contains("Failed to open or create database: Key mismatch"));
+}
+
+#[test]
+fn test_repl_readonly_flag_should_refuse_mutations() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--readonly");
+    cmd.write_stdin(".exit\n")
+        .assert()
+        .stderr(contains("Database not found"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar");
+    cmd.write_stdin("db.createCollection(\"testCollection\");\n.exit\n")
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar").arg("--readonly");
+    cmd.write_stdin(
+        r#"
+        db.listCollections();
+        db.createCollection("otherCollection");
+        .exit
+        "#,
+    )
+    .assert()
+    .stdout(contains("testCollection"))
+    .stderr(contains("Read-only database"));
+    assert!(!std::path::Path::new(&path.replace(".nosqlite", ".log")).exists());
+}
+
+#[test]
+fn test_repl_encoding_flag_should_write_binary_data_file() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain").arg("--encoding=msgpack");
+    cmd.write_stdin("db.createCollection(\"testCollection\");\n.exit\n")
+        .assert()
+        .success();
+    let content = std::fs::read(&path).unwrap();
+    assert!(content.starts_with(b"NOSQLITE "));
+    assert!(String::from_utf8_lossy(&content).contains(r#""encoding":"messagepack""#));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain").arg("--readonly");
+    cmd.write_stdin("db.listCollections();\n.exit\n")
+        .assert()
+        .stdout(contains("testCollection"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain").arg("--encoding=xml");
+    cmd.write_stdin(".exit\n")
+        .assert()
+        .stderr(contains("unknown encoding 'xml'"));
+}
+
+#[test]
+fn test_repl_layout_flag_should_write_one_segment_per_collection() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain").arg("--layout=segmented");
+    cmd.write_stdin("db.createCollection(\"users\");\ndb.createCollection(\"orders\");\n.exit\n")
+        .assert()
+        .success();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains(r#""layout":"segmented""#));
+    let segments = path.replace(".nosqlite", ".segments");
+    assert_eq!(std::fs::read_dir(&segments).unwrap().count(), 2);
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain").arg("--readonly");
+    cmd.write_stdin("db.listCollections();\n.exit\n")
+        .assert()
+        .stdout(contains("users"))
+        .stdout(contains("orders"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain").arg("--layout=pages");
+    cmd.write_stdin(".exit\n")
+        .assert()
+        .stderr(contains("unknown layout 'pages'"));
+}
@@ -0,0 +1,65 @@
+use assert_cmd::Command;
+use nosqlite_rust::engine::models::{KeySource, NosqliteOptions};
+use nosqlite_rust::engine::Nosqlite;
+use predicates::str::contains;
+
+#[test]
+fn test_repl_should_refuse_database_open_in_another_process() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+    let options = NosqliteOptions::default().with_key_source(KeySource::Sidecar);
+    let mut db = Nosqlite::open_with_options(&path, options).unwrap();
+    db.create_collection("testCollection", serde_json::json!({}))
+        .unwrap();
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar");
+    cmd.write_stdin("db.listCollections();\n.exit\n")
+        .assert()
+        .stderr(contains(
+            "Failed to open or create database: Database locked",
+        ));
+
+    drop(db);
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar").arg("--lock-timeout=1");
+    cmd.write_stdin("db.listCollections();\n.exit\n")
+        .assert()
+        .stdout(contains("testCollection"));
+}
+
+#[test]
+fn test_rekey_should_wait_for_lock_timeout() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+    let options = NosqliteOptions::default().with_key_source(KeySource::Sidecar);
+    let mut db = Nosqlite::open_with_options(&path, options).unwrap();
+    db.create_collection("testCollection", serde_json::json!({}))
+        .unwrap();
+
+    let holder = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        drop(db);
+    });
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("rekey")
+        .arg(&path)
+        .arg("--key-sidecar")
+        .arg(format!(
+            "--new-key-file={}",
+            path.replace(".nosqlite", ".new.key")
+        ))
+        .arg("--lock-timeout=10")
+        .assert()
+        .success();
+    holder.join().unwrap();
+}
+
+#[test]
+fn test_repl_should_reject_out_of_range_lock_timeout() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain").arg("--lock-timeout=1e300");
+    cmd.write_stdin(".exit\n")
+        .assert()
+        .stderr(contains("Invalid options: invalid lock timeout '1e300'"));
+}
@@ -1 +1,7 @@
 pub mod commands;
+pub mod convert;
+pub mod flags;
+pub mod lock;
+pub mod recover;
+pub mod rekey;
+pub mod stats;
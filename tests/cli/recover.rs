@@ -0,0 +1,58 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+#[test]
+fn test_recover_should_copy_a_database_into_a_new_file_with_a_report() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+    let output = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        db.insertDocument("testCollection", { "name": "Alice" });
+        .exit
+        "#,
+    )
+    .assert()
+    .success();
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("recover")
+        .arg(&path)
+        .arg(&output)
+        .arg("--key-sidecar")
+        .assert()
+        .success()
+        .stdout(contains("Collections: 1"))
+        .stdout(contains("Documents: 1"))
+        .stdout(contains("No issues found"))
+        .stdout(contains("Recovered"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&output).arg("--key-sidecar");
+    cmd.write_stdin("db.findDocuments(\"testCollection\");\n.exit\n")
+        .assert()
+        .stdout(contains("Alice"));
+}
+
+#[test]
+fn test_recover_should_refuse_to_overwrite_an_existing_file() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--plain");
+    cmd.write_stdin("db.createCollection(\"testCollection\");\n.exit\n")
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("recover")
+        .arg(&path)
+        .arg(&path)
+        .arg("--plain")
+        .assert()
+        .failure()
+        .stderr(contains("Output file already exists"));
+}
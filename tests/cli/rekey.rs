@@ -0,0 +1,52 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+#[test]
+fn test_rekey_should_reencrypt_database() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+    let new_key = path.replace(".nosqlite", ".new.key");
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        .exit
+        "#,
+    )
+    .assert()
+    .success();
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("rekey")
+        .arg(&path)
+        .arg("--key-sidecar")
+        .arg(format!("--new-key-file={new_key}"))
+        .assert()
+        .success()
+        .stdout(contains("re-encrypted successfully"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg("--key-sidecar");
+    cmd.write_stdin(".exit\n")
+        .assert()
+        .stderr(contains("Failed to open or create database: Key mismatch"));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path).arg(format!("--key-file={new_key}"));
+    cmd.write_stdin("db.listCollections();\n.exit\n")
+        .assert()
+        .stdout(contains("testCollection"));
+}
+
+#[test]
+fn test_rekey_without_new_key_should_fail() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("rekey")
+        .arg(&path)
+        .assert()
+        .failure()
+        .stderr(contains("a new key is required"));
+}
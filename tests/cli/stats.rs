@@ -0,0 +1,40 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+
+#[test]
+fn test_stats_should_report_compression_ratio() {
+    let path = format!("./temp/test_db_{}.nosqlite", rand::random::<u64>());
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg(&path)
+        .arg("--key-sidecar")
+        .arg("--compression=zstd");
+    cmd.write_stdin(
+        r#"
+        db.createCollection("testCollection");
+        db.insertDocument("testCollection", { "name": "Alice", "role": "admin" });
+        db.insertDocument("testCollection", { "name": "Alice", "role": "admin" });
+        db.insertDocument("testCollection", { "name": "Alice", "role": "admin" });
+        .exit
+        "#,
+    )
+    .assert()
+    .success();
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("stats")
+        .arg(&path)
+        .arg("--key-sidecar")
+        .assert()
+        .success()
+        .stdout(contains("Compression: Zstd"))
+        .stdout(contains("Compression ratio: "));
+
+    let mut cmd = Command::cargo_bin("nosqlite-cli").unwrap();
+    cmd.arg("stats")
+        .arg(&path)
+        .arg("--compression=lz4")
+        .assert()
+        .failure()
+        .stderr(contains("unknown compression 'lz4'"));
+}
@@ -1,4 +1,7 @@
-use nosqlite_rust::engine::{error::NosqliteErrorHandler, models::Collection};
+use nosqlite_rust::engine::{
+    error::{NosqliteError, NosqliteErrorHandler},
+    models::{Collection, IndexKind},
+};
 use serde_json::json;
 use tempfile::NamedTempFile;
 
@@ -278,3 +281,55 @@ fn delete_nonexistent_documents_should_fail() {
     let res = col.delete_documents("not-found-id", &json!("not-found"), &mut handler);
     assert!(res.is_err());
 }
+
+#[test]
+fn update_and_delete_should_ignore_expired_documents() {
+    let mut col = make_collection();
+    let mut handler = make_error_handler();
+    col.add_document(sample_doc(), &mut handler).unwrap();
+    col.add_index(
+        "expiry",
+        IndexKind::Ttl {
+            field: "$updated_at".to_string(),
+            expire_after_secs: 60,
+        },
+        &mut handler,
+    )
+    .unwrap();
+    col.documents[0].updated_at = 0;
+    col.rebuild_indexes();
+    assert!(col.live_documents().is_empty());
+
+    let result =
+        col.update_documents_field("field", &json!("val"), "seen", json!(true), &mut handler);
+    assert!(matches!(result, Err(NosqliteError::DocumentNotFound(_))));
+    let result = col.update_documents("field", &json!("val"), sample_doc(), &mut handler);
+    assert!(matches!(result, Err(NosqliteError::DocumentNotFound(_))));
+    assert_eq!(col.documents[0].updated_at, 0);
+    assert!(col.live_documents().is_empty());
+
+    let result = col.delete_documents("field", &json!("val"), &mut handler);
+    assert!(matches!(result, Err(NosqliteError::DocumentNotFound(_))));
+}
+
+#[test]
+fn document_position_should_follow_removals_and_deserialization() {
+    let mut col = make_collection();
+    let mut handler = make_error_handler();
+    for value in ["a", "b", "c"] {
+        col.add_document(json!({ "field": value }), &mut handler)
+            .unwrap();
+    }
+    let ids: Vec<String> = col.documents.iter().map(|doc| doc.id.clone()).collect();
+    assert_eq!(col.document_position(&ids[2]), Some(2));
+
+    col.remove_documents(&ids[..1]);
+    assert_eq!(col.document_position(&ids[0]), None);
+    assert_eq!(col.document_position(&ids[2]), Some(1));
+
+    let mut reloaded: Collection =
+        serde_json::from_str(&serde_json::to_string(&col).unwrap()).unwrap();
+    assert_eq!(reloaded.document_position(&ids[2]), Some(1));
+    reloaded.rebuild_indexes();
+    assert_eq!(reloaded.document_position(&ids[1]), Some(0));
+}
@@ -1,6 +1,10 @@
-use nosqlite_rust::engine::error::NosqliteErrorHandler;
+use nosqlite_rust::engine::error::{NosqliteError, NosqliteErrorHandler};
 use nosqlite_rust::engine::models::file::model::File;
-use nosqlite_rust::engine::models::Database;
+use nosqlite_rust::engine::models::{
+    Compression, Database, Encoding, EncryptionKey, FileBackend, Kdf, KeySource, Layout,
+    NosqliteOptions,
+};
+use nosqlite_rust::engine::services::database_service::save_database_to_backend;
 use serde_json::json;
 use std::fs;
 use tempfile::NamedTempFile;
@@ -35,3 +39,260 @@ fn write_and_load_should_persist_database() {
     let col = reloaded.get_collection("save_me").unwrap();
     assert_eq!(col.document_count(), 1);
 }
+
+#[test]
+fn passphrase_key_should_be_stored_in_plaintext_header() {
+    let mut handler = make_error_handler();
+    let tmp = NamedTempFile::new().unwrap();
+    let path = tmp.path().to_str().unwrap().to_string();
+    fs::remove_file(&path).unwrap();
+
+    let options = NosqliteOptions::default()
+        .with_key_source(KeySource::Passphrase("hunter2".to_string()))
+        .with_kdf(Kdf::Pbkdf2 { iterations: 1_000 });
+    let key = File::resolve_key(&path, &options, &mut handler).unwrap();
+    File::save_with_key(&path, &Database::new(&path), &key, &mut handler).unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    let header = content.lines().next().unwrap();
+    assert!(header.starts_with("NOSQLITE {\"version\":4,\"kdf\":{\"algorithm\":\"pbkdf2\""));
+    assert!(!content.contains("hunter2"));
+
+    let reloaded = File::load_or_create_with_options(&path, &options, &mut handler);
+    assert!(reloaded.is_ok());
+}
+
+#[test]
+fn key_id_should_be_stored_in_plaintext_header() {
+    let mut handler = make_error_handler();
+    let tmp = NamedTempFile::new().unwrap();
+    let path = tmp.path().to_str().unwrap().to_string();
+    fs::remove_file(&path).unwrap();
+
+    let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([7u8; 32]));
+    let key = File::resolve_key(&path, &options, &mut handler).unwrap();
+    File::save_with_key(&path, &Database::new(&path), &key, &mut handler).unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    let header = content.lines().next().unwrap();
+    assert!(header.starts_with(&format!(
+        "NOSQLITE {{\"version\":4,\"key_id\":\"{}\",",
+        key.key_id()
+    )));
+}
+
+#[test]
+fn interrupted_save_should_keep_previous_version() {
+    let mut handler = make_error_handler();
+    let tmp = NamedTempFile::new().unwrap();
+    let path = tmp.path().to_str().unwrap().to_string();
+    let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([8u8; 32]));
+
+    let mut db = Database::new(&path);
+    db.add_collection("before", json!({}), &mut handler)
+        .unwrap();
+    File::save_with_options(&path, &db, &options, &mut handler).unwrap();
+
+    // A directory in place of the temporary file makes the next save fail before the rename.
+    let tmp_path = format!("{}.tmp", path);
+    fs::create_dir(&tmp_path).unwrap();
+    db.add_collection("after", json!({}), &mut handler).unwrap();
+    assert!(File::save_with_options(&path, &db, &options, &mut handler).is_err());
+
+    let reloaded = File::load_or_create_with_options(&path, &options, &mut handler).unwrap();
+    assert!(reloaded.get_collection("before").is_some());
+    assert!(reloaded.get_collection("after").is_none());
+
+    fs::remove_dir(&tmp_path).unwrap();
+    File::save_with_options(&path, &db, &options, &mut handler).unwrap();
+    let reloaded = File::load_or_create_with_options(&path, &options, &mut handler).unwrap();
+    assert!(reloaded.get_collection("after").is_some());
+}
+
+#[test]
+fn stale_temporary_file_should_not_affect_database() {
+    let mut handler = make_error_handler();
+    let tmp = NamedTempFile::new().unwrap();
+    let path = tmp.path().to_str().unwrap().to_string();
+    let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([8u8; 32]));
+
+    let mut db = Database::new(&path);
+    db.add_collection("users", json!({}), &mut handler).unwrap();
+    File::save_with_options(&path, &db, &options, &mut handler).unwrap();
+
+    // A crash in the middle of a save leaves a truncated temporary file behind.
+    let tmp_path = format!("{}.tmp", path);
+    fs::write(&tmp_path, "NOSQLITE {\"version\":1").unwrap();
+
+    let reloaded = File::load_or_create_with_options(&path, &options, &mut handler).unwrap();
+    assert!(reloaded.get_collection("users").is_some());
+    File::save_with_options(&path, &reloaded, &options, &mut handler).unwrap();
+    assert!(!std::path::Path::new(&tmp_path).exists());
+}
+
+fn make_sample_database(path: &str, handler: &mut NosqliteErrorHandler) -> Database {
+    let mut db = Database::new(path);
+    db.add_collection("users", json!({ "name": "string" }), handler)
+        .unwrap();
+    let users = db.get_collection_mut("users").unwrap();
+    for i in 0..50 {
+        users
+            .add_document(json!({ "name": format!("user {}", i), "age": i }), handler)
+            .unwrap();
+    }
+    db
+}
+
+#[test]
+fn messagepack_encoding_should_round_trip_without_base64() {
+    let mut handler = make_error_handler();
+    for key_source in [KeySource::Bytes([9u8; 32]), KeySource::Unencrypted] {
+        let tmp = NamedTempFile::new().unwrap();
+        let path = tmp.path().to_str().unwrap().to_string();
+        fs::remove_file(&path).unwrap();
+        let db = make_sample_database(&path, &mut handler);
+        let json = NosqliteOptions::default().with_key_source(key_source.clone());
+        let binary = json.clone().with_encoding(Encoding::MessagePack);
+
+        File::save_with_options(&path, &db, &json, &mut handler).unwrap();
+        let json_size = fs::metadata(&path).unwrap().len();
+        File::save_with_options(&path, &db, &binary, &mut handler).unwrap();
+        let content = fs::read(&path).unwrap();
+        assert!((content.len() as u64) < json_size * 3 / 4);
+        assert!(String::from_utf8_lossy(&content)
+            .lines()
+            .next()
+            .unwrap()
+            .contains(r#""encoding":"messagepack""#));
+
+        // The encoding is read from the header, whatever the options ask to write.
+        let reloaded = File::load_or_create_with_options(&path, &json, &mut handler).unwrap();
+        let users = reloaded.get_collection("users").unwrap();
+        assert_eq!(users.document_count(), 50);
+        let original = &db.get_collection("users").unwrap().documents[7];
+        assert_eq!(users.documents[7].id, original.id);
+        assert_eq!(users.documents[7].data, original.data);
+        assert_eq!(users.documents[7].created_at, original.created_at);
+    }
+}
+
+#[test]
+fn json_files_should_load_with_messagepack_encoding() {
+    let mut handler = make_error_handler();
+    let tmp = NamedTempFile::new().unwrap();
+    let path = tmp.path().to_str().unwrap().to_string();
+    let db = make_sample_database(&path, &mut handler);
+    let json = NosqliteOptions::default().with_key_source(KeySource::Bytes([10u8; 32]));
+    let binary = json.clone().with_encoding(Encoding::MessagePack);
+
+    File::save_with_options(&path, &db, &json, &mut handler).unwrap();
+    let reloaded = File::load_or_create_with_options(&path, &binary, &mut handler).unwrap();
+    assert_eq!(
+        reloaded.get_collection("users").unwrap().document_count(),
+        50
+    );
+
+    File::save_with_options(&path, &reloaded, &binary, &mut handler).unwrap();
+    let reloaded = File::load_or_create_with_options(&path, &json, &mut handler).unwrap();
+    assert_eq!(
+        reloaded.get_collection("users").unwrap().document_count(),
+        50
+    );
+}
+
+#[test]
+fn compressed_payload_should_round_trip_with_every_encoding() {
+    let mut handler = make_error_handler();
+    let db = make_sample_database("compressed", &mut handler);
+
+    for compression in [Compression::Deflate, Compression::Zstd] {
+        for encoding in [Encoding::Json, Encoding::MessagePack] {
+            for key in [
+                EncryptionKey::new([11u8; 32], None),
+                EncryptionKey::unencrypted(),
+            ] {
+                let key = key.with_encoding(encoding).with_compression(compression);
+                let content = File::encode_database(&db, &key, &mut handler).unwrap();
+
+                let stats =
+                    File::payload_stats("compressed", &content, &key, &mut handler).unwrap();
+                assert_eq!(stats.compression, compression);
+                assert!(stats.compression_ratio() > 3.0);
+
+                let (header, payload) =
+                    File::decode_payload("compressed", &content, &key, &mut handler).unwrap();
+                assert_eq!(header.compression, compression);
+                let reloaded = File::database_from_value(payload, &mut handler).unwrap();
+                assert_eq!(
+                    reloaded.get_collection("users").unwrap().document_count(),
+                    50
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn corrupted_compressed_payload_should_be_rejected() {
+    let mut handler = make_error_handler();
+    let db = make_sample_database("corrupted", &mut handler);
+    let key = EncryptionKey::unencrypted().with_compression(Compression::Deflate);
+    let mut content = File::encode_database(&db, &key, &mut handler).unwrap();
+
+    let last = content.len() - 10;
+    content[last] ^= 0xff;
+    assert!(matches!(
+        File::decode_payload("corrupted", &content, &key, &mut handler),
+        Err(NosqliteError::Corrupted(_))
+    ));
+    assert!(matches!(
+        File::decode_unverified_payload("corrupted", &content, &key, &mut handler),
+        Err(NosqliteError::InvalidDatabaseFormat(_))
+    ));
+}
+
+#[test]
+fn segmented_file_should_load_every_segment() {
+    let mut handler = make_error_handler();
+    let tmp = NamedTempFile::new().unwrap();
+    let path = tmp.path().to_str().unwrap().to_string();
+    fs::remove_file(&path).unwrap();
+    let mut db = make_sample_database(&path, &mut handler);
+    db.add_collection("orders", json!({ "item": "string" }), &mut handler)
+        .unwrap();
+    let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([12u8; 32]));
+    let key = File::resolve_key(&path, &options, &mut handler).unwrap();
+
+    let mut backend = FileBackend::new(&path);
+    save_database_to_backend(&mut backend, &mut db, &key, Layout::Segmented, &mut handler).unwrap();
+    let (header, _) = File::read_payload(&path, &key, &mut handler)
+        .unwrap()
+        .unwrap();
+    assert_eq!(header.layout, Layout::Segmented);
+
+    let reloaded = File::load_or_create_with_key(&path, &key, &mut handler).unwrap();
+    assert_eq!(reloaded.collections.len(), 2);
+    assert_eq!(
+        reloaded.get_collection("users").unwrap().document_count(),
+        50
+    );
+    assert_eq!(reloaded.segments.stored, db.segments.stored);
+    fs::remove_dir_all(File::segments_path(&path)).unwrap();
+}
+
+#[test]
+fn payload_not_matching_its_checksum_should_be_corrupted() {
+    let mut handler = make_error_handler();
+    let db = make_sample_database("checksum", &mut handler);
+    let key = EncryptionKey::new([12u8; 32], None);
+    let mut content = File::encode_database(&db, &key, &mut handler).unwrap();
+    let (header, _) = File::decode_payload("checksum", &content, &key, &mut handler).unwrap();
+    assert!(header.checksum.is_some());
+
+    let last = content.len() - 10;
+    content[last] ^= 0x01;
+    assert!(matches!(
+        File::decode_payload("checksum", &content, &key, &mut handler),
+        Err(NosqliteError::Corrupted(_))
+    ));
+}
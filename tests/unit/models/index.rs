@@ -0,0 +1,311 @@
+use nosqlite_rust::engine::models::index::model::IndexData;
+use nosqlite_rust::engine::{
+    error::NosqliteErrorHandler,
+    models::{Collection, Document, GeoPoint, GeoQuery, GeoShape, Index, IndexKind, VectorMetric},
+};
+use serde_json::json;
+use tempfile::NamedTempFile;
+
+fn make_error_handler() -> NosqliteErrorHandler {
+    let tmp_log = NamedTempFile::new().unwrap();
+    let path = tmp_log
+        .path()
+        .with_extension("nosqlite")
+        .to_string_lossy()
+        .to_string();
+    NosqliteErrorHandler::new(path)
+}
+
+fn make_text_index() -> Index {
+    Index::new(
+        "content",
+        IndexKind::Text {
+            fields: vec!["title".to_string(), "body".to_string()],
+        },
+    )
+}
+
+#[test]
+fn text_search_should_match_stemmed_terms() {
+    let mut index = make_text_index();
+    let doc = Document::new(json!({ "title": "Databases", "body": "Indexing documents" }));
+    index.insert(&doc);
+
+    let scores = index.text_search("database indexes");
+    assert!(scores.contains_key(&doc.id));
+}
+
+#[test]
+fn text_search_should_rank_more_relevant_documents_first() {
+    let mut index = make_text_index();
+    let relevant = Document::new(json!({ "title": "Rust", "body": "rust rust and more rust" }));
+    let other = Document::new(json!({ "title": "Go", "body": "a short note about rust" }));
+    index.insert(&relevant);
+    index.insert(&other);
+
+    let scores = index.text_search("rust");
+    assert!(scores[&relevant.id] > scores[&other.id]);
+}
+
+#[test]
+fn remove_should_drop_document_from_results() {
+    let mut index = make_text_index();
+    let doc = Document::new(json!({ "title": "Ephemeral", "body": "gone soon" }));
+    index.insert(&doc);
+    index.remove(&doc);
+
+    assert!(index.text_search("ephemeral").is_empty());
+}
+
+#[test]
+fn remove_should_only_drop_the_postings_of_the_document_terms() {
+    let mut index = make_text_index();
+    let doc = Document::new(json!({ "title": "Ephemeral", "body": "shared words" }));
+    let other = Document::new(json!({ "title": "Durable", "body": "shared words" }));
+    index.insert(&doc);
+    index.insert(&other);
+    index.remove(&doc);
+
+    let IndexData::Text(inverted) = &index.data else {
+        panic!("expected a text index");
+    };
+    assert!(inverted
+        .postings
+        .values()
+        .all(|docs| !docs.contains_key(&doc.id)));
+    assert!(!inverted
+        .postings
+        .keys()
+        .any(|term| term.starts_with("ephemer")));
+    assert_eq!(index.text_search("shared").len(), 1);
+    assert_eq!(index.text_search("durable").len(), 1);
+}
+
+#[test]
+fn collection_should_keep_index_in_sync_with_writes() {
+    let mut handler = make_error_handler();
+    let mut col = Collection::new("notes".into(), json!({ "body": "string" }));
+    col.add_index(
+        "content",
+        IndexKind::Text {
+            fields: vec!["body".to_string()],
+        },
+        &mut handler,
+    )
+    .unwrap();
+
+    col.add_document(json!({ "body": "first draft" }), &mut handler)
+        .unwrap();
+    col.update_documents(
+        "body",
+        &json!("first draft"),
+        json!({ "body": "final version" }),
+        &mut handler,
+    )
+    .unwrap();
+
+    let index = col.text_index().unwrap();
+    assert!(index.text_search("draft").is_empty());
+    assert_eq!(index.text_search("final").len(), 1);
+
+    col.delete_documents("body", &json!("final version"), &mut handler)
+        .unwrap();
+    assert!(col.text_index().unwrap().text_search("final").is_empty());
+}
+
+#[test]
+fn add_index_without_fields_should_fail() {
+    let mut handler = make_error_handler();
+    let mut col = Collection::new("notes".into(), json!({}));
+    let result = col.add_index("empty", IndexKind::Text { fields: vec![] }, &mut handler);
+    assert!(result.is_err());
+}
+
+#[test]
+fn add_duplicate_index_should_fail() {
+    let mut handler = make_error_handler();
+    let mut col = Collection::new("notes".into(), json!({}));
+    let kind = IndexKind::Text {
+        fields: vec!["body".to_string()],
+    };
+    col.add_index("content", kind.clone(), &mut handler)
+        .unwrap();
+    assert!(col.add_index("content", kind, &mut handler).is_err());
+}
+
+#[test]
+fn ttl_index_should_expire_from_metadata_timestamps() {
+    let index = Index::new(
+        "expiry",
+        IndexKind::Ttl {
+            field: "$created_at".to_string(),
+            expire_after_secs: 30,
+        },
+    );
+    let doc = Document::new(json!({}));
+    assert_eq!(index.expires_at(&doc), Some(doc.created_at + 30));
+}
+
+#[test]
+fn ttl_index_should_ignore_documents_without_a_date() {
+    let index = Index::new(
+        "expiry",
+        IndexKind::Ttl {
+            field: "expires".to_string(),
+            expire_after_secs: 0,
+        },
+    );
+    assert_eq!(index.expires_at(&Document::new(json!({}))), None);
+    assert_eq!(
+        index.expires_at(&Document::new(json!({ "expires": "not a date" }))),
+        None
+    );
+}
+
+#[test]
+fn purge_expired_should_remove_only_expired_documents() {
+    let mut handler = make_error_handler();
+    let mut col = Collection::new("sessions".into(), json!({}));
+    col.add_document(json!({ "seen_at": "2000-01-01T00:00:00Z" }), &mut handler)
+        .unwrap();
+    col.add_document(json!({ "seen_at": "2999-01-01T00:00:00Z" }), &mut handler)
+        .unwrap();
+    col.add_index(
+        "expiry",
+        IndexKind::Ttl {
+            field: "seen_at".to_string(),
+            expire_after_secs: 60,
+        },
+        &mut handler,
+    )
+    .unwrap();
+
+    assert_eq!(col.live_documents().len(), 1);
+    assert_eq!(col.document_count(), 2);
+    assert_eq!(
+        col.purge_expired(nosqlite_rust::engine::models::utils::now()),
+        1
+    );
+    assert_eq!(col.document_count(), 1);
+}
+
+#[test]
+fn geo_shapes_should_contain_points() {
+    let paris = GeoPoint {
+        lat: 48.8566,
+        lng: 2.3522,
+    };
+    let square = GeoShape::Box {
+        min: GeoPoint {
+            lat: 48.0,
+            lng: 2.0,
+        },
+        max: GeoPoint {
+            lat: 49.0,
+            lng: 3.0,
+        },
+    };
+    let triangle = GeoShape::Polygon(vec![
+        GeoPoint {
+            lat: 48.0,
+            lng: 2.0,
+        },
+        GeoPoint {
+            lat: 49.0,
+            lng: 2.0,
+        },
+        GeoPoint {
+            lat: 48.0,
+            lng: 3.0,
+        },
+    ]);
+    assert!(square.contains(&paris));
+    assert!(triangle.contains(&GeoPoint {
+        lat: 48.2,
+        lng: 2.2
+    }));
+    assert!(!triangle.contains(&GeoPoint {
+        lat: 48.9,
+        lng: 2.9
+    }));
+}
+
+#[test]
+fn geo_query_should_reject_a_zero_near_limit() {
+    let point = json!({ "lat": 48.85, "lng": 2.35 });
+    assert_eq!(
+        GeoQuery::from_value(&json!({ "$near": { "point": point, "limit": 0 } })),
+        Err("$near expects a positive integer 'limit'".to_string())
+    );
+    assert!(GeoQuery::from_value(&json!({ "$near": { "point": point, "limit": 1 } })).is_ok());
+}
+
+#[test]
+fn geo_query_should_reject_malformed_operators() {
+    assert_eq!(GeoQuery::from_value(&json!({ "$eq": 1 })), Ok(None));
+    assert!(GeoQuery::from_value(&json!({ "$near": { "limit": 3 } })).is_err());
+    assert!(
+        GeoQuery::from_value(&json!({ "$geoWithin": { "$box": [{ "lat": 0, "lng": 0 }] } }))
+            .is_err()
+    );
+}
+
+#[test]
+fn geo_index_should_track_document_positions() {
+    let mut index = Index::new(
+        "stores_loc",
+        IndexKind::Geo {
+            field: "loc".to_string(),
+        },
+    );
+    let paris = Document::new(json!({ "loc": { "lat": 48.85, "lng": 2.35 } }));
+    let tokyo = Document::new(json!({ "loc": { "lat": 35.68, "lng": 139.69 } }));
+    let invalid = Document::new(json!({ "loc": "somewhere" }));
+    index.insert(&paris);
+    index.insert(&tokyo);
+    index.insert(&invalid);
+
+    let min = GeoPoint {
+        lat: 48.0,
+        lng: 2.0,
+    };
+    let max = GeoPoint {
+        lat: 49.0,
+        lng: 3.0,
+    };
+    let ids = index.geo_candidates(&min, &max);
+    assert_eq!(ids.len(), 1);
+    assert!(ids.contains(&paris.id));
+
+    index.remove(&paris);
+    assert!(index.geo_candidates(&min, &max).is_empty());
+}
+
+#[test]
+fn vector_index_should_find_nearest_neighbors() {
+    let mut index = Index::new(
+        "embedding_hnsw",
+        IndexKind::Vector {
+            field: "embedding".to_string(),
+            metric: VectorMetric::L2,
+        },
+    );
+    let docs: Vec<Document> = (0..300)
+        .map(|i| {
+            let (x, y) = ((i % 20) as f64, (i / 20) as f64);
+            Document::new(json!({ "embedding": [x, y] }))
+        })
+        .collect();
+    for doc in &docs {
+        index.insert(doc);
+    }
+
+    let nearest = index.vector_search(&[7.1, 4.2], 1);
+    assert_eq!(nearest[0].0, docs[4 * 20 + 7].id);
+
+    index.remove(&docs[4 * 20 + 7]);
+    let nearest = index.vector_search(&[7.1, 4.2], 3);
+    assert_eq!(nearest.len(), 3);
+    assert!(nearest.iter().all(|(id, _)| *id != docs[4 * 20 + 7].id));
+    assert!(nearest.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+}
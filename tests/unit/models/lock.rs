@@ -0,0 +1,91 @@
+use nosqlite_rust::engine::error::{NosqliteError, NosqliteErrorHandler};
+use nosqlite_rust::engine::models::DatabaseLock;
+use std::time::{Duration, Instant};
+
+fn make_db_path() -> String {
+    std::fs::create_dir_all("./temp").unwrap();
+    format!("./temp/test_db_{}.nosqlite", rand::random::<u64>())
+}
+
+#[test]
+fn exclusive_lock_should_exclude_every_other_lock() {
+    let path = make_db_path();
+    let mut handler = NosqliteErrorHandler::new(path.clone());
+
+    let lock = DatabaseLock::acquire(&path, false, None, &mut handler).unwrap();
+    assert!(std::path::Path::new(&DatabaseLock::lock_path(&path)).exists());
+    assert!(matches!(
+        DatabaseLock::acquire(&path, false, None, &mut handler),
+        Err(NosqliteError::DatabaseLocked(_))
+    ));
+    assert!(matches!(
+        DatabaseLock::acquire(&path, true, None, &mut handler),
+        Err(NosqliteError::DatabaseLocked(_))
+    ));
+
+    drop(lock);
+    assert!(DatabaseLock::acquire(&path, false, None, &mut handler).is_ok());
+}
+
+#[test]
+fn shared_locks_should_only_exclude_exclusive_locks() {
+    let path = make_db_path();
+    let mut handler = NosqliteErrorHandler::new(path.clone());
+
+    let first = DatabaseLock::acquire(&path, true, None, &mut handler).unwrap();
+    let second = DatabaseLock::acquire(&path, true, None, &mut handler).unwrap();
+    assert!(matches!(
+        DatabaseLock::acquire(&path, false, None, &mut handler),
+        Err(NosqliteError::DatabaseLocked(_))
+    ));
+
+    drop(first);
+    drop(second);
+    assert!(DatabaseLock::acquire(&path, false, None, &mut handler).is_ok());
+}
+
+#[test]
+fn acquire_should_wait_until_timeout() {
+    let path = make_db_path();
+    let mut handler = NosqliteErrorHandler::new(path.clone());
+    let lock = DatabaseLock::acquire(&path, false, None, &mut handler).unwrap();
+
+    let start = Instant::now();
+    let result =
+        DatabaseLock::acquire(&path, false, Some(Duration::from_millis(100)), &mut handler);
+    assert!(matches!(result, Err(NosqliteError::DatabaseLocked(_))));
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    let releaser = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        drop(lock);
+    });
+    let result = DatabaseLock::acquire(&path, false, Some(Duration::from_secs(10)), &mut handler);
+    assert!(result.is_ok());
+    releaser.join().unwrap();
+}
+
+#[test]
+fn acquire_should_accept_a_timeout_past_the_end_of_time() {
+    let path = make_db_path();
+    let mut handler = NosqliteErrorHandler::new(path.clone());
+
+    let lock = DatabaseLock::acquire(&path, false, Some(Duration::MAX), &mut handler).unwrap();
+    let releaser = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        drop(lock);
+    });
+    let result = DatabaseLock::acquire(&path, false, Some(Duration::MAX), &mut handler);
+    assert!(result.is_ok());
+    releaser.join().unwrap();
+}
+
+#[test]
+fn databases_differing_by_extension_should_not_share_a_lock() {
+    let path = make_db_path();
+    let other = path.replace(".nosqlite", ".db");
+    let mut handler = NosqliteErrorHandler::new(path.clone());
+
+    let _lock = DatabaseLock::acquire(&path, false, None, &mut handler).unwrap();
+    assert!(DatabaseLock::acquire(&other, false, None, &mut handler).is_ok());
+}
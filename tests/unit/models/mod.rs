@@ -2,3 +2,6 @@ pub mod collection;
 pub mod database;
 pub mod document;
 pub mod file;
+pub mod index;
+pub mod lock;
+pub mod storage;
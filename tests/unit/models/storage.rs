@@ -0,0 +1,243 @@
+use nosqlite_rust::engine::error::{NosqliteError, NosqliteErrorHandler};
+use nosqlite_rust::engine::models::{
+    FaultyBackend, FileBackend, KeySource, MemoryBackend, NosqliteOptions, StorageBackend,
+};
+use nosqlite_rust::engine::Nosqlite;
+use serde_json::json;
+
+fn make_db_path() -> String {
+    std::fs::create_dir_all("./temp").unwrap();
+    format!("./temp/test_db_{}.nosqlite", rand::random::<u64>())
+}
+
+fn make_error_handler(name: &str) -> NosqliteErrorHandler {
+    NosqliteErrorHandler::in_memory(name.to_string())
+}
+
+#[test]
+fn file_backend_should_round_trip_data_and_log() {
+    let path = make_db_path();
+    let mut handler = make_error_handler(&path);
+    let mut backend = FileBackend::new(&path);
+
+    assert!(!backend.exists(&mut handler).unwrap());
+    assert_eq!(backend.load(&mut handler).unwrap(), None);
+    backend.save(b"first", &mut handler).unwrap();
+    backend.save(b"second", &mut handler).unwrap();
+    assert!(backend.exists(&mut handler).unwrap());
+    assert_eq!(
+        backend.load(&mut handler).unwrap(),
+        Some(b"second".to_vec())
+    );
+
+    assert_eq!(backend.append_log(b"one\n", &mut handler).unwrap(), 4);
+    assert_eq!(backend.append_log(b"two\n", &mut handler).unwrap(), 8);
+    assert_eq!(
+        backend.load_log(&mut handler).unwrap(),
+        Some(b"one\ntwo\n".to_vec())
+    );
+    backend.clear_log(&mut handler).unwrap();
+    assert_eq!(backend.load_log(&mut handler).unwrap(), None);
+}
+
+#[test]
+fn memory_backend_clones_should_share_storage() {
+    let mut handler = make_error_handler("shared");
+    let backend = MemoryBackend::new("shared");
+    let mut writer = backend.clone();
+
+    writer.save(b"data", &mut handler).unwrap();
+    writer.append_log(b"log\n", &mut handler).unwrap();
+    drop(writer);
+
+    assert_eq!(backend.data(), Some(b"data".to_vec()));
+    assert_eq!(backend.log(), Some(b"log\n".to_vec()));
+    let mut reader = backend.clone();
+    assert!(reader.exists(&mut handler).unwrap());
+    reader.clear_log(&mut handler).unwrap();
+    assert_eq!(backend.log(), None);
+}
+
+#[test]
+fn memory_backend_lock_should_be_exclusive_across_clones() {
+    let mut handler = make_error_handler("locked");
+    let backend = MemoryBackend::new("locked");
+
+    let mut writer = backend.clone();
+    writer.lock(false, None, &mut handler).unwrap();
+    assert!(matches!(
+        backend.clone().lock(true, None, &mut handler),
+        Err(NosqliteError::DatabaseLocked(_))
+    ));
+    drop(writer);
+
+    let mut first = backend.clone();
+    let mut second = backend.clone();
+    first.lock(true, None, &mut handler).unwrap();
+    second.lock(true, None, &mut handler).unwrap();
+    assert!(matches!(
+        backend.clone().lock(false, None, &mut handler),
+        Err(NosqliteError::DatabaseLocked(_))
+    ));
+    drop(first);
+    drop(second);
+    assert!(backend.clone().lock(false, None, &mut handler).is_ok());
+}
+
+#[test]
+fn faulty_backend_should_fail_writes_after_the_given_count() {
+    let mut handler = make_error_handler("faulty");
+    let memory = MemoryBackend::new("faulty");
+    let backend = FaultyBackend::new(Box::new(memory.clone()));
+    let mut writer = backend.clone();
+
+    backend.fail_writes_after(1);
+    writer.save(b"kept", &mut handler).unwrap();
+    assert!(matches!(
+        writer.save(b"lost", &mut handler),
+        Err(NosqliteError::IoError(_))
+    ));
+    assert!(writer.append_log(b"lost\n", &mut handler).is_err());
+    assert_eq!(memory.data(), Some(b"kept".to_vec()));
+    assert_eq!(memory.log(), None);
+
+    backend.heal();
+    writer.save(b"healed", &mut handler).unwrap();
+    assert_eq!(memory.data(), Some(b"healed".to_vec()));
+}
+
+#[test]
+fn faulty_backend_should_fail_reads_on_demand() {
+    let mut handler = make_error_handler("unreadable");
+    let backend = FaultyBackend::new(Box::new(MemoryBackend::new("unreadable")));
+    let mut reader = backend.clone();
+    reader.save(b"data", &mut handler).unwrap();
+
+    backend.fail_reads(true);
+    assert!(reader.load(&mut handler).is_err());
+    assert!(reader.load_log(&mut handler).is_err());
+
+    backend.fail_reads(false);
+    assert_eq!(reader.load(&mut handler).unwrap(), Some(b"data".to_vec()));
+}
+
+#[test]
+fn backends_should_store_segments_by_id() {
+    let path = make_db_path();
+    let mut handler = make_error_handler(&path);
+    let backends: Vec<Box<dyn StorageBackend>> = vec![
+        Box::new(FileBackend::new(&path)),
+        Box::new(MemoryBackend::new("segments")),
+    ];
+
+    for mut backend in backends {
+        assert!(backend.list_segments(&mut handler).unwrap().is_empty());
+        backend.save_segment("b", b"second", &mut handler).unwrap();
+        backend.save_segment("a", b"first", &mut handler).unwrap();
+        assert_eq!(backend.list_segments(&mut handler).unwrap(), vec!["a", "b"]);
+        assert_eq!(
+            backend.load_segment("a", &mut handler).unwrap(),
+            Some(b"first".to_vec())
+        );
+
+        backend.remove_segment("a", &mut handler).unwrap();
+        backend.remove_segment("a", &mut handler).unwrap();
+        assert_eq!(backend.load_segment("a", &mut handler).unwrap(), None);
+        assert_eq!(backend.list_segments(&mut handler).unwrap(), vec!["b"]);
+    }
+}
+
+#[test]
+fn backends_should_replace_snapshots_by_name() {
+    let path = make_db_path();
+    let mut handler = make_error_handler(&path);
+    let backends: Vec<Box<dyn StorageBackend>> = vec![
+        Box::new(FileBackend::new(&path)),
+        Box::new(MemoryBackend::new("snapshots")),
+    ];
+
+    for mut backend in backends {
+        assert!(backend.list_snapshots(&mut handler).unwrap().is_empty());
+        backend
+            .save_snapshot("nightly", b"old", &mut handler)
+            .unwrap();
+        backend
+            .save_snapshot("nightly", b"new", &mut handler)
+            .unwrap();
+        backend
+            .save_snapshot("before", b"first", &mut handler)
+            .unwrap();
+        assert_eq!(
+            backend.list_snapshots(&mut handler).unwrap(),
+            vec!["before", "nightly"]
+        );
+        assert_eq!(
+            backend.load_snapshot("nightly", &mut handler).unwrap(),
+            Some(b"new".to_vec())
+        );
+
+        backend.remove_snapshot("before", &mut handler).unwrap();
+        backend.remove_snapshot("before", &mut handler).unwrap();
+        assert_eq!(backend.load_snapshot("before", &mut handler).unwrap(), None);
+        assert_eq!(
+            backend.list_snapshots(&mut handler).unwrap(),
+            vec!["nightly"]
+        );
+    }
+}
+
+/// A backend implementing only the required methods of [`StorageBackend`].
+#[derive(Debug, Default)]
+struct MinimalBackend {
+    data: Option<Vec<u8>>,
+}
+
+impl StorageBackend for MinimalBackend {
+    fn name(&self) -> &str {
+        "minimal"
+    }
+
+    fn load(
+        &mut self,
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<Option<Vec<u8>>, NosqliteError> {
+        Ok(self.data.clone())
+    }
+
+    fn save(
+        &mut self,
+        bytes: &[u8],
+        _handler: &mut NosqliteErrorHandler,
+    ) -> Result<(), NosqliteError> {
+        self.data = Some(bytes.to_vec());
+        Ok(())
+    }
+}
+
+#[test]
+fn minimal_backend_should_store_a_database_without_optional_features() {
+    let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([20u8; 32]));
+    let mut db = Nosqlite::open_with_backend(Box::new(MinimalBackend::default()), options).unwrap();
+    db.create_collection("users", json!({ "name": "string" }))
+        .unwrap();
+    db.insert_document("users", json!({ "name": "Alice" }))
+        .unwrap();
+    assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+
+    assert!(db.list_snapshots().unwrap().is_empty());
+    assert!(matches!(
+        db.create_snapshot("nightly"),
+        Err(NosqliteError::IoError(_))
+    ));
+
+    let mut handler = make_error_handler("minimal");
+    let mut backend = MinimalBackend::default();
+    assert!(!backend.exists(&mut handler).unwrap());
+    backend.save(b"data", &mut handler).unwrap();
+    assert!(backend.exists(&mut handler).unwrap());
+    backend.lock(false, None, &mut handler).unwrap();
+    assert_eq!(backend.load_log(&mut handler).unwrap(), None);
+    assert!(backend.append_log(b"log\n", &mut handler).is_err());
+    assert!(backend.save_segment("a", b"a", &mut handler).is_err());
+    assert!(backend.list_segments(&mut handler).unwrap().is_empty());
+}
@@ -1,5 +1,11 @@
 #[cfg(test)]
 mod tests {
+    use nosqlite_rust::engine::error::{NosqliteError, NosqliteErrorHandler};
+    use nosqlite_rust::engine::models::{
+        AccessPath, Compression, Durability, Encoding, EncryptionKey, ExportFormat, FaultyBackend,
+        File, IndexKind, Kdf, KeySource, Layout, MemoryBackend, NosqliteOptions, StorageBackend,
+        VectorMetric,
+    };
     use nosqlite_rust::engine::Nosqlite;
     use serde_json::json;
 
@@ -347,4 +353,1605 @@ mod tests {
         assert_eq!(docs.len(), 1);
         assert_eq!(docs[0].data["name"], "saved");
     }
+
+    #[test]
+    fn explain_should_report_full_scan() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("posts", json!({ "author": "string" }))
+            .unwrap();
+        db.insert_document("posts", json!({ "author": "alice" }))
+            .unwrap();
+        db.insert_document("posts", json!({ "author": "bob" }))
+            .unwrap();
+        db.insert_document("posts", json!({ "author": "alice" }))
+            .unwrap();
+
+        let plan = db
+            .explain("posts", &json!({ "author": "alice" }), &json!({}))
+            .unwrap();
+        assert_eq!(plan.access_path, AccessPath::FullScan);
+        assert_eq!(plan.documents_examined, 3);
+        assert_eq!(plan.documents_returned, 2);
+    }
+
+    #[test]
+    fn explain_should_fail() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+
+        let result = db.explain("not_there", &json!({}), &json!({}));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn text_search_should_rank_results_and_use_index() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("articles", json!({ "body": "string" }))
+            .unwrap();
+        db.insert_document("articles", json!({ "body": "Rust in production" }))
+            .unwrap();
+        db.insert_document("articles", json!({ "body": "Rust, rust and rust" }))
+            .unwrap();
+        db.insert_document("articles", json!({ "body": "Gardening tips" }))
+            .unwrap();
+        db.create_index(
+            "articles",
+            "content",
+            IndexKind::Text {
+                fields: vec!["body".to_string()],
+            },
+        )
+        .unwrap();
+
+        let filter = json!({ "$text": { "$search": "rust" } });
+        let results = db.get_documents("articles", &filter, &json!({})).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["body"], "Rust, rust and rust");
+        assert!(results[0]["_score"].as_f64() >= results[1]["_score"].as_f64());
+
+        let plan = db.explain("articles", &filter, &json!({})).unwrap();
+        assert_eq!(plan.access_path, AccessPath::Index("content".to_string()));
+        assert_eq!(plan.documents_examined, 2);
+    }
+
+    #[test]
+    fn text_search_should_skip_expired_documents() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("articles", json!({ "body": "string" }))
+            .unwrap();
+        db.insert_document("articles", json!({ "body": "Old rust", "seen_at": 0 }))
+            .unwrap();
+        db.insert_document(
+            "articles",
+            json!({ "body": "New rust", "seen_at": 4102444800u64 }),
+        )
+        .unwrap();
+        for (name, kind) in [
+            (
+                "content",
+                IndexKind::Text {
+                    fields: vec!["body".to_string()],
+                },
+            ),
+            (
+                "expiry",
+                IndexKind::Ttl {
+                    field: "seen_at".to_string(),
+                    expire_after_secs: 60,
+                },
+            ),
+        ] {
+            db.create_index("articles", name, kind).unwrap();
+        }
+
+        let filter = json!({ "$text": { "$search": "rust" } });
+        let results = db.get_documents("articles", &filter, &json!({})).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["body"], "New rust");
+    }
+
+    #[test]
+    fn text_search_without_index_should_fail() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("articles", json!({})).unwrap();
+
+        let filter = json!({ "$text": { "$search": "rust" } });
+        assert!(db.get_documents("articles", &filter, &json!({})).is_err());
+    }
+
+    #[test]
+    fn text_index_should_be_rebuilt_on_open() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        {
+            let mut db = Nosqlite::open(db_path_str).unwrap();
+            db.create_collection("articles", json!({ "body": "string" }))
+                .unwrap();
+            db.create_index(
+                "articles",
+                "content",
+                IndexKind::Text {
+                    fields: vec!["body".to_string()],
+                },
+            )
+            .unwrap();
+            db.insert_document("articles", json!({ "body": "persisted words" }))
+                .unwrap();
+        }
+
+        let mut reopened = Nosqlite::open(db_path_str).unwrap();
+        let filter = json!({ "$text": { "$search": "word" } });
+        let results = reopened
+            .get_documents("articles", &filter, &json!({}))
+            .unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn ttl_index_should_hide_and_purge_expired_documents() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        {
+            let mut db = Nosqlite::open(db_path_str).unwrap();
+            db.create_collection("sessions", json!({ "user": "string" }))
+                .unwrap();
+            db.insert_document("sessions", json!({ "user": "old", "seen_at": 0 }))
+                .unwrap();
+            db.insert_document(
+                "sessions",
+                json!({ "user": "new", "seen_at": 4102444800u64 }),
+            )
+            .unwrap();
+            db.create_index(
+                "sessions",
+                "expiry",
+                IndexKind::Ttl {
+                    field: "seen_at".to_string(),
+                    expire_after_secs: 60,
+                },
+            )
+            .unwrap();
+
+            assert_eq!(db.get_all_documents("sessions").unwrap().len(), 1);
+            assert!(db.get_document("sessions", "user", &json!("old")).is_err());
+            let results = db
+                .get_documents("sessions", &json!({}), &json!({}))
+                .unwrap();
+            assert_eq!(results.len(), 1);
+        }
+
+        let mut reopened = Nosqlite::open(db_path_str).unwrap();
+        let sessions = reopened.list_collections();
+        assert_eq!(sessions[0].document_count(), 1);
+    }
+
+    #[test]
+    fn get_all_documents_should_hide_expired_documents_before_the_purge() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let wal_path = File::wal_path(db_path_str);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("sessions", json!({})).unwrap();
+        db.create_index(
+            "sessions",
+            "expiry",
+            IndexKind::Ttl {
+                field: "seen_at".to_string(),
+                expire_after_secs: 60,
+            },
+        )
+        .unwrap();
+        db.insert_document("sessions", json!({ "seen_at": 0 }))
+            .unwrap();
+        db.insert_document("sessions", json!({ "seen_at": 4102444800u64 }))
+            .unwrap();
+        assert!(std::path::Path::new(&wal_path).exists());
+
+        let docs = db.get_all_documents("sessions").unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].data["seen_at"], 4102444800u64);
+    }
+
+    #[test]
+    fn geo_queries_should_filter_and_sort_by_distance() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("stores", json!({ "name": "string", "loc": "point" }))
+            .unwrap();
+        db.insert_document(
+            "stores",
+            json!({ "name": "Louvre", "loc": { "lat": 48.8606, "lng": 2.3376 } }),
+        )
+        .unwrap();
+        db.insert_document(
+            "stores",
+            json!({ "name": "Versailles", "loc": { "lat": 48.8049, "lng": 2.1204 } }),
+        )
+        .unwrap();
+        db.insert_document(
+            "stores",
+            json!({ "name": "London", "loc": { "type": "Point", "coordinates": [-0.1278, 51.5074] } }),
+        )
+        .unwrap();
+        assert!(db
+            .insert_document(
+                "stores",
+                json!({ "name": "Nowhere", "loc": { "lat": 200 } })
+            )
+            .is_err());
+
+        let center = json!({ "lat": 48.8566, "lng": 2.3522 });
+        let within =
+            json!({ "loc": { "$geoWithin": { "$circle": { "center": center, "radius_km": 5 } } } });
+        let results = db.get_documents("stores", &within, &json!({})).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["name"], "Louvre");
+
+        let nearest = json!({ "loc": { "$near": { "point": center, "limit": 2 } } });
+        let results = db.get_documents("stores", &nearest, &json!({})).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["name"], "Louvre");
+        assert_eq!(results[1]["name"], "Versailles");
+        assert!(results[0]["_distance"].as_f64() < results[1]["_distance"].as_f64());
+
+        let plan = db.explain("stores", &nearest, &json!({})).unwrap();
+        assert_eq!(plan.access_path, AccessPath::FullScan);
+
+        db.create_index(
+            "stores",
+            "stores_loc",
+            IndexKind::Geo {
+                field: "loc".to_string(),
+            },
+        )
+        .unwrap();
+        let indexed = db.get_documents("stores", &nearest, &json!({})).unwrap();
+        assert_eq!(indexed, results);
+
+        let plan = db.explain("stores", &within, &json!({})).unwrap();
+        assert_eq!(
+            plan.access_path,
+            AccessPath::Index("stores_loc".to_string())
+        );
+        assert_eq!(plan.documents_examined, 1);
+        assert_eq!(plan.documents_returned, 1);
+    }
+
+    #[test]
+    fn geo_index_queries_should_skip_expired_documents() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("stores", json!({ "loc": "point" }))
+            .unwrap();
+        db.insert_document(
+            "stores",
+            json!({ "name": "Closed", "seen_at": 0, "loc": { "lat": 48.8566, "lng": 2.3522 } }),
+        )
+        .unwrap();
+        db.insert_document(
+            "stores",
+            json!({ "name": "Open", "seen_at": 4102444800u64, "loc": { "lat": 48.8606, "lng": 2.3376 } }),
+        )
+        .unwrap();
+        for (name, kind) in [
+            (
+                "stores_loc",
+                IndexKind::Geo {
+                    field: "loc".to_string(),
+                },
+            ),
+            (
+                "expiry",
+                IndexKind::Ttl {
+                    field: "seen_at".to_string(),
+                    expire_after_secs: 60,
+                },
+            ),
+        ] {
+            db.create_index("stores", name, kind).unwrap();
+        }
+
+        let nearest = json!({ "loc": { "$near": { "point": { "lat": 48.8566, "lng": 2.3522 }, "limit": 2 } } });
+        let results = db.get_documents("stores", &nearest, &json!({})).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["name"], "Open");
+
+        let plan = db.explain("stores", &nearest, &json!({})).unwrap();
+        assert_eq!(
+            plan.access_path,
+            AccessPath::Index("stores_loc".to_string())
+        );
+    }
+
+    #[test]
+    fn geo_query_with_invalid_shape_should_fail() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("stores", json!({})).unwrap();
+        let filter = json!({ "loc": { "$geoWithin": { "$polygon": [{ "lat": 0, "lng": 0 }] } } });
+        assert!(db.get_documents("stores", &filter, &json!({})).is_err());
+    }
+
+    #[test]
+    fn k_nearest_should_rank_by_metric() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("docs", json!({ "embedding": "vector(2)" }))
+            .unwrap();
+        db.insert_document("docs", json!({ "name": "east", "embedding": [1.0, 0.0] }))
+            .unwrap();
+        db.insert_document(
+            "docs",
+            json!({ "name": "far-east", "embedding": [10.0, 0.5] }),
+        )
+        .unwrap();
+        db.insert_document("docs", json!({ "name": "north", "embedding": [0.0, 1.0] }))
+            .unwrap();
+        assert!(db
+            .insert_document(
+                "docs",
+                json!({ "name": "bad", "embedding": [1.0, 2.0, 3.0] })
+            )
+            .is_err());
+
+        let cosine = db
+            .k_nearest("docs", "embedding", &[1.0, 0.0], 2, VectorMetric::Cosine)
+            .unwrap();
+        assert_eq!(cosine.len(), 2);
+        assert_eq!(cosine[0]["name"], "east");
+        assert_eq!(cosine[0]["_score"], 1.0);
+
+        let dot = db
+            .k_nearest("docs", "embedding", &[1.0, 0.0], 1, VectorMetric::Dot)
+            .unwrap();
+        assert_eq!(dot[0]["name"], "far-east");
+
+        let l2 = db
+            .k_nearest("docs", "embedding", &[0.1, 0.9], 3, VectorMetric::L2)
+            .unwrap();
+        assert_eq!(l2[0]["name"], "north");
+        assert_eq!(l2[2]["name"], "far-east");
+        assert!(l2[0]["_distance"].as_f64() < l2[1]["_distance"].as_f64());
+
+        assert!(db
+            .k_nearest("docs", "embedding", &[1.0, 0.0, 0.0], 1, VectorMetric::L2)
+            .is_err());
+    }
+
+    #[test]
+    fn k_nearest_should_use_vector_index() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("docs", json!({ "embedding": "vector(2)" }))
+            .unwrap();
+        for i in 0..50 {
+            let angle = i as f64 / 50.0 * std::f64::consts::PI;
+            db.insert_document(
+                "docs",
+                json!({ "n": i, "embedding": [angle.cos(), angle.sin()] }),
+            )
+            .unwrap();
+        }
+        let exact = db
+            .k_nearest("docs", "embedding", &[1.0, 0.05], 5, VectorMetric::Cosine)
+            .unwrap();
+
+        db.create_index(
+            "docs",
+            "embedding_hnsw",
+            IndexKind::Vector {
+                field: "embedding".to_string(),
+                metric: VectorMetric::Cosine,
+            },
+        )
+        .unwrap();
+        let approximate = db
+            .k_nearest("docs", "embedding", &[1.0, 0.05], 5, VectorMetric::Cosine)
+            .unwrap();
+        assert_eq!(approximate, exact);
+
+        db.delete_documents("docs", "n", &json!(1)).unwrap();
+        let after_delete = db
+            .k_nearest("docs", "embedding", &[1.0, 0.05], 5, VectorMetric::Cosine)
+            .unwrap();
+        assert!(after_delete.iter().all(|doc| doc["n"] != 1));
+        assert_eq!(after_delete.len(), 5);
+    }
+
+    #[test]
+    fn k_nearest_with_vector_index_should_skip_expired_documents() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("docs", json!({ "embedding": "vector(2)" }))
+            .unwrap();
+        db.insert_document(
+            "docs",
+            json!({ "name": "old", "seen_at": 0, "embedding": [1.0, 0.0] }),
+        )
+        .unwrap();
+        db.insert_document(
+            "docs",
+            json!({ "name": "new", "seen_at": 4102444800u64, "embedding": [0.9, 0.1] }),
+        )
+        .unwrap();
+        for (name, kind) in [
+            (
+                "embedding_hnsw",
+                IndexKind::Vector {
+                    field: "embedding".to_string(),
+                    metric: VectorMetric::Cosine,
+                },
+            ),
+            (
+                "expiry",
+                IndexKind::Ttl {
+                    field: "seen_at".to_string(),
+                    expire_after_secs: 60,
+                },
+            ),
+        ] {
+            db.create_index("docs", name, kind).unwrap();
+        }
+
+        let results = db
+            .k_nearest("docs", "embedding", &[1.0, 0.0], 2, VectorMetric::Cosine)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["name"], "new");
+    }
+
+    #[test]
+    fn open_with_sidecar_key_should_work_from_any_directory() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let options = NosqliteOptions::default().with_key_source(KeySource::Sidecar);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        let key_path = db_path.replace(".nosqlite", ".key");
+        assert!(std::path::Path::new(&key_path).exists());
+        drop(db);
+
+        let absolute = std::fs::canonicalize(&db_path).unwrap();
+        let mut reopened =
+            Nosqlite::open_with_options(absolute.to_str().unwrap(), options.clone()).unwrap();
+        assert_eq!(reopened.list_collections().len(), 1);
+    }
+
+    #[test]
+    fn open_with_wrong_key_should_report_mismatch() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([1u8; 32]));
+        let mut db = Nosqlite::open_with_options(db_path_str, options).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        drop(db);
+
+        let wrong = NosqliteOptions::default().with_key_source(KeySource::Bytes([2u8; 32]));
+        let result = Nosqlite::open_with_options(db_path_str, wrong);
+        assert!(matches!(result, Err(NosqliteError::KeyMismatch(_))));
+    }
+
+    #[test]
+    fn open_with_env_key_should_read_variable() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let variable = format!("NOSQLITE_TEST_KEY_{}", rand::random::<u64>());
+        std::env::set_var(&variable, "ab".repeat(32));
+
+        let options = NosqliteOptions::default().with_key_source(KeySource::Env(variable.clone()));
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        drop(db);
+        assert!(Nosqlite::open_with_options(db_path_str, options).is_ok());
+
+        let same_bytes = NosqliteOptions::default().with_key_source(KeySource::Bytes([0xab; 32]));
+        assert!(Nosqlite::open_with_options(db_path_str, same_bytes).is_ok());
+
+        let missing = NosqliteOptions::default()
+            .with_key_source(KeySource::Env(format!("{}_MISSING", variable)));
+        let result = Nosqlite::open_with_options(db_path_str, missing);
+        assert!(matches!(result, Err(NosqliteError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn open_existing_database_without_key_file_should_fail() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let key_path = db_path.replace(".nosqlite", ".custom.key");
+
+        let options = NosqliteOptions::default().with_key_source(KeySource::File(key_path.clone()));
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        std::fs::remove_file(&key_path).unwrap();
+        drop(db);
+
+        let result = Nosqlite::open_with_options(db_path_str, options);
+        assert!(matches!(result, Err(NosqliteError::InvalidKey(_))));
+        assert!(!std::path::Path::new(&key_path).exists());
+    }
+
+    fn passphrase_options(passphrase: &str) -> NosqliteOptions {
+        NosqliteOptions::default()
+            .with_key_source(KeySource::Passphrase(passphrase.to_string()))
+            .with_kdf(Kdf::Argon2id {
+                memory_kib: 1024,
+                iterations: 1,
+                parallelism: 1,
+            })
+    }
+
+    #[test]
+    fn open_with_passphrase_should_derive_key() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db =
+            Nosqlite::open_with_options(db_path_str, passphrase_options("open sesame")).unwrap();
+        db.create_collection("secrets", json!({})).unwrap();
+        drop(db);
+
+        let mut reopened =
+            Nosqlite::open_with_options(db_path_str, passphrase_options("open sesame")).unwrap();
+        assert_eq!(reopened.list_collections().len(), 1);
+        drop(reopened);
+
+        let wrong = Nosqlite::open_with_options(db_path_str, passphrase_options("open barley"));
+        assert!(matches!(wrong, Err(NosqliteError::KeyMismatch(_))));
+
+        let key_file = NosqliteOptions::default().with_key_source(KeySource::Sidecar);
+        let result = Nosqlite::open_with_options(db_path_str, key_file);
+        assert!(matches!(result, Err(NosqliteError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn change_passphrase_should_reencrypt_database() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([9u8; 32]));
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("secrets", json!({})).unwrap();
+        assert!(db.change_passphrase("").is_err());
+        db.change_passphrase("first").unwrap();
+        db.insert_document("secrets", json!({ "value": 42 }))
+            .unwrap();
+        drop(db);
+
+        let result = Nosqlite::open_with_options(db_path_str, options);
+        assert!(matches!(result, Err(NosqliteError::InvalidKey(_))));
+
+        let mut db = Nosqlite::open_with_options(db_path_str, passphrase_options("first")).unwrap();
+        db.change_passphrase("second").unwrap();
+        drop(db);
+        assert!(Nosqlite::open_with_options(db_path_str, passphrase_options("first")).is_err());
+
+        let mut reopened =
+            Nosqlite::open_with_options(db_path_str, passphrase_options("second")).unwrap();
+        let docs = reopened.get_all_documents("secrets").unwrap();
+        assert_eq!(docs[0].data["value"], 42);
+    }
+
+    #[test]
+    fn rotate_key_should_reencrypt_with_new_key() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let old = NosqliteOptions::default().with_key_source(KeySource::Bytes([3u8; 32]));
+        let new = NosqliteOptions::default().with_key_source(KeySource::Bytes([4u8; 32]));
+
+        let mut db = Nosqlite::open_with_options(db_path_str, old.clone()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        let result = db.rotate_key(KeySource::Bytes([3u8; 32]));
+        assert!(matches!(result, Err(NosqliteError::InvalidKey(_))));
+
+        db.rotate_key(KeySource::Bytes([4u8; 32])).unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        assert!(!std::path::Path::new(&format!("{}.tmp", db_path)).exists());
+        drop(db);
+
+        let result = Nosqlite::open_with_options(db_path_str, old);
+        match result {
+            Err(NosqliteError::KeyMismatch(message)) => {
+                let key_id = EncryptionKey::new([4u8; 32], None).key_id();
+                assert!(message.contains(&key_id));
+            }
+            other => panic!("expected a key mismatch, got {:?}", other.map(|_| ())),
+        }
+
+        let mut reopened = Nosqlite::open_with_options(db_path_str, new).unwrap();
+        let docs = reopened.get_all_documents("users").unwrap();
+        assert_eq!(docs[0].data["name"], "Alice");
+    }
+
+    #[test]
+    fn fallback_key_should_open_and_migrate_database() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let key_path = db_path.replace(".nosqlite", ".new.key");
+
+        let old = NosqliteOptions::default().with_key_source(KeySource::Bytes([5u8; 32]));
+        let mut db = Nosqlite::open_with_options(db_path_str, old.clone()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        drop(db);
+
+        let migrating = NosqliteOptions::default()
+            .with_key_source(KeySource::File(key_path.clone()))
+            .with_fallback_key(KeySource::Bytes([5u8; 32]));
+        let mut db = Nosqlite::open_with_options(db_path_str, migrating.clone()).unwrap();
+        assert_eq!(db.list_collections().len(), 1);
+        db.create_collection("orders", json!({})).unwrap();
+        drop(db);
+
+        assert!(Nosqlite::open_with_options(db_path_str, old).is_err());
+        let new = NosqliteOptions::default().with_key_source(KeySource::File(key_path));
+        let mut reopened = Nosqlite::open_with_options(db_path_str, new).unwrap();
+        assert_eq!(reopened.list_collections().len(), 2);
+        drop(reopened);
+        assert!(Nosqlite::open_with_options(db_path_str, migrating).is_ok());
+    }
+
+    #[test]
+    fn unencrypted_database_should_be_plain_json() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let plain = NosqliteOptions::default().with_key_source(KeySource::Unencrypted);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, plain.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        let content = std::fs::read_to_string(db_path_str).unwrap();
+        assert!(content.starts_with("NOSQLITE {\"version\":4,\"plaintext\":true,\"checksum\":"));
+        assert!(content.contains("\"Alice\""));
+        drop(db);
+
+        let keyed = NosqliteOptions::default().with_key_source(KeySource::Bytes([6u8; 32]));
+        let result = Nosqlite::open_with_options(db_path_str, keyed.clone());
+        assert!(matches!(result, Err(NosqliteError::InvalidKey(_))));
+
+        let mut db = Nosqlite::open_with_options(db_path_str, plain.clone()).unwrap();
+        db.rotate_key(KeySource::Bytes([6u8; 32])).unwrap();
+        assert!(!std::fs::read_to_string(db_path_str)
+            .unwrap()
+            .contains("Alice"));
+        drop(db);
+        let result = Nosqlite::open_with_options(db_path_str, plain.clone());
+        assert!(matches!(result, Err(NosqliteError::InvalidKey(_))));
+
+        let mut db = Nosqlite::open_with_options(db_path_str, keyed).unwrap();
+        db.rotate_key(KeySource::Unencrypted).unwrap();
+        drop(db);
+        let mut reopened = Nosqlite::open_with_options(db_path_str, plain).unwrap();
+        let docs = reopened.get_all_documents("users").unwrap();
+        assert_eq!(docs[0].data["name"], "Alice");
+    }
+
+    fn wal_options() -> NosqliteOptions {
+        NosqliteOptions::default()
+            .with_key_source(KeySource::Bytes([10u8; 32]))
+            .with_wal(true)
+    }
+
+    #[test]
+    fn wal_should_replay_changes_on_open() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let wal_path = File::wal_path(db_path_str);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("users", json!({ "name": "string", "age": "number" }))
+            .unwrap();
+        db.create_index(
+            "users",
+            "by_name",
+            IndexKind::Text {
+                fields: vec!["name".into()],
+            },
+        )
+        .unwrap();
+        db.insert_document("users", json!({ "name": "Alice", "age": 30 }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Bob", "age": 25 }))
+            .unwrap();
+        db.update_documents_field("users", "name", &json!("Bob"), "age", json!(26))
+            .unwrap();
+        db.delete_documents("users", "name", &json!("Alice"))
+            .unwrap();
+
+        assert!(!std::path::Path::new(db_path_str).exists());
+        assert!(std::path::Path::new(&wal_path).exists());
+        drop(db);
+
+        let mut reopened = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        let docs = reopened.get_all_documents("users").unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].data["age"], 26);
+        assert_eq!(reopened.list_indexes("users").unwrap().len(), 1);
+        assert!(std::path::Path::new(db_path_str).exists());
+        assert!(!std::path::Path::new(&wal_path).exists());
+    }
+
+    #[test]
+    fn wal_should_checkpoint_when_log_grows() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let options = wal_options().with_wal_checkpoint_bytes(1);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        assert!(std::path::Path::new(db_path_str).exists());
+        assert!(!std::path::Path::new(&File::wal_path(db_path_str)).exists());
+    }
+
+    #[test]
+    fn wal_should_not_replay_records_already_checkpointed() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let wal_path = File::wal_path(db_path_str);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+
+        // A crash between the checkpoint and the removal of the log leaves the old log behind.
+        let log = std::fs::read(&wal_path).unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+        std::fs::write(&wal_path, log).unwrap();
+
+        let mut reopened = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        assert_eq!(reopened.get_all_documents("users").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn wal_should_ignore_torn_last_record_and_reject_corruption() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let wal_path = File::wal_path(db_path_str);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        drop(db);
+        let log = std::fs::read_to_string(&wal_path).unwrap();
+
+        std::fs::write(&wal_path, format!("{}AAAA", log)).unwrap();
+        let mut reopened = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        assert_eq!(reopened.list_collections().len(), 1);
+        drop(reopened);
+
+        std::fs::remove_file(db_path_str).unwrap();
+        std::fs::write(&wal_path, format!("AAAA\n{}", log)).unwrap();
+        let result = Nosqlite::open_with_options(db_path_str, wal_options());
+        assert!(matches!(
+            result,
+            Err(NosqliteError::InvalidDatabaseFormat(_))
+        ));
+    }
+
+    #[test]
+    fn transaction_should_persist_on_commit_and_restore_on_error() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("orders", json!({ "item": "string" }))
+            .unwrap();
+        db.create_collection("stock", json!({ "item": "string", "count": "number" }))
+            .unwrap();
+        db.insert_document("stock", json!({ "item": "book", "count": 2 }))
+            .unwrap();
+
+        let result = db.transaction(|tx| {
+            tx.insert_document("orders", json!({ "item": "book" }))?;
+            tx.update_documents_field("stock", "item", &json!("book"), "count", json!(1))?;
+            Ok("done")
+        });
+        assert_eq!(result.unwrap(), "done");
+
+        let result = db.transaction(|tx| {
+            tx.insert_document("orders", json!({ "item": "book" }))?;
+            tx.delete_documents("stock", "item", &json!("pen"))
+        });
+        assert!(matches!(result, Err(NosqliteError::DocumentNotFound(_))));
+        assert_eq!(db.get_all_documents("orders").unwrap().len(), 1);
+        drop(db);
+
+        let mut reopened = Nosqlite::open(db_path_str).unwrap();
+        assert_eq!(reopened.get_all_documents("orders").unwrap().len(), 1);
+        let stock = reopened.get_all_documents("stock").unwrap();
+        assert_eq!(stock[0].data["count"], 1);
+    }
+
+    #[test]
+    fn transaction_should_be_written_as_one_wal_record() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let wal_path = File::wal_path(db_path_str);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        db.begin().unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Bob" }))
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&wal_path).unwrap().lines().count(),
+            1
+        );
+        db.commit().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&wal_path).unwrap().lines().count(),
+            2
+        );
+        drop(db);
+
+        let mut reopened = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        assert_eq!(reopened.get_all_documents("users").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn transaction_misuse_should_fail() {
+        let db_path = create_random_file_path();
+        let mut db = Nosqlite::open(db_path.as_str()).unwrap();
+
+        assert!(matches!(
+            db.commit(),
+            Err(NosqliteError::TransactionError(_))
+        ));
+        assert!(matches!(
+            db.rollback(),
+            Err(NosqliteError::TransactionError(_))
+        ));
+
+        db.begin().unwrap();
+        assert!(matches!(
+            db.begin(),
+            Err(NosqliteError::TransactionError(_))
+        ));
+        assert!(matches!(
+            db.checkpoint(),
+            Err(NosqliteError::TransactionError(_))
+        ));
+        db.create_collection("users", json!({})).unwrap();
+        db.rollback().unwrap();
+        assert!(db.list_collections().is_empty());
+    }
+
+    #[test]
+    fn manual_durability_should_write_only_on_flush() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let options = NosqliteOptions::default()
+            .with_key_source(KeySource::Bytes([11u8; 32]))
+            .with_durability(Durability::Manual);
+        let reopen_options =
+            NosqliteOptions::default().with_key_source(KeySource::Bytes([11u8; 32]));
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        assert!(!std::path::Path::new(db_path_str).exists());
+
+        db.flush().unwrap();
+        drop(db);
+        let mut reopened = Nosqlite::open_with_options(db_path_str, reopen_options).unwrap();
+        assert_eq!(reopened.get_all_documents("users").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn every_n_durability_should_batch_wal_records() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let wal_path = File::wal_path(db_path_str);
+        let options = wal_options().with_durability(Durability::EveryN(3));
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        assert!(!std::path::Path::new(&wal_path).exists());
+
+        db.insert_document("users", json!({ "name": "Bob" }))
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&wal_path).unwrap().lines().count(),
+            3
+        );
+        drop(db);
+
+        let mut reopened = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        assert_eq!(reopened.get_all_documents("users").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn failed_save_should_be_returned_by_mutations() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+
+        std::fs::create_dir_all(format!("{}.tmp", db_path_str)).unwrap();
+        assert!(db
+            .insert_document("users", json!({ "name": "Alice" }))
+            .is_err());
+        assert!(db.flush().is_err());
+
+        std::fs::remove_dir_all(format!("{}.tmp", db_path_str)).unwrap();
+        db.flush().unwrap();
+        drop(db);
+        let mut reopened = Nosqlite::open(db_path_str).unwrap();
+        assert_eq!(reopened.get_all_documents("users").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn read_only_open_should_never_write() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let log_path = db_path.replace(".nosqlite", ".log");
+        let key_path = db_path.replace(".nosqlite", ".key");
+        let read_only = NosqliteOptions::default()
+            .with_key_source(KeySource::Sidecar)
+            .with_read_only(true);
+
+        let result = Nosqlite::open_with_options(db_path_str, read_only.clone());
+        assert!(matches!(result, Err(NosqliteError::DatabaseNotFound(_))));
+        assert!(!std::path::Path::new(db_path_str).exists());
+        assert!(!std::path::Path::new(&key_path).exists());
+        assert!(!std::path::Path::new(&log_path).exists());
+
+        let options = wal_options().with_key_source(KeySource::Sidecar);
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        db.checkpoint().unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        drop(db);
+        let content = std::fs::read(db_path_str).unwrap();
+        let wal = std::fs::read(File::wal_path(db_path_str)).unwrap();
+
+        let mut db = Nosqlite::open_with_options(db_path_str, read_only).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        assert!(matches!(
+            db.insert_document("users", json!({ "name": "Bob" })),
+            Err(NosqliteError::ReadOnly(_))
+        ));
+        assert!(matches!(
+            db.delete_collection("users"),
+            Err(NosqliteError::ReadOnly(_))
+        ));
+        assert!(matches!(db.begin(), Err(NosqliteError::ReadOnly(_))));
+        assert!(matches!(db.checkpoint(), Err(NosqliteError::ReadOnly(_))));
+        assert!(matches!(
+            db.rotate_key(KeySource::Bytes([12u8; 32])),
+            Err(NosqliteError::ReadOnly(_))
+        ));
+        assert!(db.get_document("users", "name", &json!("Bob")).is_err());
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+
+        assert_eq!(std::fs::read(db_path_str).unwrap(), content);
+        assert_eq!(std::fs::read(File::wal_path(db_path_str)).unwrap(), wal);
+        assert!(!std::path::Path::new(&log_path).exists());
+    }
+
+    #[test]
+    fn read_only_open_should_not_create_or_delete_snapshots() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let snapshots_path = File::snapshots_path(db_path_str);
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+        db.create_snapshot("before").unwrap();
+        drop(db);
+        let listing = || {
+            let mut names: Vec<_> = std::fs::read_dir(&snapshots_path)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name())
+                .collect();
+            names.sort();
+            names
+        };
+        let before = listing();
+
+        let mut db = Nosqlite::open_read_only(db_path_str).unwrap();
+        assert!(matches!(
+            db.create_snapshot("after"),
+            Err(NosqliteError::ReadOnly(_))
+        ));
+        assert!(matches!(
+            db.delete_snapshot("before"),
+            Err(NosqliteError::ReadOnly(_))
+        ));
+        assert_eq!(db.list_snapshots().unwrap(), vec!["before".to_string()]);
+        assert_eq!(listing(), before);
+    }
+
+    #[test]
+    fn read_only_open_should_share_the_lock_with_readers_only() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let mut db = Nosqlite::open(db_path_str).unwrap();
+        db.create_collection("users", json!({})).unwrap();
+
+        assert!(matches!(
+            Nosqlite::open_read_only(db_path_str),
+            Err(NosqliteError::DatabaseLocked(_))
+        ));
+        drop(db);
+
+        let first = Nosqlite::open_read_only(db_path_str).unwrap();
+        let second = Nosqlite::open_read_only(db_path_str).unwrap();
+        assert!(matches!(
+            Nosqlite::open(db_path_str),
+            Err(NosqliteError::DatabaseLocked(_))
+        ));
+        drop(first);
+        drop(second);
+        assert!(Nosqlite::open(db_path_str).is_ok());
+    }
+
+    #[test]
+    fn in_memory_database_should_never_touch_the_filesystem() {
+        let mut db = Nosqlite::open_in_memory();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        assert!(db.insert_document("missing", json!({})).is_err());
+        db.transaction(|tx| tx.insert_document("users", json!({ "name": "Bob" })))
+            .unwrap();
+        db.flush().unwrap();
+        db.checkpoint().unwrap();
+        assert!(matches!(
+            db.rotate_key(KeySource::Bytes([13u8; 32])),
+            Err(NosqliteError::InvalidKey(_))
+        ));
+
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 2);
+        for path in [":memory:", ":memory:.lock", ":memory:.wal", ":memory:.tmp"] {
+            assert!(!std::path::Path::new(path).exists());
+        }
+    }
+
+    #[test]
+    fn in_memory_database_should_save_to_and_load_from_files() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let options = wal_options();
+
+        let mut memory = Nosqlite::open_in_memory();
+        memory
+            .create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        memory
+            .insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        memory.save_to(db_path_str, options.clone()).unwrap();
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        db.insert_document("users", json!({ "name": "Bob" }))
+            .unwrap();
+        assert!(matches!(
+            memory.save_to(db_path_str, options.clone()),
+            Err(NosqliteError::DatabaseLocked(_))
+        ));
+        drop(db);
+
+        let wal = std::fs::read(File::wal_path(db_path_str)).unwrap();
+        let mut loaded = Nosqlite::load_into_memory(db_path_str, options.clone()).unwrap();
+        assert_eq!(loaded.get_all_documents("users").unwrap().len(), 2);
+        loaded.delete_collection("users").unwrap();
+        assert_eq!(std::fs::read(File::wal_path(db_path_str)).unwrap(), wal);
+
+        let mut reopened = Nosqlite::open_with_options(db_path_str, options).unwrap();
+        assert_eq!(reopened.get_all_documents("users").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn memory_backend_should_persist_across_instances_and_replay_wal() {
+        let storage = MemoryBackend::new("users");
+        let options = wal_options();
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        assert!(storage.log().is_some());
+        assert!(matches!(
+            Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone()),
+            Err(NosqliteError::DatabaseLocked(_))
+        ));
+        drop(db);
+
+        let mut db = Nosqlite::open_with_backend(Box::new(storage.clone()), options).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        assert!(storage.log().is_none());
+        assert!(!std::path::Path::new("users.log").exists());
+    }
+
+    #[test]
+    fn unflushed_changes_should_be_lost_on_crash() {
+        let storage = MemoryBackend::new("crash");
+        let options = NosqliteOptions::default()
+            .with_key_source(KeySource::Bytes([14u8; 32]))
+            .with_durability(Durability::Manual);
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.flush().unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        drop(db);
+
+        let mut db = Nosqlite::open_with_backend(Box::new(storage), options).unwrap();
+        assert!(db.get_all_documents("users").unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_wal_appends_should_be_retried_by_flush() {
+        let backend = FaultyBackend::new(Box::new(MemoryBackend::new("faulty")));
+        let options = wal_options();
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(backend.clone()), options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+
+        backend.fail_writes_after(0);
+        assert!(matches!(
+            db.insert_document("users", json!({ "name": "Alice" })),
+            Err(NosqliteError::IoError(_))
+        ));
+        assert!(db.flush().is_err());
+
+        backend.heal();
+        db.flush().unwrap();
+        drop(db);
+
+        let mut db = Nosqlite::open_with_backend(Box::new(backend), options).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn failed_checkpoint_should_not_undo_a_durable_commit() {
+        let backend = FaultyBackend::new(Box::new(MemoryBackend::new("checkpoint")));
+        let options = wal_options().with_wal_checkpoint_bytes(1);
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(backend.clone()), options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+
+        db.begin().unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        backend.fail_writes_after(1);
+        db.commit().unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        drop(db);
+
+        backend.heal();
+        let mut db = Nosqlite::open_with_backend(Box::new(backend), options).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn messagepack_database_should_reopen_with_any_encoding() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let options = wal_options().with_encoding(Encoding::MessagePack);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+        assert!(std::fs::read_to_string(db_path_str).is_err());
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        db.insert_document("users", json!({ "name": "Bob" }))
+            .unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+        assert!(std::fs::read_to_string(db_path_str).is_ok());
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn compression_should_apply_to_the_next_save() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let options = NosqliteOptions::default().with_key_source(KeySource::Bytes([15u8; 32]));
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        for _ in 0..30 {
+            db.insert_document("users", json!({ "name": "Alice" }))
+                .unwrap();
+        }
+        let plain = db.payload_stats().unwrap();
+        assert_eq!(plain.compression, Compression::None);
+        assert_eq!(plain.compression_ratio(), 1.0);
+        drop(db);
+
+        let zstd = options.clone().with_compression(Compression::Zstd);
+        let mut db = Nosqlite::open_with_options(db_path_str, zstd).unwrap();
+        db.insert_document("users", json!({ "name": "Bob" }))
+            .unwrap();
+        let compressed = db.payload_stats().unwrap();
+        assert_eq!(compressed.compression, Compression::Zstd);
+        assert!(compressed.file_bytes < plain.file_bytes / 3);
+        drop(db);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 31);
+    }
+
+    fn segmented_options() -> NosqliteOptions {
+        NosqliteOptions::default()
+            .with_key_source(KeySource::Bytes([16u8; 32]))
+            .with_layout(Layout::Segmented)
+    }
+
+    #[test]
+    fn segmented_layout_should_load_collections_on_first_access() {
+        let backend = FaultyBackend::new(Box::new(MemoryBackend::new("lazy")));
+        let options = segmented_options();
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(backend.clone()), options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.create_collection("orders", json!({ "item": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        drop(db);
+
+        let mut db = Nosqlite::open_with_backend(Box::new(backend.clone()), options).unwrap();
+        backend.fail_reads(true);
+        assert!(matches!(
+            db.get_all_documents("users"),
+            Err(NosqliteError::IoError(_))
+        ));
+
+        backend.fail_reads(false);
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        backend.fail_reads(true);
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        assert!(db.get_all_documents("orders").is_err());
+
+        backend.fail_reads(false);
+        assert_eq!(db.load_all_collections().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn segmented_layout_should_rewrite_only_changed_collections() {
+        let storage = MemoryBackend::new("dirty");
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(storage.clone()), segmented_options()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.create_collection("orders", json!({ "item": "string" }))
+            .unwrap();
+        let before = storage.segments();
+        assert_eq!(before.len(), 2);
+
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        let after = storage.segments();
+        assert_eq!(after.len(), 2);
+        assert_eq!(after.iter().filter(|id| before.contains(id)).count(), 1);
+
+        db.delete_collection("orders").unwrap();
+        assert_eq!(storage.segments().len(), 1);
+    }
+
+    #[test]
+    fn layout_should_be_converted_on_the_next_save() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let single = NosqliteOptions::default().with_key_source(KeySource::Bytes([17u8; 32]));
+        let segments = File::segments_path(db_path_str);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, single.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        drop(db);
+
+        let segmented = single.clone().with_layout(Layout::Segmented);
+        let mut db = Nosqlite::open_with_options(db_path_str, segmented.clone()).unwrap();
+        db.create_collection("orders", json!({ "item": "string" }))
+            .unwrap();
+        drop(db);
+        assert_eq!(std::fs::read_dir(&segments).unwrap().count(), 2);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, single.clone()).unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+        assert_eq!(std::fs::read_dir(&segments).unwrap().count(), 0);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, segmented).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        assert_eq!(db.load_all_collections().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn segmented_layout_should_replay_wal_and_rotate_key() {
+        let storage = MemoryBackend::new("segmented-wal");
+        let options = segmented_options().with_wal(true);
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.create_collection("orders", json!({ "item": "string" }))
+            .unwrap();
+        db.checkpoint().unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        assert!(storage.log().is_some());
+        drop(db);
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone()).unwrap();
+        assert!(storage.log().is_none());
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        db.rotate_key(KeySource::Bytes([18u8; 32])).unwrap();
+        drop(db);
+
+        let rotated = options.with_key_source(KeySource::Bytes([18u8; 32]));
+        let mut db = Nosqlite::open_with_backend(Box::new(storage.clone()), rotated).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        assert!(db.get_all_documents("orders").unwrap().is_empty());
+        assert_eq!(storage.segments().len(), 2);
+    }
+
+    #[test]
+    fn verify_and_salvage_should_report_a_corrupted_segment() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let options = segmented_options().with_key_source(KeySource::Bytes([19u8; 32]));
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.create_collection("orders", json!({ "item": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        db.insert_document("orders", json!({ "item": "book" }))
+            .unwrap();
+        assert!(db.verify().unwrap().is_ok());
+        drop(db);
+
+        let segments = File::segments_path(db_path_str);
+        let mut entries: Vec<_> = std::fs::read_dir(&segments)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        entries.sort();
+        let mut content = std::fs::read(&entries[0]).unwrap();
+        let last = content.len() - 5;
+        content[last] ^= 0xff;
+        std::fs::write(&entries[0], content).unwrap();
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        let report = db.verify().unwrap();
+        assert_eq!(report.collections, 1);
+        assert_eq!(report.documents, 1);
+        assert_eq!(report.issues.len(), 1);
+        drop(db);
+
+        let (mut salvaged, report) = Nosqlite::salvage(db_path_str, options.clone()).unwrap();
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(salvaged.load_all_collections().unwrap().len(), 1);
+
+        let recovered = create_random_file_path();
+        salvaged.save_to(&recovered, options.clone()).unwrap();
+        let mut db = Nosqlite::open_with_options(&recovered, options).unwrap();
+        assert!(db.verify().unwrap().is_ok());
+        assert_eq!(db.load_all_collections().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn backup_should_copy_the_open_database_with_its_own_key() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+        let options = wal_options().with_durability(Durability::Manual);
+
+        let mut db = Nosqlite::open_with_options(db_path_str, options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+
+        let same_key = create_random_file_path();
+        db.backup_to(&same_key, None).unwrap();
+        let other_key = create_random_file_path();
+        let rotated = KeySource::Bytes([20u8; 32]);
+        db.backup_to(&other_key, Some(rotated.clone())).unwrap();
+
+        db.begin().unwrap();
+        assert!(matches!(
+            db.backup_to(&create_random_file_path(), None),
+            Err(NosqliteError::TransactionError(_))
+        ));
+        db.rollback().unwrap();
+        db.insert_document("users", json!({ "name": "Bob" }))
+            .unwrap();
+
+        let mut backup = Nosqlite::open_with_options(&same_key, options.clone()).unwrap();
+        assert_eq!(backup.get_all_documents("users").unwrap().len(), 1);
+        let mut backup =
+            Nosqlite::open_with_options(&other_key, options.with_key_source(rotated)).unwrap();
+        assert_eq!(backup.get_all_documents("users").unwrap().len(), 1);
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn snapshots_should_be_listed_restored_and_deleted() {
+        let storage = MemoryBackend::new("snapshots");
+        let options = segmented_options();
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.insert_document("users", json!({ "name": "Alice" }))
+            .unwrap();
+        db.create_snapshot("v1").unwrap();
+        db.create_collection("orders", json!({ "item": "string" }))
+            .unwrap();
+        db.delete_documents("users", "name", &json!("Alice"))
+            .unwrap();
+        db.create_snapshot("v2").unwrap();
+        assert_eq!(db.list_snapshots().unwrap(), vec!["v1", "v2"]);
+        assert!(matches!(
+            db.create_snapshot("../escape"),
+            Err(NosqliteError::InvalidSnapshotName(_))
+        ));
+        assert!(matches!(
+            db.restore_snapshot("v3"),
+            Err(NosqliteError::SnapshotNotFound(_))
+        ));
+        drop(db);
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone()).unwrap();
+        db.restore_snapshot("v1").unwrap();
+        assert_eq!(db.load_all_collections().unwrap().len(), 1);
+        assert_eq!(storage.segments().len(), 1);
+        drop(db);
+
+        let mut db = Nosqlite::open_with_backend(Box::new(storage.clone()), options).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 1);
+        db.delete_snapshot("v1").unwrap();
+        assert_eq!(db.list_snapshots().unwrap(), vec!["v2"]);
+
+        let mut memory = Nosqlite::open_in_memory();
+        assert!(memory.create_snapshot("v1").is_err());
+    }
+
+    #[test]
+    fn vacuum_should_rewrite_storage_from_live_data() {
+        let storage = MemoryBackend::new("vacuum");
+        let options = segmented_options().with_wal(true);
+
+        let mut db =
+            Nosqlite::open_with_backend(Box::new(storage.clone()), options.clone()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        db.create_index(
+            "users",
+            "by_name",
+            IndexKind::Text {
+                fields: vec!["name".into()],
+            },
+        )
+        .unwrap();
+        for i in 0..20 {
+            db.insert_document("users", json!({ "name": format!("user {}", i) }))
+                .unwrap();
+        }
+        db.delete_documents("users", "name", &json!("user 3"))
+            .unwrap();
+        let mut handler = NosqliteErrorHandler::in_memory("vacuum".to_string());
+        let mut orphan = storage.clone();
+        orphan
+            .save_segment("orphan", &[0u8; 512], &mut handler)
+            .unwrap();
+        assert!(storage.log().is_some());
+
+        let report = db.vacuum().unwrap();
+        assert!(report.reclaimed_bytes() > 512);
+        assert_eq!(report.rebuilt_indexes, 1);
+        assert!(storage.log().is_none());
+        assert_eq!(storage.segments().len(), 1);
+        drop(db);
+
+        let mut db = Nosqlite::open_with_backend(Box::new(storage.clone()), options).unwrap();
+        assert_eq!(db.get_all_documents("users").unwrap().len(), 19);
+        assert_eq!(db.vacuum().unwrap().reclaimed_bytes(), 0);
+
+        db.begin().unwrap();
+        assert!(matches!(
+            db.vacuum(),
+            Err(NosqliteError::TransactionError(_))
+        ));
+    }
+
+    #[test]
+    fn import_collection_should_be_replayed_from_wal() {
+        let db_path = create_random_file_path();
+        let db_path_str = db_path.as_str();
+
+        let mut db = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        db.create_collection("users", json!({ "name": "string" }))
+            .unwrap();
+        let input = "name,age\nAlice,30\nBob,25\n";
+        assert_eq!(
+            db.import_collection("users", ExportFormat::Csv, input.as_bytes())
+                .unwrap(),
+            2
+        );
+        drop(db);
+
+        let mut reopened = Nosqlite::open_with_options(db_path_str, wal_options()).unwrap();
+        let mut output = Vec::new();
+        reopened
+            .export_collection("users", ExportFormat::Ndjson, &mut output)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"age\":30,\"name\":\"Alice\"}\n{\"age\":25,\"name\":\"Bob\"}\n"
+        );
+    }
 }
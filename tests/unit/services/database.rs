@@ -1,7 +1,10 @@
 use nosqlite_rust::engine::{
     error::{NosqliteError, NosqliteErrorHandler},
     models::database::model::Database,
-    services::database_service::{load_or_create_database, save_database},
+    models::{KeySource, NosqliteOptions},
+    services::database_service::{
+        load_or_create_database, load_or_create_database_with_options, save_database,
+    },
 };
 use serde_json::json;
 use std::fs;
@@ -58,3 +61,47 @@ fn save_and_reload_should_persist_data() {
     cleanup(&db_path);
     cleanup(&log_path);
 }
+
+#[test]
+fn load_should_migrate_unversioned_file() {
+    let (db_path, log_path) = temp_paths();
+    let mut handler = NosqliteErrorHandler::new(log_path.clone());
+
+    let mut db = Database::new(&db_path);
+    db.add_collection("legacy", json!({ "field": "string" }), &mut handler)
+        .unwrap();
+    let payload = serde_json::to_string_pretty(&db).unwrap();
+    fs::write(
+        &db_path,
+        format!("NOSQLITE {{\"plaintext\":true}}\n{}", payload),
+    )
+    .unwrap();
+
+    let options = NosqliteOptions::default().with_key_source(KeySource::Unencrypted);
+    let reloaded = load_or_create_database_with_options(&db_path, &options, &mut handler).unwrap();
+    assert!(reloaded.get_collection("legacy").is_some());
+
+    let content = fs::read_to_string(&db_path).unwrap();
+    assert!(content.starts_with("NOSQLITE {\"version\":4,"));
+
+    cleanup(&db_path);
+    cleanup(&log_path);
+}
+
+#[test]
+fn load_should_reject_newer_format_version() {
+    let (db_path, log_path) = temp_paths();
+    let mut handler = NosqliteErrorHandler::new(log_path.clone());
+
+    fs::write(&db_path, "NOSQLITE {\"version\":99,\"plaintext\":true}\n{}").unwrap();
+
+    let options = NosqliteOptions::default().with_key_source(KeySource::Unencrypted);
+    let result = load_or_create_database_with_options(&db_path, &options, &mut handler);
+    assert!(matches!(
+        result,
+        Err(NosqliteError::UnsupportedFormatVersion(_))
+    ));
+
+    cleanup(&db_path);
+    cleanup(&log_path);
+}
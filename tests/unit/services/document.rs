@@ -146,3 +146,22 @@ fn delete_nonexistent_documents_should_fail() {
     );
     assert!(res.is_err());
 }
+
+#[test]
+fn explain_documents_should_count_examined_and_returned() {
+    let (mut db, mut handler) = create_db_and_collection();
+    insert_document(&mut db, "users", json!({ "name": "Ivy" }), &mut handler).unwrap();
+    insert_document(&mut db, "users", json!({ "name": "John" }), &mut handler).unwrap();
+
+    let plan = explain_documents(
+        &db,
+        "users",
+        &json!({ "name": "Ivy" }),
+        &json!({}),
+        &mut handler,
+    )
+    .unwrap();
+    assert_eq!(plan.collection, "users");
+    assert_eq!(plan.documents_examined, 2);
+    assert_eq!(plan.documents_returned, 1);
+}
@@ -0,0 +1,167 @@
+use nosqlite_rust::engine::error::{NosqliteError, NosqliteErrorHandler};
+use nosqlite_rust::engine::models::{Database, ExportFormat};
+use nosqlite_rust::engine::services::export_service::{export_documents, import_documents};
+use serde_json::{json, Value};
+
+fn make_error_handler() -> NosqliteErrorHandler {
+    NosqliteErrorHandler::in_memory("export".to_string())
+}
+
+fn make_sample_database(handler: &mut NosqliteErrorHandler) -> Database {
+    let mut db = Database::default();
+    db.add_collection(
+        "users",
+        json!({ "name": "string", "age": "number", "address": { "city": "string" } }),
+        handler,
+    )
+    .unwrap();
+    let users = db.get_collection_mut("users").unwrap();
+    users
+        .add_document(
+            json!({ "name": "Alice", "age": 30, "active": true, "address": { "city": "Paris", "country": "FR" }, "tags": ["admin", "dev"] }),
+            handler,
+        )
+        .unwrap();
+    users
+        .add_document(
+            json!({ "name": "Bob", "age": 25.5, "address": { "city": "Lyon" } }),
+            handler,
+        )
+        .unwrap();
+    db
+}
+
+fn documents(db: &Database, name: &str) -> Vec<Value> {
+    db.get_collection(name)
+        .unwrap()
+        .documents
+        .iter()
+        .map(|document| document.data.clone())
+        .collect()
+}
+
+fn round_trip(format: ExportFormat) {
+    let mut handler = make_error_handler();
+    let mut db = make_sample_database(&mut handler);
+    let mut output = Vec::new();
+    assert_eq!(
+        export_documents(&db, "users", format, &mut output, &mut handler).unwrap(),
+        2
+    );
+
+    let structure = db.get_collection("users").unwrap().structure.clone();
+    db.add_collection("copy", structure, &mut handler).unwrap();
+    let count = import_documents(&mut db, "copy", format, output.as_slice(), &mut handler).unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(documents(&db, "copy"), documents(&db, "users"));
+}
+
+#[test]
+fn json_export_should_round_trip() {
+    round_trip(ExportFormat::Json);
+}
+
+#[test]
+fn ndjson_export_should_round_trip() {
+    round_trip(ExportFormat::Ndjson);
+}
+
+#[test]
+fn csv_export_should_round_trip() {
+    round_trip(ExportFormat::Csv);
+}
+
+#[test]
+fn csv_export_should_flatten_nested_fields_into_dot_paths() {
+    let mut handler = make_error_handler();
+    let db = make_sample_database(&mut handler);
+    let mut output = Vec::new();
+    export_documents(&db, "users", ExportFormat::Csv, &mut output, &mut handler).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines[0],
+        "active,address.city,address.country,age,name,tags"
+    );
+    assert_eq!(lines[1], r#"true,Paris,FR,30,Alice,"[""admin"",""dev""]""#);
+    assert_eq!(lines[2], ",Lyon,,25.5,Bob,");
+}
+
+#[test]
+fn csv_import_should_type_cells_by_the_collection_structure() {
+    let mut handler = make_error_handler();
+    let mut db = Database::default();
+    db.add_collection("codes", json!({ "code": "string" }), &mut handler)
+        .unwrap();
+
+    let input = "code,count\n007,12\n";
+    import_documents(
+        &mut db,
+        "codes",
+        ExportFormat::Csv,
+        input.as_bytes(),
+        &mut handler,
+    )
+    .unwrap();
+
+    assert_eq!(
+        documents(&db, "codes"),
+        vec![json!({ "code": "007", "count": 12 })]
+    );
+}
+
+#[test]
+fn import_should_insert_nothing_when_one_record_is_invalid() {
+    let mut handler = make_error_handler();
+    let mut db = make_sample_database(&mut handler);
+
+    let input = concat!(
+        r#"{ "name": "Carol", "age": 41, "address": { "city": "Nice" } }"#,
+        "\n",
+        r#"{ "name": "Dave", "age": "unknown", "address": { "city": "Nice" } }"#,
+        "\n",
+    );
+    let result = import_documents(
+        &mut db,
+        "users",
+        ExportFormat::Ndjson,
+        input.as_bytes(),
+        &mut handler,
+    );
+
+    assert!(
+        matches!(result, Err(NosqliteError::DocumentInvalid(message)) if message.contains("Record 2"))
+    );
+    assert_eq!(db.get_collection("users").unwrap().documents.len(), 2);
+}
+
+#[test]
+fn import_should_fail_on_malformed_input() {
+    let mut handler = make_error_handler();
+    let mut db = make_sample_database(&mut handler);
+
+    let result = import_documents(
+        &mut db,
+        "users",
+        ExportFormat::Json,
+        r#"{ "name": "Carol" }"#.as_bytes(),
+        &mut handler,
+    );
+
+    assert!(matches!(
+        result,
+        Err(NosqliteError::DeserializationError(_))
+    ));
+}
+
+#[test]
+fn export_should_fail_on_missing_collection() {
+    let mut handler = make_error_handler();
+    let db = make_sample_database(&mut handler);
+
+    let result = export_documents(&db, "missing", ExportFormat::Json, Vec::new(), &mut handler);
+
+    assert!(matches!(result, Err(NosqliteError::CollectionNotFound(_))));
+}
@@ -1,3 +1,5 @@
 pub mod collection;
 pub mod database;
 pub mod document;
+pub mod export;
+pub mod recovery;
@@ -0,0 +1,156 @@
+use nosqlite_rust::engine::error::{NosqliteError, NosqliteErrorHandler};
+use nosqlite_rust::engine::models::{
+    Database, EncryptionKey, File, Layout, MemoryBackend, StorageBackend, WalOperation, WalRecord,
+};
+use nosqlite_rust::engine::services::database_service::save_database_to_backend;
+use nosqlite_rust::engine::services::recovery_service::{salvage_database, verify_database};
+use serde_json::json;
+
+fn make_error_handler() -> NosqliteErrorHandler {
+    NosqliteErrorHandler::in_memory("recovery".to_string())
+}
+
+fn make_sample_database(handler: &mut NosqliteErrorHandler) -> Database {
+    let mut db = Database::default();
+    db.add_collection("users", json!({ "name": "string" }), handler)
+        .unwrap();
+    db.add_collection("orders", json!({ "item": "string" }), handler)
+        .unwrap();
+    let users = db.get_collection_mut("users").unwrap();
+    for i in 0..10 {
+        users
+            .add_document(json!({ "name": format!("user {}", i) }), handler)
+            .unwrap();
+    }
+    db
+}
+
+#[test]
+fn salvage_should_skip_corrupted_segment_and_keep_the_others() {
+    let mut handler = make_error_handler();
+    let key = EncryptionKey::new([19u8; 32], None);
+    let mut backend = MemoryBackend::new("segments");
+    let mut db = make_sample_database(&mut handler);
+    save_database_to_backend(&mut backend, &mut db, &key, Layout::Segmented, &mut handler).unwrap();
+
+    let orders = db.segments.segment_of("orders").unwrap().to_string();
+    let mut content = backend
+        .load_segment(&orders, &mut handler)
+        .unwrap()
+        .unwrap();
+    let last = content.len() - 5;
+    content[last] ^= 0xff;
+    backend
+        .save_segment(&orders, &content, &mut handler)
+        .unwrap();
+
+    let (salvaged, report) = salvage_database(&mut backend, &key, &mut handler).unwrap();
+    assert_eq!(salvaged.collections.len(), 1);
+    assert_eq!(
+        salvaged.get_collection("users").unwrap().documents.len(),
+        10
+    );
+    assert_eq!(report.collections, 1);
+    assert_eq!(report.documents, 10);
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.issues[0].location.contains("orders"));
+    assert!(matches!(
+        report.issues[0].error,
+        NosqliteError::Corrupted(_)
+    ));
+}
+
+#[test]
+fn salvage_should_skip_unreadable_documents_of_a_plaintext_file() {
+    let mut handler = make_error_handler();
+    let key = EncryptionKey::unencrypted();
+    let mut backend = MemoryBackend::new("plaintext");
+    let mut db = make_sample_database(&mut handler);
+    save_database_to_backend(&mut backend, &mut db, &key, Layout::Single, &mut handler).unwrap();
+
+    let content = String::from_utf8(backend.data().unwrap()).unwrap();
+    let damaged = content.replacen("\"updated_at\": ", "\"updated_at\": \"lost\", \"x\": ", 1);
+    backend.save(damaged.as_bytes(), &mut handler).unwrap();
+
+    let (salvaged, report) = salvage_database(&mut backend, &key, &mut handler).unwrap();
+    assert_eq!(salvaged.collections.len(), 2);
+    assert_eq!(salvaged.get_collection("users").unwrap().documents.len(), 9);
+    assert_eq!(report.issues.len(), 2);
+    assert_eq!(report.issues[0].location, "data file");
+    assert_eq!(report.issues[1].location, "collection 'users', document 1");
+}
+
+#[test]
+fn salvage_should_replay_wal_up_to_the_first_corrupted_record() {
+    let mut handler = make_error_handler();
+    let key = EncryptionKey::new([19u8; 32], None);
+    let mut backend = MemoryBackend::new("wal");
+    let mut db = make_sample_database(&mut handler);
+    save_database_to_backend(&mut backend, &mut db, &key, Layout::Single, &mut handler).unwrap();
+
+    let records: Vec<WalRecord> = (1..=3)
+        .map(|sequence| {
+            let mut collection = db.get_collection("orders").unwrap().clone();
+            collection.name = format!("archive {}", sequence);
+            WalRecord {
+                sequence,
+                operation: WalOperation::CreateCollection { collection },
+            }
+        })
+        .collect();
+    let log = File::encode_wal(&records, &key, &mut handler).unwrap();
+    let log = String::from_utf8(log).unwrap();
+    let mut lines: Vec<&str> = log.lines().collect();
+    lines[1] = "garbage";
+    backend
+        .append_log(format!("{}\n", lines.join("\n")).as_bytes(), &mut handler)
+        .unwrap();
+
+    let (salvaged, report) = salvage_database(&mut backend, &key, &mut handler).unwrap();
+    assert!(salvaged.get_collection("archive 1").is_some());
+    assert!(salvaged.get_collection("archive 3").is_none());
+    assert_eq!(report.wal_records, 1);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].location, "write-ahead log");
+
+    assert_eq!(
+        verify_database(&mut backend, &key, &mut handler)
+            .unwrap()
+            .issues
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn salvage_should_lose_the_whole_encrypted_single_file_but_keep_the_wal() {
+    let mut handler = make_error_handler();
+    let key = EncryptionKey::new([19u8; 32], None);
+    let mut backend = MemoryBackend::new("encrypted");
+    let mut db = make_sample_database(&mut handler);
+    save_database_to_backend(&mut backend, &mut db, &key, Layout::Single, &mut handler).unwrap();
+
+    let record = WalRecord {
+        sequence: 1,
+        operation: WalOperation::CreateCollection {
+            collection: db.get_collection("orders").unwrap().clone(),
+        },
+    };
+    let log = File::encode_wal(&[record], &key, &mut handler).unwrap();
+    backend.append_log(&log, &mut handler).unwrap();
+
+    let mut content = backend.data().unwrap();
+    let payload_start = content.iter().position(|&byte| byte == b'\n').unwrap() + 1;
+    let middle = payload_start + (content.len() - payload_start) / 2;
+    content[middle] ^= 0x01;
+    backend.save(&content, &mut handler).unwrap();
+
+    let (salvaged, report) = salvage_database(&mut backend, &key, &mut handler).unwrap();
+    assert!(salvaged.get_collection("users").is_none());
+    assert!(salvaged.get_collection("orders").is_some());
+    assert_eq!(report.collections, 1);
+    assert_eq!(report.documents, 0);
+    assert_eq!(report.wal_records, 1);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].location, "data file");
+}